@@ -0,0 +1,77 @@
+-- ISBN を正規化する。kernel の Isbn と同じ規則で検証し、
+-- 区切りのハイフン・空白を除いた 13 桁の ISBN-13 を返す。ISBN として正しくない場合は NULL を返す
+CREATE FUNCTION pg_temp.normalize_isbn(raw TEXT) RETURNS TEXT AS $$
+DECLARE
+    s TEXT := UPPER(REGEXP_REPLACE(raw, '[- ]', '', 'g'));
+    total INTEGER := 0;
+BEGIN
+    IF s ~ '^[0-9]{9}[0-9X]$' THEN
+        -- ISBN-10 は重みを 10 から 1 まで減らしながら掛けた和が 11 の倍数になる
+        FOR i IN 1..10 LOOP
+            total := total + (11 - i) * CASE
+                WHEN SUBSTRING(s FROM i FOR 1) = 'X' THEN 10
+                ELSE SUBSTRING(s FROM i FOR 1)::INTEGER
+            END;
+        END LOOP;
+        IF total % 11 <> 0 THEN
+            RETURN NULL;
+        END IF;
+        -- 先頭に 978 を付け、チェックディジットは下で計算し直す
+        s := '978' || LEFT(s, 9);
+    ELSIF s !~ '^97[89][0-9]{10}$' THEN
+        RETURN NULL;
+    END IF;
+
+    -- ISBN-13 は奇数桁に 1、偶数桁に 3 を掛けた和が 10 の倍数になる
+    total := 0;
+    FOR i IN 1..12 LOOP
+        total := total + SUBSTRING(s FROM i FOR 1)::INTEGER * CASE WHEN i % 2 = 1 THEN 1 ELSE 3 END;
+    END LOOP;
+    IF LENGTH(s) = 13 AND RIGHT(s, 1)::INTEGER <> (10 - total % 10) % 10 THEN
+        RETURN NULL;
+    END IF;
+    RETURN LEFT(s, 12) || ((10 - total % 10) % 10)::TEXT;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- 正規化できなかった ISBN や、正規化すると他の蔵書と重複する ISBN の印
+-- 'invalid' は ISBN として正しくないもの、'duplicate' は同じ ISBN の蔵書がほかにあるもの
+-- 印の付いた蔵書の ISBN は元の値のまま残し、ISBN を更新すると印を外す
+ALTER TABLE books ADD COLUMN isbn_issue VARCHAR(16);
+
+-- 正規化後の ISBN が同じ蔵書のうち、元から正規化済みのもの、次に登録の古いものを残し、
+-- それ以外には 'duplicate' の印を付ける
+WITH normalized AS (
+    SELECT
+        book_id,
+        pg_temp.normalize_isbn(isbn) AS isbn,
+        ROW_NUMBER() OVER (
+            PARTITION BY pg_temp.normalize_isbn(isbn)
+            ORDER BY (isbn = pg_temp.normalize_isbn(isbn)) DESC, created_at ASC, book_id ASC
+        ) AS rn
+    FROM books
+)
+UPDATE books AS b
+SET
+    isbn = CASE WHEN n.isbn IS NOT NULL AND n.rn = 1 THEN n.isbn ELSE b.isbn END,
+    isbn_issue = CASE
+        WHEN n.isbn IS NULL THEN 'invalid'
+        WHEN n.rn > 1 THEN 'duplicate'
+    END
+FROM normalized AS n
+WHERE n.book_id = b.book_id;
+
+-- 印を付けた蔵書を報告する
+DO $$
+DECLARE
+    r RECORD;
+BEGIN
+    FOR r IN SELECT book_id, isbn, isbn_issue FROM books WHERE isbn_issue IS NOT NULL ORDER BY isbn_issue, isbn LOOP
+        RAISE NOTICE 'book % has % ISBN: %', r.book_id, r.isbn_issue, r.isbn;
+    END LOOP;
+END;
+$$;
+
+-- 同じ ISBN の蔵書を重複して登録できないようにする
+-- 印の付いた蔵書は、ISBN を直すまでこの制約の対象から外す
+CREATE UNIQUE INDEX IF NOT EXISTS books_isbn_key ON books(isbn) WHERE isbn_issue IS NULL;
@@ -0,0 +1,48 @@
+-- books テーブルは書誌情報を表すものとし、実際に貸し出す冊子を book_copies テーブルで管理する
+CREATE TABLE IF NOT EXISTS book_copies (
+    copy_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    barcode VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_copies_book_id_idx ON book_copies(book_id);
+
+CREATE TRIGGER book_copies_updated_at_trigger
+    BEFORE UPDATE ON book_copies FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 登録済みの蔵書はそれぞれ 1 冊の冊子を持つものとして移行する
+-- バーコードは冊子 ID から生成する
+INSERT INTO book_copies (copy_id, book_id, barcode, created_at)
+SELECT id, book_id, REPLACE(id::text, '-', ''), created_at
+FROM (SELECT gen_random_uuid() AS id, book_id, created_at FROM books) AS b;
+
+-- 貸出は蔵書ではなく冊子に対して行う
+-- 同じ蔵書の別の冊子は同時に貸し出せるよう、一意制約を book_id から copy_id に移す
+ALTER TABLE checkouts ADD COLUMN copy_id UUID;
+UPDATE checkouts AS c
+SET copy_id = bc.copy_id
+FROM book_copies AS bc
+WHERE bc.book_id = c.book_id;
+ALTER TABLE checkouts ALTER COLUMN copy_id SET NOT NULL;
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_book_id_key;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_key UNIQUE (copy_id);
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_fkey
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS checkouts_book_id_idx ON checkouts(book_id);
+
+-- 返却済みの貸出にも冊子 ID を記録する
+-- 削除済みの蔵書に対する履歴は冊子を特定できないため NULL のままとする
+ALTER TABLE returned_checkouts ADD COLUMN copy_id UUID;
+UPDATE returned_checkouts AS rc
+SET copy_id = bc.copy_id
+FROM book_copies AS bc
+WHERE bc.book_id = rc.book_id;
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS book_tags;
+
+DROP TRIGGER IF EXISTS tags_updated_at_trigger ON tags;
+DROP TABLE IF EXISTS tags;
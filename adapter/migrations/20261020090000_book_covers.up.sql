@@ -0,0 +1,2 @@
+-- 表紙画像の形式。表紙画像が登録されていない場合は NULL
+ALTER TABLE books ADD COLUMN cover_format VARCHAR(16);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS books_isbn_key;
+CREATE UNIQUE INDEX books_isbn_key ON books(isbn) WHERE isbn_issue IS NULL;
+
+ALTER TABLE books DROP COLUMN IF EXISTS deleted_at;
@@ -0,0 +1,6 @@
+-- 蔵書を論理削除した日時。削除されていない場合は NULL
+ALTER TABLE books ADD COLUMN deleted_at TIMESTAMP(3) WITH TIME ZONE;
+
+-- 削除済みの蔵書と同じ ISBN の蔵書は登録し直せるようにする
+DROP INDEX IF EXISTS books_isbn_key;
+CREATE UNIQUE INDEX books_isbn_key ON books(isbn) WHERE deleted_at IS NULL AND isbn_issue IS NULL;
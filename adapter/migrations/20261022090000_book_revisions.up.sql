@@ -0,0 +1,25 @@
+-- 蔵書の書誌情報の版。登録・更新のたびに、変更後の内容を 1 版として記録する
+CREATE TABLE IF NOT EXISTS book_revisions (
+    book_id UUID NOT NULL,
+    revision INTEGER NOT NULL,
+    -- 変更したユーザー。ユーザーが削除された場合は NULL
+    user_id UUID,
+    title VARCHAR(255) NOT NULL,
+    author VARCHAR(255) NOT NULL,
+    isbn VARCHAR(255) NOT NULL,
+    description VARCHAR(1024) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    PRIMARY KEY (book_id, revision),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL
+);
+
+-- 登録済みの蔵書は、現在の内容を最初の版とする
+INSERT INTO book_revisions (book_id, revision, user_id, title, author, isbn, description, created_at)
+SELECT book_id, 1, user_id, title, author, isbn, description, updated_at
+FROM books;
@@ -0,0 +1,20 @@
+-- 蔵書の所有権の譲渡の申し出。受け取る側が承認すると所有者が変わる
+-- 蔵書ごとに申し出は 1 件までとし、申し出をし直した場合は置き換える
+CREATE TABLE IF NOT EXISTS book_transfers (
+    book_id UUID PRIMARY KEY,
+    from_user_id UUID NOT NULL,
+    to_user_id UUID NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (from_user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (to_user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_transfers_to_user_id_idx ON book_transfers(to_user_id);
@@ -0,0 +1,66 @@
+-- 著者。表記の揺れで同じ人物が分かれないよう、名前ごとに 1 件だけ登録する
+CREATE TABLE IF NOT EXISTS authors (
+    author_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+CREATE TRIGGER authors_updated_at_trigger
+    BEFORE UPDATE ON authors FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 蔵書と著者の多対多の関連。著者の役割と、蔵書ごとの著者の並び順を持つ
+-- books.author には、表示や検索・並び替えのために著者の一覧をまとめた文字列を持つ
+CREATE TABLE IF NOT EXISTS book_authors (
+    book_id UUID NOT NULL,
+    author_id UUID NOT NULL,
+    -- 'author'・'editor'・'translator' のいずれか
+    role VARCHAR(32) NOT NULL,
+    position INTEGER NOT NULL,
+
+    PRIMARY KEY (book_id, author_id, role),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(author_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS book_authors_author_id_idx ON book_authors(author_id);
+
+-- 各版の著者。版を戻す際に、著者の一覧も合わせて戻すために使う
+CREATE TABLE IF NOT EXISTS book_revision_authors (
+    book_id UUID NOT NULL,
+    revision INTEGER NOT NULL,
+    author_id UUID NOT NULL,
+    role VARCHAR(32) NOT NULL,
+    position INTEGER NOT NULL,
+
+    PRIMARY KEY (book_id, revision, author_id, role),
+    FOREIGN KEY (book_id, revision) REFERENCES book_revisions(book_id, revision)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES authors(author_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+-- 登録済みの蔵書と版の著者名は、それぞれ 1 人の著者として登録する
+-- 「初田直也他」のような表記から複数の著者を正しく切り出すことはできないため、分割はしない
+INSERT INTO authors (name)
+SELECT DISTINCT BTRIM(author) FROM books WHERE BTRIM(author) <> ''
+UNION
+SELECT DISTINCT BTRIM(author) FROM book_revisions WHERE BTRIM(author) <> ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO book_authors (book_id, author_id, role, position)
+SELECT b.book_id, a.author_id, 'author', 0
+FROM books AS b
+INNER JOIN authors AS a ON a.name = BTRIM(b.author);
+
+INSERT INTO book_revision_authors (book_id, revision, author_id, role, position)
+SELECT r.book_id, r.revision, a.author_id, 'author', 0
+FROM book_revisions AS r
+INNER JOIN authors AS a ON a.name = BTRIM(r.author);
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS books_series_id_idx;
+ALTER TABLE books
+    DROP CONSTRAINT IF EXISTS books_series_volume_check,
+    DROP COLUMN IF EXISTS series_volume,
+    DROP COLUMN IF EXISTS series_id;
+
+DROP TRIGGER IF EXISTS series_updated_at_trigger ON series;
+DROP TABLE IF EXISTS series;
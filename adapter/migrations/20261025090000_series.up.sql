@@ -0,0 +1,21 @@
+-- 複数の巻からなる蔵書のまとまり。タイトルごとに 1 件だけ登録する
+CREATE TABLE IF NOT EXISTS series (
+    series_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+);
+
+CREATE TRIGGER series_updated_at_trigger
+    BEFORE UPDATE ON series FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 蔵書が属するシリーズと巻数。シリーズに属さない蔵書はどちらも NULL とする
+ALTER TABLE books
+    ADD COLUMN series_id UUID REFERENCES series(series_id)
+        ON UPDATE CASCADE,
+    ADD COLUMN series_volume INTEGER,
+    ADD CONSTRAINT books_series_volume_check
+        CHECK ((series_id IS NULL) = (series_volume IS NULL));
+
+CREATE INDEX IF NOT EXISTS books_series_id_idx ON books(series_id, series_volume);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS book_copies_location_id_idx;
+ALTER TABLE book_copies DROP COLUMN IF EXISTS location_id;
+
+DROP VIEW IF EXISTS location_paths;
+DROP TRIGGER IF EXISTS locations_updated_at_trigger ON locations;
+DROP TABLE IF EXISTS locations;
@@ -0,0 +1,52 @@
+-- 冊子を置く場所。建物・部屋・書架の順に親子関係を持つ階層で管理する
+-- 同じ親の下には同じ名前の場所を登録できない
+CREATE TABLE IF NOT EXISTS locations (
+    location_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    parent_id UUID REFERENCES locations(location_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT,
+    name VARCHAR(255) NOT NULL,
+    kind VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    CONSTRAINT locations_parent_id_name_key UNIQUE NULLS NOT DISTINCT (parent_id, name)
+);
+
+CREATE TRIGGER locations_updated_at_trigger
+    BEFORE UPDATE ON locations FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
+
+-- 各場所について、最上位の建物からその場所までの名前と ID を並べたもの
+-- ancestor_ids は自身の ID も含むため、ある場所の配下にあるかの判定に使える
+CREATE OR REPLACE VIEW location_paths AS
+WITH RECURSIVE tree AS (
+    SELECT
+        location_id,
+        parent_id,
+        name,
+        kind,
+        ARRAY[name]::TEXT[] AS path,
+        ARRAY[location_id] AS ancestor_ids
+    FROM locations
+    WHERE parent_id IS NULL
+    UNION ALL
+    SELECT
+        l.location_id,
+        l.parent_id,
+        l.name,
+        l.kind,
+        t.path || l.name::TEXT,
+        t.ancestor_ids || l.location_id
+    FROM locations AS l
+    INNER JOIN tree AS t ON l.parent_id = t.location_id
+)
+SELECT * FROM tree;
+
+-- 冊子が現在置かれている場所。未設定の場合は NULL とする
+ALTER TABLE book_copies
+    ADD COLUMN location_id UUID REFERENCES locations(location_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS book_copies_location_id_idx ON book_copies(location_id);
@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS damage_reports_updated_at_trigger ON damage_reports;
+DROP TABLE IF EXISTS damage_reports;
+
+ALTER TABLE book_copies
+    DROP COLUMN IF EXISTS withdrawn_at,
+    DROP COLUMN IF EXISTS condition;
@@ -0,0 +1,44 @@
+-- 冊子の状態と、貸出の対象から外しているかどうか
+-- 修理中などで貸し出さない冊子は withdrawn_at に外した日時を入れる
+ALTER TABLE book_copies
+    ADD COLUMN condition VARCHAR(32) NOT NULL DEFAULT 'good',
+    ADD COLUMN withdrawn_at TIMESTAMP(3) WITH TIME ZONE;
+
+-- 借りた冊子の破損の報告。報告は貸出ごとに行い、管理者が対応したら resolved_at を入れる
+-- 貸出は返却時に returned_checkouts へ移るため、checkout_id には外部キーを張らない
+CREATE TABLE IF NOT EXISTS damage_reports (
+    damage_report_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    checkout_id UUID NOT NULL,
+    book_id UUID NOT NULL,
+    copy_id UUID NOT NULL,
+    reported_by UUID NOT NULL,
+    description TEXT NOT NULL,
+    -- 写真が添付されていない場合は NULL
+    photo_format VARCHAR(16),
+    resolved_by UUID,
+    resolution_note TEXT,
+    resolved_at TIMESTAMP(3) WITH TIME ZONE,
+    created_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (reported_by) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (resolved_by) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS damage_reports_checkout_id_idx ON damage_reports(checkout_id);
+CREATE INDEX IF NOT EXISTS damage_reports_open_idx
+    ON damage_reports(created_at) WHERE resolved_at IS NULL;
+
+CREATE TRIGGER damage_reports_updated_at_trigger
+    BEFORE UPDATE ON damage_reports FOR EACH ROW
+    EXECUTE PROCEDURE set_updated_at();
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS book_reviews_updated_at_trigger ON book_reviews;
+DROP TABLE IF EXISTS book_reviews;
@@ -0,0 +1,30 @@
+-- 貸出の履歴から定期的に集計する、一緒に借りられている蔵書
+-- score は両方の蔵書を借りたことのあるユーザーの数
+CREATE TABLE IF NOT EXISTS related_books (
+    book_id UUID NOT NULL,
+    related_book_id UUID NOT NULL,
+    score INTEGER NOT NULL,
+
+    PRIMARY KEY (book_id, related_book_id),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (related_book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+-- ユーザーごとのおすすめの蔵書。ユーザーが借りた蔵書の related_books の score を合計したもの
+CREATE TABLE IF NOT EXISTS book_recommendations (
+    user_id UUID NOT NULL,
+    book_id UUID NOT NULL,
+    score INTEGER NOT NULL,
+
+    PRIMARY KEY (user_id, book_id),
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
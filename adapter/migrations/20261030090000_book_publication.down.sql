@@ -0,0 +1,4 @@
+ALTER TABLE books
+    DROP CONSTRAINT IF EXISTS books_published_year_check,
+    DROP COLUMN IF EXISTS published_year,
+    DROP COLUMN IF EXISTS publisher;
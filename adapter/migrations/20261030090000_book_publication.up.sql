@@ -0,0 +1,6 @@
+-- 出版社と出版年。引用の書き出しに使う。不明な場合は NULL
+ALTER TABLE books
+    ADD COLUMN publisher VARCHAR(255),
+    ADD COLUMN published_year INTEGER,
+    ADD CONSTRAINT books_published_year_check
+        CHECK (published_year BETWEEN 1 AND 9999);
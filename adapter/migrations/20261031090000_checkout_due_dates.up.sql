@@ -0,0 +1,13 @@
+-- 貸出の返却期限。貸出時に既定の貸出期間から決める
+-- 既存の貸出は、貸出日から既定の貸出期間（14 日）後を返却期限とする
+-- CHECKOUT_LOAN_PERIOD_DAYS を既定値から変えている場合も 14 日で埋めるため、
+-- 必要であれば移行後に due_at を更新すること
+ALTER TABLE checkouts ADD COLUMN due_at TIMESTAMP(3) WITH TIME ZONE;
+UPDATE checkouts SET due_at = checked_out_at + INTERVAL '14 days';
+ALTER TABLE checkouts ALTER COLUMN due_at SET NOT NULL;
+
+ALTER TABLE returned_checkouts ADD COLUMN due_at TIMESTAMP(3) WITH TIME ZONE;
+UPDATE returned_checkouts SET due_at = checked_out_at + INTERVAL '14 days';
+ALTER TABLE returned_checkouts ALTER COLUMN due_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS checkouts_due_at_idx ON checkouts(due_at);
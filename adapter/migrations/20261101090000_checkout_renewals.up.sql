@@ -0,0 +1,17 @@
+-- 貸出の延長の記録。延長するたびに 1 行追加し、延長後の返却期限を due_at に入れる
+-- 貸出は返却時に returned_checkouts へ移るため、checkout_id には外部キーを張らない
+CREATE TABLE IF NOT EXISTS checkout_renewals (
+    renewal_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    checkout_id UUID NOT NULL,
+    renewed_by UUID NOT NULL,
+    previous_due_at TIMESTAMP(3) WITH TIME ZONE NOT NULL,
+    due_at TIMESTAMP(3) WITH TIME ZONE NOT NULL,
+    renewed_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    FOREIGN KEY (renewed_by) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS checkout_renewals_checkout_id_idx
+    ON checkout_renewals(checkout_id, renewed_at);
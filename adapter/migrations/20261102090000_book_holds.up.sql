@@ -0,0 +1,16 @@
+-- 蔵書の予約。予約したユーザーが貸し出すと削除する
+-- ほかのユーザーの予約がある蔵書の貸出は延長できない
+CREATE TABLE IF NOT EXISTS book_holds (
+    hold_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    book_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    held_at TIMESTAMP(3) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+
+    UNIQUE (book_id, user_id),
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
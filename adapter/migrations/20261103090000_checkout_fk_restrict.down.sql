@@ -0,0 +1,11 @@
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_book_id_fkey;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_book_id_fkey
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE;
+
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_copy_id_fkey;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_fkey
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE;
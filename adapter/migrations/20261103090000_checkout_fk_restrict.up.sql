@@ -0,0 +1,13 @@
+-- 貸し出し中の冊子や蔵書が削除されると、貸出がまとめて消えてしまう
+-- 貸し出し中のものはデータベース側でも削除できないよう、外部キーを RESTRICT に変更する
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_copy_id_fkey;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_copy_id_fkey
+    FOREIGN KEY (copy_id) REFERENCES book_copies(copy_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT;
+
+ALTER TABLE checkouts DROP CONSTRAINT IF EXISTS checkouts_book_id_fkey;
+ALTER TABLE checkouts ADD CONSTRAINT checkouts_book_id_fkey
+    FOREIGN KEY (book_id) REFERENCES books(book_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT;
@@ -0,0 +1,3 @@
+ALTER TABLE book_revisions
+    DROP COLUMN IF EXISTS published_year,
+    DROP COLUMN IF EXISTS publisher;
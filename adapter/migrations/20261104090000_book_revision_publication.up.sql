@@ -0,0 +1,11 @@
+-- 出版社と出版年も版として記録し、版を戻したときに元に戻せるようにする
+ALTER TABLE book_revisions
+    ADD COLUMN publisher VARCHAR(255),
+    ADD COLUMN published_year INTEGER;
+
+-- これまでの版には出版社と出版年の変更が記録されていないため、
+-- 過去の版もすべて現在の値だったものとして扱う
+UPDATE book_revisions AS r
+SET publisher = b.publisher, published_year = b.published_year
+FROM books AS b
+WHERE b.book_id = r.book_id;
@@ -0,0 +1,4 @@
+-- 表紙画像のファイルはアップロードごとに別のキーに保存し、どのファイルが現在の表紙かをこの ID で表す
+-- 登録をコミットしてから古いファイルを削除することで、コミットに失敗しても元の表紙が残るようにする
+-- この列を追加する前に登録した表紙画像は NULL のままとし、ID を含まないキーのファイルを使う
+ALTER TABLE books ADD COLUMN cover_id UUID;
@@ -0,0 +1,7 @@
+-- ユーザーを削除すると、そのユーザーが所有する蔵書もまとめて消えてしまう
+-- 蔵書を持つユーザーは削除できないよう外部キーを RESTRICT に変更し、先に所有者を付け替えてもらう
+ALTER TABLE books DROP CONSTRAINT IF EXISTS books_user_id_fkey;
+ALTER TABLE books ADD CONSTRAINT books_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE RESTRICT;
@@ -1,9 +1,22 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use kernel::model::{
-    book::{Book, Checkout},
-    id::{BookId, CheckoutId, UserId},
-    user::{BookOwner, CheckoutUser},
+    author::BookAuthor,
+    book::{
+        duplicate::{DuplicateCandidate, DuplicateReason},
+        revision::{BookField, BookFieldChange, BookRevision},
+        Book, BookCopy, Checkout, CopyCondition,
+    },
+    id::{BookId, CheckoutId, CopyId, LocationId, SeriesId, UserId},
+    location::Location,
+    series::BookSeries,
+    tag::Tag,
+    user::{BookEditor, BookOwner, CheckoutUser},
 };
+use shared::error::{AppError, AppResult};
+
+use super::location::LocationRow;
 
 pub struct BookRow {
     pub book_id: BookId,
@@ -11,48 +24,139 @@ pub struct BookRow {
     pub author: String,
     pub isbn: String,
     pub description: String,
+    pub publisher: Option<String>,
+    pub published_year: Option<i32>,
     // 蔵書の所有者のID、名前
     pub owned_by: UserId,
     pub owner_name: String,
+    pub total_copies: i64,
+    // 貸出の対象から外した冊子の数。これらは貸し出し中にならない
+    pub withdrawn_copies: i64,
+    pub has_cover: bool,
+    // レビューがない蔵書では average_rating は None になる
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    // シリーズに属さない蔵書ではすべて None になる
+    pub series_id: Option<SeriesId>,
+    pub series_title: Option<String>,
+    pub series_volume: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl BookRow {
-    pub fn into_book(self, checkout: Option<Checkout>) -> Book {
+    pub fn into_book(
+        self,
+        checkouts: Vec<Checkout>,
+        tags: Vec<Tag>,
+        authors: Vec<BookAuthor>,
+    ) -> Book {
         let BookRow {
             book_id,
             title,
             author,
             isbn,
             description,
+            publisher,
+            published_year,
             owned_by,
             owner_name,
+            total_copies,
+            withdrawn_copies,
+            has_cover,
+            average_rating,
+            review_count,
+            series_id,
+            series_title,
+            series_volume,
+            updated_at,
+            deleted_at,
         } = self;
+        let series = match (series_id, series_title, series_volume) {
+            (Some(id), Some(title), Some(volume)) => Some(BookSeries { id, title, volume }),
+            _ => None,
+        };
         Book {
             id: book_id,
             title,
             author,
+            authors,
             isbn,
             description,
+            publisher,
+            published_year,
             owner: BookOwner {
                 id: owned_by,
                 name: owner_name,
             },
-            checkout,
+            total_copies,
+            available_copies: total_copies - withdrawn_copies - checkouts.len() as i64,
+            checkouts,
+            tags,
+            series,
+            has_cover,
+            average_rating,
+            review_count,
+            updated_at,
+            deleted_at,
         }
     }
 }
 
+#[derive(sqlx::FromRow)]
 pub struct PagenatedBookRow {
     pub total: i64,
     pub id: BookId,
 }
 
+// カーソルによるページネーションで、蔵書 ID と並び替えのキーの値を取得する際に使う型
+#[derive(sqlx::FromRow)]
+pub struct BookCursorRow {
+    pub id: BookId,
+    pub keys: Vec<String>,
+}
+
+// 重複の候補として取得した蔵書
+pub struct DuplicateCandidateRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub isbn_match: bool,
+}
+
+impl From<DuplicateCandidateRow> for DuplicateCandidate {
+    fn from(value: DuplicateCandidateRow) -> Self {
+        let DuplicateCandidateRow {
+            book_id,
+            title,
+            author,
+            isbn,
+            isbn_match,
+        } = value;
+        DuplicateCandidate {
+            book_id,
+            title,
+            author,
+            isbn,
+            reason: if isbn_match {
+                DuplicateReason::Isbn
+            } else {
+                DuplicateReason::TitleAndAuthor
+            },
+        }
+    }
+}
+
 pub struct BookCheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub barcode: String,
     pub user_id: UserId,
     pub user_name: String,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
 }
 
 impl From<BookCheckoutRow> for Checkout {
@@ -60,17 +164,140 @@ impl From<BookCheckoutRow> for Checkout {
         let BookCheckoutRow {
             checkout_id,
             book_id: _,
+            copy_id,
+            barcode,
             user_id,
             user_name,
             checked_out_at,
+            due_at,
         } = value;
         Checkout {
             checkout_id,
+            copy_id,
+            barcode,
             checked_out_by: CheckoutUser {
                 id: user_id,
                 name: user_name,
             },
             checked_out_at,
+            due_at,
         }
     }
 }
+
+pub struct BookCopyRow {
+    pub copy_id: CopyId,
+    pub barcode: String,
+    pub condition: String,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    // 場所が設定されていない冊子ではすべて None になる
+    pub location_id: Option<LocationId>,
+    pub location_parent_id: Option<LocationId>,
+    pub location_name: Option<String>,
+    pub location_kind: Option<String>,
+    pub location_path: Option<Vec<String>>,
+}
+
+impl BookCopyRow {
+    pub fn into_book_copy(self, checkout: Option<Checkout>) -> AppResult<BookCopy> {
+        let BookCopyRow {
+            copy_id,
+            barcode,
+            condition,
+            withdrawn_at,
+            location_id,
+            location_parent_id,
+            location_name,
+            location_kind,
+            location_path,
+        } = self;
+        let location = match (location_id, location_name, location_kind, location_path) {
+            (Some(location_id), Some(name), Some(kind), Some(path)) => {
+                Some(Location::try_from(LocationRow {
+                    location_id,
+                    parent_id: location_parent_id,
+                    name,
+                    kind,
+                    path,
+                })?)
+            }
+            _ => None,
+        };
+        Ok(BookCopy {
+            id: copy_id,
+            barcode,
+            location,
+            condition: CopyCondition::from_str(condition.as_str())
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            withdrawn_at,
+            checkout,
+        })
+    }
+}
+
+pub struct BookRevisionRow {
+    pub revision: i32,
+    pub user_id: Option<UserId>,
+    pub user_name: Option<String>,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub description: String,
+    pub publisher: Option<String>,
+    pub published_year: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BookRevisionRow {
+    fn fields(&self) -> [(BookField, Option<String>); 6] {
+        [
+            (BookField::Title, Some(self.title.clone())),
+            (BookField::Author, Some(self.author.clone())),
+            (BookField::Isbn, Some(self.isbn.clone())),
+            (BookField::Description, Some(self.description.clone())),
+            (BookField::Publisher, self.publisher.clone()),
+            (
+                BookField::PublishedYear,
+                self.published_year.map(|y| y.to_string()),
+            ),
+        ]
+    }
+}
+
+// 古い順に並んだ版の行から直前の版との差分を求め、新しい順の版の一覧にする
+pub fn into_revisions(rows: Vec<BookRevisionRow>) -> Vec<BookRevision> {
+    let mut revisions = Vec::with_capacity(rows.len());
+    let mut prev: Option<[(BookField, Option<String>); 6]> = None;
+    for row in &rows {
+        let fields = row.fields();
+        let changes = fields
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (field, after))| {
+                // 最初の版は、直前の版ではすべての項目の値がなかったものとして扱う
+                let before = prev.as_ref().and_then(|p| p[i].1.clone());
+                (before != *after).then(|| BookFieldChange {
+                    field: *field,
+                    before,
+                    after: after.clone(),
+                })
+            })
+            .collect();
+        let edited_by = match (row.user_id, &row.user_name) {
+            (Some(id), Some(name)) => Some(BookEditor {
+                id,
+                name: name.clone(),
+            }),
+            _ => None,
+        };
+        revisions.push(BookRevision {
+            revision: row.revision,
+            edited_by,
+            edited_at: row.created_at,
+            changes,
+        });
+        prev = Some(fields);
+    }
+    revisions.reverse();
+    revisions
+}
@@ -1,25 +1,48 @@
 use chrono::{DateTime, Utc};
 use kernel::model::{
-    checkout::{Checkout, CheckoutBook},
-    id::{BookId, CheckoutId, UserId},
+    checkout::{Checkout, CheckoutBook, CheckoutRenewal},
+    id::{BookId, CheckoutId, CopyId, UserId},
 };
 
 // 貸し出し状態を確認するための型
 // 蔵書が存在する場合はこの型にはまるレコードが存在し、
-// その蔵書が貸し出し中の場合は checkout_id および user_id が None でない値になる
-// 蔵書が貸し出し中でない場合は checkout_id も user_id も None
+// その蔵書に貸し出し中でない冊子がある場合は copy_id がその冊子の ID になる
+// すべての冊子が貸し出し中の場合は copy_id が None
 pub struct CheckoutStateRow {
     pub book_id: BookId,
-    pub checkout_id: Option<CheckoutId>,
+    pub copy_id: Option<CopyId>,
+}
+
+// 返却できる状態かを確認するための型
+// 蔵書が存在する場合はこの型にはまるレコードが存在し、
+// 指定した貸出がその蔵書の冊子に対して貸し出し中の場合は user_id が借りたユーザーの ID になる
+pub struct ReturnStateRow {
+    pub book_id: BookId,
     pub user_id: Option<UserId>,
 }
 
+// 延長できる状態かを確認するための型
+// 蔵書が存在する場合はこの型にはまるレコードが存在し、
+// 指定した貸出がその蔵書の冊子に対して貸し出し中の場合は user_id と due_at に値が入る
+pub struct RenewStateRow {
+    pub book_id: BookId,
+    pub user_id: Option<UserId>,
+    pub due_at: Option<DateTime<Utc>>,
+    // これまでに延長した回数
+    pub renewal_count: i64,
+    // 借りたユーザー以外のユーザーがこの蔵書を予約しているか
+    pub held_by_others: bool,
+}
+
 // 貸し出し中の一覧を取得する際に使う型
 pub struct CheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub barcode: String,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
     pub title: String,
     pub author: String,
     pub isbn: String,
@@ -30,8 +53,11 @@ impl From<CheckoutRow> for Checkout {
         let CheckoutRow {
             checkout_id,
             book_id,
+            copy_id,
+            barcode,
             user_id,
             checked_out_at,
+            due_at,
             title,
             author,
             isbn,
@@ -40,24 +66,33 @@ impl From<CheckoutRow> for Checkout {
             id: checkout_id,
             checked_out_by: user_id,
             checked_out_at,
+            due_at,
             // 未返却なので、returned_at は None を入れる
             returned_at: None,
             book: CheckoutBook {
                 book_id,
+                copy_id: Some(copy_id),
+                barcode: Some(barcode),
                 title,
                 author,
                 isbn,
             },
+            // 延長の記録は別途取得して設定する
+            renewals: Vec::new(),
         }
     }
 }
 
 // 返却済みの貸し出し一覧を取得する際に使う型
+// 返却後に冊子が削除された場合などは copy_id・barcode が None になる
 pub struct ReturnedCheckoutRow {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
     pub user_id: UserId,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
     pub returned_at: DateTime<Utc>,
     pub title: String,
     pub author: String,
@@ -69,8 +104,11 @@ impl From<ReturnedCheckoutRow> for Checkout {
         let ReturnedCheckoutRow {
             checkout_id,
             book_id,
+            copy_id,
+            barcode,
             user_id,
             checked_out_at,
+            due_at,
             returned_at,
             title,
             author,
@@ -80,13 +118,92 @@ impl From<ReturnedCheckoutRow> for Checkout {
             id: checkout_id,
             checked_out_by: user_id,
             checked_out_at,
+            due_at,
             returned_at: Some(returned_at),
             book: CheckoutBook {
                 book_id,
+                copy_id,
+                barcode,
+                title,
+                author,
+                isbn,
+            },
+            // 延長の記録は別途取得して設定する
+            renewals: Vec::new(),
+        }
+    }
+}
+
+// 貸出中・返却済みをまとめた貸出履歴を取得する際に使う型
+// 貸出中の場合は returned_at が None になる
+pub struct CheckoutHistoryRow {
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
+    pub user_id: UserId,
+    pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+}
+
+impl From<CheckoutHistoryRow> for Checkout {
+    fn from(value: CheckoutHistoryRow) -> Self {
+        let CheckoutHistoryRow {
+            checkout_id,
+            book_id,
+            copy_id,
+            barcode,
+            user_id,
+            checked_out_at,
+            due_at,
+            returned_at,
+            title,
+            author,
+            isbn,
+        } = value;
+        Checkout {
+            id: checkout_id,
+            checked_out_by: user_id,
+            checked_out_at,
+            due_at,
+            returned_at,
+            book: CheckoutBook {
+                book_id,
+                copy_id,
+                barcode,
                 title,
                 author,
                 isbn,
             },
+            // 延長の記録は別途取得して設定する
+            renewals: Vec::new(),
+        }
+    }
+}
+
+pub struct CheckoutRenewalRow {
+    pub checkout_id: CheckoutId,
+    pub renewed_at: DateTime<Utc>,
+    pub previous_due_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+}
+
+impl From<CheckoutRenewalRow> for CheckoutRenewal {
+    fn from(value: CheckoutRenewalRow) -> Self {
+        let CheckoutRenewalRow {
+            renewed_at,
+            previous_due_at,
+            due_at,
+            ..
+        } = value;
+        CheckoutRenewal {
+            renewed_at,
+            previous_due_at,
+            due_at,
         }
     }
 }
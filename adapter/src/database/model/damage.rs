@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    cover::CoverImageFormat,
+    damage::{DamageReport, DamageResolution},
+    id::{BookId, CheckoutId, CopyId, DamageReportId, UserId},
+    user::DamageReporter,
+};
+use shared::error::AppError;
+
+pub struct DamageReportRow {
+    pub damage_report_id: DamageReportId,
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub title: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
+    pub reported_by: UserId,
+    pub reporter_name: String,
+    pub description: String,
+    pub photo_format: Option<String>,
+    pub created_at: DateTime<Utc>,
+    // 対応済みでない報告ではすべて None になる
+    pub resolved_by: Option<UserId>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<DamageReportRow> for DamageReport {
+    type Error = AppError;
+    fn try_from(value: DamageReportRow) -> Result<Self, Self::Error> {
+        let DamageReportRow {
+            damage_report_id,
+            checkout_id,
+            book_id,
+            title,
+            copy_id,
+            barcode,
+            reported_by,
+            reporter_name,
+            description,
+            photo_format,
+            created_at,
+            resolved_by,
+            resolution_note,
+            resolved_at,
+        } = value;
+        let photo_format = photo_format
+            .map(|f| f.parse::<CoverImageFormat>())
+            .transpose()
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+        let resolution = resolved_at.map(|resolved_at| DamageResolution {
+            resolved_by,
+            note: resolution_note.unwrap_or_default(),
+            resolved_at,
+        });
+        Ok(DamageReport {
+            id: damage_report_id,
+            checkout_id,
+            book_id,
+            book_title: title,
+            copy_id,
+            barcode,
+            reported_by: DamageReporter {
+                id: reported_by,
+                name: reporter_name,
+            },
+            description,
+            photo_format,
+            reported_at: created_at,
+            resolution,
+        })
+    }
+}
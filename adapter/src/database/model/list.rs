@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use kernel::model::list::{Cursor, PageCursor};
+use shared::error::{AppError, AppResult};
+use uuid::Uuid;
+
+// 日時と ID の組で並べる一覧（貸出一覧やユーザー一覧）のカーソルの中身
+pub struct TimestampCursor {
+    pub at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl TimestampCursor {
+    pub fn into_cursor(self, order_by: &str) -> Cursor {
+        Cursor {
+            order_by: order_by.into(),
+            keys: vec![
+                self.at.to_rfc3339_opts(SecondsFormat::Micros, true),
+                self.id.to_string(),
+            ],
+        }
+    }
+
+    fn from_cursor(cursor: &Cursor, order_by: &str) -> AppResult<Self> {
+        let invalid = || AppError::InvalidCursorError("不正なカーソルです。".into());
+        if cursor.order_by != order_by {
+            return Err(AppError::InvalidCursorError(
+                "並び順が異なるカーソルは使用できません。".into(),
+            ));
+        }
+        match cursor.keys.as_slice() {
+            [at, id] => Ok(Self {
+                at: DateTime::parse_from_rfc3339(at)
+                    .map_err(|_| invalid())?
+                    .with_timezone(&Utc),
+                id: Uuid::from_str(id).map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    // PageCursor を (after, before) の組に分解する
+    // 静的なクエリに渡しやすいよう、指定されていない方は None とする
+    pub fn split(
+        cursor: Option<&PageCursor>,
+        order_by: &str,
+    ) -> AppResult<(Option<Self>, Option<Self>)> {
+        match cursor {
+            None => Ok((None, None)),
+            Some(PageCursor::After(c)) => Ok((Some(Self::from_cursor(c, order_by)?), None)),
+            Some(PageCursor::Before(c)) => Ok((None, Some(Self::from_cursor(c, order_by)?))),
+        }
+    }
+}
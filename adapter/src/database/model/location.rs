@@ -0,0 +1,36 @@
+use std::str::FromStr;
+
+use kernel::model::{
+    id::LocationId,
+    location::{Location, LocationKind},
+};
+use shared::error::AppError;
+
+pub struct LocationRow {
+    pub location_id: LocationId,
+    pub parent_id: Option<LocationId>,
+    pub name: String,
+    pub kind: String,
+    pub path: Vec<String>,
+}
+
+impl TryFrom<LocationRow> for Location {
+    type Error = AppError;
+    fn try_from(value: LocationRow) -> Result<Self, Self::Error> {
+        let LocationRow {
+            location_id,
+            parent_id,
+            name,
+            kind,
+            path,
+        } = value;
+        Ok(Location {
+            id: location_id,
+            parent_id,
+            name,
+            kind: LocationKind::from_str(kind.as_str())
+                .map_err(|e| AppError::ConversionEntityError(e.to_string()))?,
+            path,
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use kernel::model::{book::metadata::BookMetadata, isbn::Isbn};
+use serde::{Deserialize, Serialize};
+use shared::error::AppError;
+
+use crate::redis::model::{RedisKey, RedisValue};
+
+// 書誌情報のキャッシュのキー。ISBN ごとに 1 件保存する
+pub struct BookMetadataKey(String);
+
+impl From<&Isbn> for BookMetadataKey {
+    fn from(isbn: &Isbn) -> Self {
+        Self(format!("book-metadata:{isbn}"))
+    }
+}
+
+// 該当する書誌情報がなかったことも含めてキャッシュするため Option で持つ
+#[derive(Serialize, Deserialize)]
+pub struct CachedBookMetadata(Option<BookMetadataItem>);
+
+#[derive(Serialize, Deserialize)]
+pub struct BookMetadataItem {
+    isbn: String,
+    title: Option<String>,
+    // 著者が 1 人だった頃のキャッシュは、著者なしとして読み込む
+    #[serde(default)]
+    authors: Vec<String>,
+    description: Option<String>,
+}
+
+impl RedisKey for BookMetadataKey {
+    type Value = CachedBookMetadata;
+
+    fn inner(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl RedisValue for CachedBookMetadata {
+    fn inner(&self) -> String {
+        // 文字列と Option だけからなる型なので失敗しない
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl TryFrom<String> for CachedBookMetadata {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value).map_err(|e| AppError::ConversionEntityError(e.to_string()))
+    }
+}
+
+impl From<Option<BookMetadata>> for CachedBookMetadata {
+    fn from(value: Option<BookMetadata>) -> Self {
+        Self(value.map(|m| BookMetadataItem {
+            isbn: m.isbn.into_inner(),
+            title: m.title,
+            authors: m.authors,
+            description: m.description,
+        }))
+    }
+}
+
+impl TryFrom<CachedBookMetadata> for Option<BookMetadata> {
+    type Error = AppError;
+
+    fn try_from(value: CachedBookMetadata) -> Result<Self, Self::Error> {
+        value
+            .0
+            .map(|item| {
+                Ok(BookMetadata {
+                    isbn: item.isbn.parse()?,
+                    title: item.title,
+                    authors: item.authors,
+                    description: item.description,
+                })
+            })
+            .transpose()
+    }
+}
@@ -1,4 +1,14 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod damage;
+pub mod list;
+pub mod location;
+pub mod metadata;
+pub mod recommendation;
+pub mod review;
+pub mod series;
+pub mod tag;
+pub mod transfer;
 pub mod user;
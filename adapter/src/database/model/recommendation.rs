@@ -0,0 +1,28 @@
+use kernel::model::{id::BookId, recommendation::RecommendedBook};
+
+pub struct RecommendedBookRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub has_cover: bool,
+    pub score: i32,
+}
+
+impl From<RecommendedBookRow> for RecommendedBook {
+    fn from(value: RecommendedBookRow) -> Self {
+        let RecommendedBookRow {
+            book_id,
+            title,
+            author,
+            has_cover,
+            score,
+        } = value;
+        RecommendedBook {
+            book_id,
+            title,
+            author,
+            has_cover,
+            score: score.into(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, ReviewId, UserId},
+    review::BookReview,
+    user::Reviewer,
+};
+
+pub struct BookReviewRow {
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<BookReviewRow> for BookReview {
+    fn from(value: BookReviewRow) -> Self {
+        let BookReviewRow {
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        BookReview {
+            id: review_id,
+            book_id,
+            reviewer: Reviewer {
+                id: user_id,
+                name: user_name,
+            },
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+pub struct PaginatedBookReviewRow {
+    pub total: i64,
+    pub review_id: ReviewId,
+    pub book_id: BookId,
+    pub user_id: UserId,
+    pub user_name: String,
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PaginatedBookReviewRow> for BookReview {
+    fn from(value: PaginatedBookReviewRow) -> Self {
+        let PaginatedBookReviewRow {
+            total: _,
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        BookReviewRow {
+            review_id,
+            book_id,
+            user_id,
+            user_name,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+        .into()
+    }
+}
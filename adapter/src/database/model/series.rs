@@ -0,0 +1,50 @@
+use kernel::model::{
+    id::{BookId, SeriesId},
+    series::{Series, SeriesVolume},
+};
+
+pub struct SeriesRow {
+    pub series_id: SeriesId,
+    pub title: String,
+}
+
+impl SeriesRow {
+    pub fn into_series(self, volumes: Vec<SeriesVolume>) -> Series {
+        let SeriesRow { series_id, title } = self;
+        Series {
+            id: series_id,
+            title,
+            volumes,
+        }
+    }
+}
+
+pub struct SeriesVolumeRow {
+    pub volume: i32,
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub total_copies: i64,
+    pub available_copies: i64,
+}
+
+impl From<SeriesVolumeRow> for SeriesVolume {
+    fn from(value: SeriesVolumeRow) -> Self {
+        let SeriesVolumeRow {
+            volume,
+            book_id,
+            title,
+            author,
+            total_copies,
+            available_copies,
+        } = value;
+        SeriesVolume {
+            volume,
+            book_id,
+            title,
+            author,
+            total_copies,
+            available_copies,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use kernel::model::{
+    id::{BookId, TagId},
+    tag::Tag,
+};
+
+pub struct TagRow {
+    pub tag_id: TagId,
+    pub name: String,
+}
+
+impl From<TagRow> for Tag {
+    fn from(value: TagRow) -> Self {
+        let TagRow { tag_id, name } = value;
+        Tag { id: tag_id, name }
+    }
+}
+
+// 蔵書に付けられたタグをまとめて取得する際に使う型
+pub struct BookTagRow {
+    pub book_id: BookId,
+    pub tag_id: TagId,
+    pub name: String,
+}
+
+impl From<BookTagRow> for Tag {
+    fn from(value: BookTagRow) -> Self {
+        let BookTagRow {
+            book_id: _,
+            tag_id,
+            name,
+        } = value;
+        Tag { id: tag_id, name }
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, UserId},
+    transfer::BookTransfer,
+    user::{BookOwner, TransferRecipient},
+};
+
+pub struct BookTransferRow {
+    pub book_id: BookId,
+    pub title: String,
+    pub from_user_id: UserId,
+    pub from_user_name: String,
+    pub to_user_id: UserId,
+    pub to_user_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<BookTransferRow> for BookTransfer {
+    fn from(value: BookTransferRow) -> Self {
+        let BookTransferRow {
+            book_id,
+            title,
+            from_user_id,
+            from_user_name,
+            to_user_id,
+            to_user_name,
+            created_at,
+        } = value;
+        BookTransfer {
+            book_id,
+            book_title: title,
+            from: BookOwner {
+                id: from_user_id,
+                name: from_user_name,
+            },
+            to: TransferRecipient {
+                id: to_user_id,
+                name: to_user_name,
+            },
+            proposed_at: created_at,
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod database;
 pub mod redis;
 pub mod repository;
+pub mod storage;
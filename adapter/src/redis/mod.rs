@@ -16,7 +16,10 @@ impl RedisClient {
 
     pub async fn set_ex<T: RedisKey>(&self, key: &T, value: &T::Value, ttl: u64) -> AppResult<()> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
-        conn.set_ex(key.inner(), value.inner(), ttl).await?;
+        // 戻り値の型を () と明示する。省略すると never type fallback に依存し、
+        // 現在のツールチェーンでは dependency_on_unit_never_type_fallback がエラーになる
+        conn.set_ex::<_, _, ()>(key.inner(), value.inner(), ttl)
+            .await?;
         Ok(())
     }
 
@@ -28,7 +31,8 @@ impl RedisClient {
 
     pub async fn delete<T: RedisKey>(&self, key: &T) -> AppResult<()> {
         let mut conn = self.client.get_multiplexed_async_connection().await?;
-        conn.del(key.inner()).await?;
+        // set_ex と同じく、戻り値の型を明示する
+        conn.del::<_, ()>(key.inner()).await?;
         Ok(())
     }
 
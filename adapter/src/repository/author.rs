@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{author::Author, id::AuthorId},
+    repository::author::AuthorRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    database::{model::author::AuthorRow, ConnectionPool},
+    repository::book::escape_like,
+};
+
+#[derive(new)]
+pub struct AuthorRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl AuthorRepository for AuthorRepositoryImpl {
+    async fn find_by_id(&self, author_id: AuthorId) -> AppResult<Option<Author>> {
+        let row = sqlx::query_as!(
+            AuthorRow,
+            r#"
+                SELECT author_id, name
+                FROM authors
+                WHERE author_id = $1
+            "#,
+            author_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(row.map(Author::from))
+    }
+
+    async fn suggest(&self, keyword: &str, limit: i64) -> AppResult<Vec<Author>> {
+        let authors = sqlx::query_as!(
+            AuthorRow,
+            r#"
+                SELECT a.author_id, a.name
+                FROM authors AS a
+                WHERE a.name ILIKE '%' || $1 || '%'
+                AND EXISTS (
+                    SELECT 1 FROM book_authors AS ba
+                    INNER JOIN books AS b USING(book_id)
+                    WHERE ba.author_id = a.author_id
+                    AND   b.deleted_at IS NULL
+                )
+                ORDER BY a.name ILIKE $1 || '%' DESC, a.name ASC
+                LIMIT $2
+            "#,
+            escape_like(keyword.trim()),
+            limit
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Author::from)
+        .collect();
+
+        Ok(authors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use kernel::{
+        model::{
+            book::event::DeleteBook,
+            id::{BookId, UserId},
+            role::Role,
+            user::Actor,
+        },
+        repository::book::BookRepository,
+    };
+
+    use crate::repository::book::BookRepositoryImpl;
+
+    use super::*;
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_suggest_authors(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = AuthorRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+
+        sqlx::query!(
+            r#"
+                WITH a AS (INSERT INTO authors (name) VALUES ('田中一郎') RETURNING author_id)
+                INSERT INTO book_authors (book_id, author_id, role, position)
+                SELECT '9890736e-a4e4-461a-a77d-eac3517ef11b', author_id, 'editor', 1 FROM a
+            "#
+        )
+        .execute(&pool)
+        .await?;
+
+        // 部分一致する著者のうち、前方一致するものが先に並ぶ
+        let authors = repo.suggest("田", 10).await?;
+        assert_eq!(authors.len(), 3);
+        assert_eq!(authors[0].name, "田中一郎");
+        let authors = repo.suggest("豊", 10).await?;
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "豊田優貴他");
+        assert_eq!(repo.suggest("田", 1).await?.len(), 1);
+
+        // LIKE のワイルドカードは文字として扱う
+        assert!(repo.suggest("%", 10).await?.is_empty());
+
+        let found = repo.find_by_id(authors[0].id).await?.unwrap();
+        assert_eq!(found, authors[0]);
+        assert!(repo.find_by_id(AuthorId::new()).await?.is_none());
+
+        // 削除された蔵書にしか関わらない著者は候補に含めない
+        book_repo
+            .delete(DeleteBook {
+                book_id: BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?,
+                requested_by: Actor {
+                    id: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?,
+                    role: Role::User,
+                },
+                expected_updated_at: None,
+            })
+            .await?;
+        assert!(repo.suggest("豊", 10).await?.is_empty());
+
+        Ok(())
+    }
+}
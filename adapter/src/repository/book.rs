@@ -1,25 +1,56 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use derive_new::new;
 use kernel::{
     model::{
+        author::{credit_line, AuthorCredit, BookAuthor},
         book::{
-            event::{CreateBook, DeleteBook, UpdateBook},
-            Book, BookListOptions, Checkout,
+            duplicate::{author_key, is_similar_title, DuplicateCandidate},
+            event::{
+                CreateBook, CreateBookCopy, DeleteBook, DeleteBookCopy, ImportBookRow, ImportBooks,
+                ImportMode, PurgeBook, RestoreBook, RevertBook, UpdateBook,
+                UpdateBookCopyCondition, UpdateBookCopyLocation, UpdateBookCopyWithdrawal,
+            },
+            permission::BookPermission,
+            revision::BookRevision,
+            Book, BookAvailability, BookCopy, BookImportResult, BookImportStatus, BookListOptions,
+            BookSortKey, Checkout,
         },
-        id::{BookId, UserId},
-        list::PaginatedList,
+        id::{BookId, CopyId, LocationId, SeriesId, TagId, UserId},
+        isbn::Isbn,
+        list::{Cursor, CursorOptions, CursorPaginatedList, PageCursor, PaginatedList, SortOrder},
+        series::SeriesMembership,
+        tag::Tag,
+        user::Actor,
     },
-    repository::book::BookRepository,
+    repository::book::{BookRepository, BookStream},
 };
 use shared::error::{AppError, AppResult};
+use sqlx::{Acquire, Postgres, QueryBuilder};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use uuid::Uuid;
 
-use crate::database::{
-    model::book::{BookCheckoutRow, BookRow, PagenatedBookRow},
-    ConnectionPool,
+use crate::{
+    database::{
+        model::{
+            author::BookAuthorRow,
+            book::{
+                into_revisions, BookCheckoutRow, BookCopyRow, BookCursorRow, BookRevisionRow,
+                BookRow, DuplicateCandidateRow, PagenatedBookRow,
+            },
+            tag::BookTagRow,
+        },
+        ConnectionPool,
+    },
+    repository::location::map_location_not_found,
 };
 
+// エクスポート時に、貸出情報とタグをまとめて取得する蔵書の件数
+const EXPORT_CHUNK_SIZE: usize = 100;
+
 #[derive(new)]
 pub struct BookRepositoryImpl {
     db: ConnectionPool,
@@ -28,84 +59,164 @@ pub struct BookRepositoryImpl {
 #[async_trait]
 impl BookRepository for BookRepositoryImpl {
     async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()> {
-        sqlx::query!(
-            r#"
-                INSERT INTO books (title, author, isbn, description, user_id)
-                VALUES($1, $2, $3, $4, $5)
-            "#,
-            event.title,
-            event.author,
-            event.isbn,
-            event.description,
-            user_id as _
-        )
-        .execute(self.db.inner_ref())
-        .await
-        .map_err(AppError::SpecificOperationError)?;
+        let mut tx = self.db.begin().await?;
+
+        insert_book(&mut tx, event, user_id).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
 
-    async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>> {
-        let BookListOptions { limit, offset } = options;
-        let rows: Vec<PagenatedBookRow> = sqlx::query_as!(
-            PagenatedBookRow,
+    async fn find_duplicates(&self, event: &CreateBook) -> AppResult<Vec<DuplicateCandidate>> {
+        // ISBN が同じ蔵書と、共通する著者がいる蔵書を取得し、
+        // 後者はタイトルが似ているものだけに絞り込む
+        // 著者名は空白（全角を含む）を除き、英字を小文字にして比較する
+        let author_keys = event
+            .authors
+            .iter()
+            .map(|a| author_key(&a.name))
+            .collect::<Vec<_>>();
+        let rows = sqlx::query_as!(
+            DuplicateCandidateRow,
             r#"
                 SELECT
-                    COUNT(*) OVER() AS "total!",
-                    b.book_id AS id
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.isbn,
+                    b.isbn = $1 AS "isbn_match!"
                 FROM books AS b
-                ORDER BY b.created_at DESC
-                LIMIT $1
-                OFFSET $2
+                WHERE b.deleted_at IS NULL
+                AND (
+                    b.isbn = $1
+                    OR EXISTS (
+                        SELECT 1
+                        FROM book_authors AS ba
+                        INNER JOIN authors AS a USING(author_id)
+                        WHERE ba.book_id = b.book_id
+                        AND LOWER(REPLACE(REPLACE(a.name, ' ', ''), '　', '')) = ANY($2)
+                    )
+                )
+                ORDER BY b.created_at ASC
             "#,
-            limit,
-            offset
+            event.isbn.as_str(),
+            &author_keys
         )
         .fetch_all(self.db.inner_ref())
         .await
         .map_err(AppError::SpecificOperationError)?;
 
-        let total = rows.first().map(|r| r.total).unwrap_or_default();
-        let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+        Ok(rows
+            .into_iter()
+            .filter(|row| row.isbn_match || is_similar_title(&row.title, &event.title))
+            .map(DuplicateCandidate::from)
+            .collect())
+    }
 
-        let rows: Vec<BookRow> = sqlx::query_as!(
-            BookRow,
-            r#"
-                SELECT
-                    b.book_id AS book_id,
-                    b.title AS title,
-                    b.author AS author,
-                    b.isbn AS isbn,
-                    b.description AS description,
-                    u.user_id AS owned_by,
-                    u.name AS owner_name
-                FROM books AS b
-                INNER JOIN users AS u USING(user_id)
-                WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
-                ORDER BY b.created_at DESC
-            "#,
-            &book_ids as _
-        )
-        .fetch_all(self.db.inner_ref())
-        .await
-        .map_err(AppError::SpecificOperationError)?;
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>> {
+        let mut tx = self.db.begin().await?;
 
-        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
-        let mut checkouts = self.find_checkouts(&book_ids).await?;
-        let items = rows
-            .into_iter()
-            .map(|row| {
-                let checkout = checkouts.remove(&row.book_id);
-                row.into_book(checkout)
-            })
-            .collect();
+        // 1 行ずつセーブポイントを作り、失敗した行の登録だけを取り消す
+        let mut results = Vec::with_capacity(event.rows.len());
+        for ImportBookRow { line, book } in event.rows {
+            let mut savepoint = tx.begin().await.map_err(AppError::TransactionError)?;
+            let status = match insert_book(&mut savepoint, book, event.requested_user).await {
+                Ok(book_id) => {
+                    savepoint
+                        .commit()
+                        .await
+                        .map_err(AppError::TransactionError)?;
+                    BookImportStatus::Imported(book_id)
+                }
+                Err(e) => {
+                    savepoint
+                        .rollback()
+                        .await
+                        .map_err(AppError::TransactionError)?;
+                    BookImportStatus::Failed(vec![e.to_string()])
+                }
+            };
+            results.push(BookImportResult { line, status });
+        }
+
+        let failed = results
+            .iter()
+            .any(|r| matches!(r.status, BookImportStatus::Failed(_)));
+        if failed && event.mode == ImportMode::AllOrNothing {
+            tx.rollback().await.map_err(AppError::TransactionError)?;
+            for result in results.iter_mut() {
+                if matches!(result.status, BookImportStatus::Imported(_)) {
+                    result.status = BookImportStatus::Skipped;
+                }
+            }
+            return Ok(results);
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(results)
+    }
+
+    async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>> {
+        let rows: Vec<PagenatedBookRow> = BookListSql::new(&options)
+            .offset_query()
+            .build_query_as()
+            .fetch_all(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+        let items = self.find_by_ids(&book_ids).await?;
 
         Ok(PaginatedList {
             total,
+            limit: options.limit,
+            offset: options.offset,
+            items,
+        })
+    }
+
+    async fn find_all_by_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<PageCursor>,
+    ) -> AppResult<CursorPaginatedList<Book>> {
+        let sql = BookListSql::new(&options);
+        let rows: Vec<BookCursorRow> = sql
+            .cursor_query(cursor.as_ref())?
+            .build_query_as()
+            .fetch_all(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        let order_by = sql.order_by();
+        let page = CursorPaginatedList::from_rows(
+            rows,
+            &CursorOptions {
+                limit: options.limit,
+                cursor,
+            },
+            |row| Cursor {
+                order_by: order_by.clone(),
+                keys: row.keys.clone(),
+            },
+        );
+
+        let CursorPaginatedList {
             limit,
-            offset,
             items,
+            next_cursor,
+            prev_cursor,
+        } = page;
+        let book_ids = items.into_iter().map(|r| r.id).collect::<Vec<BookId>>();
+
+        Ok(CursorPaginatedList {
+            limit,
+            items: self.find_by_ids(&book_ids).await?,
+            next_cursor,
+            prev_cursor,
         })
     }
 
@@ -119,11 +230,36 @@ impl BookRepository for BookRepositoryImpl {
                     b.author AS author,
                     b.isbn AS isbn,
                     b.description AS description,
+                    b.publisher,
+                    b.published_year,
                     u.user_id AS owned_by,
-                    u.name AS owner_name
+                    u.name AS owner_name,
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc WHERE bc.book_id = b.book_id
+                    ) AS "total_copies!",
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND   bc.withdrawn_at IS NOT NULL
+                    ) AS "withdrawn_copies!",
+                    b.cover_format IS NOT NULL AS "has_cover!",
+                    (
+                        SELECT AVG(r.rating)::float8 FROM book_reviews AS r
+                        WHERE r.book_id = b.book_id
+                    ) AS average_rating,
+                    (
+                        SELECT COUNT(*) FROM book_reviews AS r WHERE r.book_id = b.book_id
+                    ) AS "review_count!",
+                    b.series_id AS "series_id: SeriesId",
+                    s.title AS "series_title?",
+                    b.series_volume,
+                    b.updated_at,
+                    b.deleted_at
                 FROM books AS b
                 INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN series AS s ON s.series_id = b.series_id
                 WHERE b.book_id = $1
+                AND   b.deleted_at IS NULL
             "#,
             book_id as _ // これって型検査の無効化って本には書いてあったけど、本当？無効にして安全性が壊れない？
                          // queryマクロの中に書いてあることに注意らしい
@@ -134,15 +270,53 @@ impl BookRepository for BookRepositoryImpl {
 
         match row {
             Some(r) => {
-                let checkout = self.find_checkouts(&[r.book_id]).await?.remove(&r.book_id);
-                Ok(Some(r.into_book(checkout)))
+                let checkouts = self
+                    .find_checkouts(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                let tags = self
+                    .find_tags(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                let authors = self
+                    .find_authors(&[r.book_id])
+                    .await?
+                    .remove(&r.book_id)
+                    .unwrap_or_default();
+                Ok(Some(r.into_book(checkouts, tags, authors)))
             }
             None => Ok(None),
         }
     }
 
-    // use_id = $6 は、本の内容を変更できるのは所有者だけにするため
+    async fn find_all_by_ids(&self, book_ids: &[BookId]) -> AppResult<Vec<Book>> {
+        let books = self.find_by_ids(book_ids).await?;
+        Ok(books
+            .into_iter()
+            .filter(|book| book.deleted_at.is_none())
+            .collect())
+    }
+
+    fn export_all(&self) -> BookStream {
+        let repo = BookRepositoryImpl::new(self.db.clone());
+        let (tx, rx) = mpsc::channel(EXPORT_CHUNK_SIZE);
+        tokio::spawn(async move {
+            if let Err(e) = repo.send_all_books(&tx).await {
+                let _ = tx.send(Err(e)).await;
+            }
+        });
+        Box::pin(ReceiverStream::new(rx))
+    }
+
     async fn update(&self, event: UpdateBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let updated_at =
+            authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        check_updated_at(updated_at, event.expected_updated_at.as_deref())?;
+
         let res = sqlx::query!(
             r#"
                 UPDATE books
@@ -150,190 +324,2880 @@ impl BookRepository for BookRepositoryImpl {
                     title = $1,
                     author = $2,
                     isbn = $3,
-                    description = $4
-                WHERE book_id = $5
-                AND   user_id = $6
+                    -- 検証済みの ISBN で更新するので、移行時に付けた印は外す
+                    isbn_issue = NULL,
+                    description = $4,
+                    publisher = CASE WHEN $5 THEN $6 ELSE publisher END,
+                    published_year = CASE WHEN $7 THEN $8 ELSE published_year END
+                WHERE book_id = $9
             "#,
             event.title,
-            event.author,
-            event.isbn,
+            credit_line(&event.authors),
+            event.isbn as _,
             event.description,
-            event.book_id as _,
-            event.requested_user as _
+            // 指定されなかった項目は変更しない
+            event.publisher.is_some(),
+            event.publisher.clone().flatten(),
+            event.published_year.is_some(),
+            event.published_year.flatten(),
+            event.book_id as _
         )
-        .execute(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(map_isbn_conflict)?;
 
         if res.rows_affected() < 1 {
             return Err(AppError::EntityNotFound("specified book not found".into()));
         }
+
+        replace_authors(&mut tx, event.book_id, &event.authors).await?;
+        record_revision(&mut tx, event.book_id, event.requested_by.id).await?;
+        if let Some(tags) = &event.tags {
+            replace_tags(&mut tx, event.book_id, tags).await?;
+        }
+        if let Some(series) = &event.series {
+            set_series(&mut tx, event.book_id, series.as_ref()).await?;
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
         Ok(())
     }
 
-    async fn delete(&self, event: DeleteBook) -> AppResult<()> {
-        let res = sqlx::query!(
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>> {
+        let rows = sqlx::query_as!(
+            BookRevisionRow,
             r#"
-                DELETE FROM books
-                WHERE book_id = $1
-                AND   user_id = $2
+                SELECT
+                    r.revision,
+                    r.user_id AS "user_id?: UserId",
+                    u.name AS "user_name?",
+                    r.title,
+                    r.author,
+                    r.isbn,
+                    r.description,
+                    r.publisher,
+                    r.published_year,
+                    r.created_at
+                FROM book_revisions AS r
+                INNER JOIN books AS b USING(book_id)
+                LEFT OUTER JOIN users AS u ON u.user_id = r.user_id
+                WHERE r.book_id = $1
+                AND   b.deleted_at IS NULL
+                ORDER BY r.revision ASC
             "#,
-            event.book_id as _,
-            event.requested_user as _
+            book_id as _
         )
-        .execute(self.db.inner_ref())
+        .fetch_all(self.db.inner_ref())
         .await
         .map_err(AppError::SpecificOperationError)?;
 
-        if res.rows_affected() < 1 {
+        // 蔵書の登録時に最初の版を記録するため、版がなければ蔵書が存在しない
+        if rows.is_empty() {
             return Err(AppError::EntityNotFound("specified book not found".into()));
         }
 
-        Ok(())
+        Ok(into_revisions(rows))
     }
-}
 
-impl BookRepositoryImpl {
-    // 指定された book_id が貸出中の場合に貸出情報を返すメソッドを追加する
-    async fn find_checkouts(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Checkout>> {
-        let res = sqlx::query_as!(
-            BookCheckoutRow,
+    async fn revert(&self, event: RevertBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let updated_at =
+            authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        check_updated_at(updated_at, event.expected_updated_at.as_deref())?;
+
+        // 版には移行時に印を付けた正しくない ISBN が記録されている場合があるため、
+        // 更新時と同じく検証して正規化した ISBN に戻す
+        let isbn = sqlx::query_scalar!(
+            "SELECT isbn FROM book_revisions WHERE book_id = $1 AND revision = $2",
+            event.book_id as _,
+            event.revision
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified revision not found".into()))?;
+        let isbn = Isbn::from_str(&isbn).map_err(|_| {
+            AppError::UnprocessableEntiry(format!(
+                "版 {} の ISBN（{isbn}）が正しくないため、この版には戻せません。",
+                event.revision
+            ))
+        })?;
+
+        sqlx::query!(
             r#"
-                SELECT
-                c.checkout_id,
-                c.book_id,
-                u.user_id,
-                u.name AS user_name,
-                c.checked_out_at
-                FROM checkouts AS c
-                INNER JOIN users AS u USING(user_id)
-                WHERE book_id = ANY($1);
+                UPDATE books AS b
+                SET
+                    title = r.title,
+                    author = r.author,
+                    isbn = $3,
+                    -- 検証済みの ISBN で更新するので、移行時に付けた印は外す
+                    isbn_issue = NULL,
+                    description = r.description,
+                    publisher = r.publisher,
+                    published_year = r.published_year
+                FROM book_revisions AS r
+                WHERE b.book_id = $1
+                AND   r.book_id = b.book_id
+                AND   r.revision = $2
             "#,
-            book_ids as _
+            event.book_id as _,
+            event.revision,
+            isbn as _
         )
-        .fetch_all(self.db.inner_ref())
+        .execute(&mut *tx)
         .await
-        .map_err(AppError::SpecificOperationError)?
-        .into_iter()
-        .map(|checkout| (checkout.book_id, Checkout::from(checkout)))
-        .collect();
+        .map_err(map_isbn_conflict)?;
 
-        Ok(res)
-    }
-}
+        // 著者の一覧も、その版の時点のものに戻す
+        sqlx::query!(
+            "DELETE FROM book_authors WHERE book_id = $1",
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        sqlx::query!(
+            r#"
+                INSERT INTO book_authors (book_id, author_id, role, position)
+                SELECT book_id, author_id, role, position
+                FROM book_revision_authors
+                WHERE book_id = $1
+                AND   revision = $2
+            "#,
+            event.book_id as _,
+            event.revision
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
+        record_revision(&mut tx, event.book_id, event.requested_by.id).await?;
 
-    use chrono::Utc;
-    use kernel::{
-        model::{
-            checkout::event::{CreateCheckout, UpdateReturned},
-            user::event::CreateUser,
-        },
-        repository::{checkout::CheckoutRepository, user::UserRepository},
-    };
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
-    use crate::repository::{checkout::CheckoutRepositoryImpl, user::UserRepositoryImpl};
+        Ok(())
+    }
 
-    use super::*;
+    // 貸出履歴などを残すため、行は削除せずに削除日時を記録する
+    async fn delete(&self, event: DeleteBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
 
-    #[sqlx::test]
-    async fn test_register_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        // 蔵書のデータを追加・取得するためにはユーザー情報がないといけないため
-        // テストコードの方でもロール及びユーザー情報を追加するコードがいる
-        sqlx::query!(r#"INSERT INTO roles(name) VALUES ('Admin'), ('User');"#)
-            .execute(&pool)
-            .await?;
-        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let user = user_repo
-            .create(CreateUser {
-                name: "Test User".into(),
-                email: "test@exapmle.com".into(),
-                password: "test_password".into(),
-            })
-            .await?;
-        let book = CreateBook {
-            title: "Test Title".into(),
-            author: "Test Author".into(),
-            isbn: "Test ISBN".into(),
-            description: "Test Description".into(),
-        };
+        let updated_at =
+            authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        check_updated_at(updated_at, event.expected_updated_at.as_deref())?;
 
-        repo.create(book, user.id).await?;
-        // find_all を実行するためには BookListOptions 型が必要なので作る。
-        let options = BookListOptions {
-            limit: 20,
-            offset: 0,
-        };
-        let res = repo.find_all(options).await?;
-        assert_eq!(res.items.len(), 1);
+        // 貸出中の蔵書を削除すると、借りている人が返却できなくなる
+        let checked_out = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM checkouts WHERE book_id = $1
+                ) AS "exists!"
+            "#,
+            event.book_id as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if checked_out {
+            return Err(AppError::UnprocessableEntiry(
+                "specified book is checked out".into(),
+            ));
+        }
 
-        let book_id = res.items[0].id;
-        let res = repo.find_by_id(book_id).await?;
-        assert!(res.is_some());
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET deleted_at = CURRENT_TIMESTAMP(3)
+                WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
 
-        let Book {
-            id,
-            title,
-            author,
-            isbn,
-            description,
-            owner,
-            ..
-        } = res.unwrap();
-        assert_eq!(id, book_id);
-        assert_eq!(title, "Test Title");
-        assert_eq!(author, "Test Author");
-        assert_eq!(isbn, "Test ISBN");
-        assert_eq!(description, "Test Description");
-        assert_eq!(owner.name, "Test User");
+        tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("common", "book"))]
-    async fn test_update_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        // 2. fixture/book.sql で作成済みの書籍を取得
-        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b").unwrap();
-        let book = repo.find_by_id(book_id).await?.unwrap();
-        const NEW_AUTHOR: &str = "更新後の著者名";
-        assert_ne!(book.author, NEW_AUTHOR);
-
-        // 3. 書籍の更新用のパラメーターを作成し、更新を行う
-        let update_book = UpdateBook {
-            book_id: book.id,
-            title: book.title,
-            author: NEW_AUTHOR.into(),
-            isbn: book.isbn,
-            description: book.description,
-            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c").unwrap(),
-        };
-        repo.update(update_book).await.unwrap();
+    async fn find_deleted(&self, limit: i64, offset: i64) -> AppResult<PaginatedList<Book>> {
+        let rows = sqlx::query_as!(
+            PagenatedBookRow,
+            r#"
+                SELECT
+                    COUNT(*) OVER() AS "total!",
+                    b.book_id AS id
+                FROM books AS b
+                WHERE b.deleted_at IS NOT NULL
+                ORDER BY b.deleted_at DESC, b.book_id ASC
+                LIMIT $1
+                OFFSET $2
+            "#,
+            limit,
+            offset
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
 
-        // 4. 更新後の書籍を取得し、期待通りに更新されていることを検証する
-        let book = repo.find_by_id(book_id).await?.unwrap();
-        assert_eq!(book.author, NEW_AUTHOR);
+        let total = rows.first().map(|r| r.total).unwrap_or_default();
+        let book_ids = rows.into_iter().map(|r| r.id).collect::<Vec<_>>();
 
-        Ok(())
+        Ok(PaginatedList {
+            total,
+            limit,
+            offset,
+            items: self.find_by_ids(&book_ids).await?,
+        })
     }
 
-    #[sqlx::test(fixtures("common", "book"))]
-    async fn test_delete_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
-        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+    async fn restore(&self, event: RestoreBook) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                UPDATE books
+                SET deleted_at = NULL
+                WHERE book_id = $1
+                AND   deleted_at IS NOT NULL
+            "#,
+            event.book_id as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_isbn_conflict)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified deleted book not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn purge(&self, event: PurgeBook) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM books
+                WHERE book_id = $1
+                AND   deleted_at IS NOT NULL
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_checked_out)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified deleted book not found".into(),
+            ));
+        }
+
+        // 返却済みの貸出履歴は外部キーを持たないので、ここで合わせて削除する
+        sqlx::query!(
+            "DELETE FROM returned_checkouts WHERE book_id = $1",
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn find_copies(&self, book_id: BookId) -> AppResult<Vec<BookCopy>> {
+        let rows = sqlx::query_as!(
+            BookCopyRow,
+            r#"
+                SELECT
+                    bc.copy_id,
+                    bc.barcode,
+                    bc.condition,
+                    bc.withdrawn_at,
+                    lp.location_id AS "location_id?: LocationId",
+                    lp.parent_id AS "location_parent_id?: LocationId",
+                    lp.name AS "location_name?",
+                    lp.kind AS "location_kind?",
+                    lp.path AS "location_path?"
+                FROM book_copies AS bc
+                LEFT OUTER JOIN location_paths AS lp ON lp.location_id = bc.location_id
+                WHERE bc.book_id = $1
+                ORDER BY bc.created_at ASC, bc.copy_id ASC
+            "#,
+            book_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut checkouts = self
+            .find_checkouts(&[book_id])
+            .await?
+            .remove(&book_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|checkout| (checkout.copy_id, checkout))
+            .collect::<HashMap<_, _>>();
+
+        rows.into_iter()
+            .map(|row| {
+                let checkout = checkouts.remove(&row.copy_id);
+                row.into_book_copy(checkout)
+            })
+            .collect()
+    }
+
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<CopyId> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        let copy_id = insert_copy(&mut tx, event.book_id, event.barcode).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(copy_id)
+    }
+
+    async fn update_copy_location(&self, event: UpdateBookCopyLocation) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+
+        let res = sqlx::query!(
+            r#"
+                UPDATE book_copies
+                SET location_id = $3
+                WHERE copy_id = $1
+                AND   book_id = $2
+            "#,
+            event.copy_id as _,
+            event.book_id as _,
+            event.location_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_location_not_found)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified book copy not found".into(),
+            ));
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn update_copy_condition(&self, event: UpdateBookCopyCondition) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+
+        let res = sqlx::query!(
+            r#"
+                UPDATE book_copies
+                SET condition = $3
+                WHERE copy_id = $1
+                AND   book_id = $2
+            "#,
+            event.copy_id as _,
+            event.book_id as _,
+            event.condition.as_ref()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified book copy not found".into(),
+            ));
+        }
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn update_copy_withdrawal(&self, event: UpdateBookCopyWithdrawal) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+
+        // 貸し出し中の冊子が貸出の対象から外れると、返却まで貸出可能な冊子の数が合わなくなる
+        let checked_out = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id
+                ) AS "checked_out!"
+                FROM book_copies AS bc
+                WHERE bc.copy_id = $1
+                AND   bc.book_id = $2
+                FOR UPDATE
+            "#,
+            event.copy_id as _,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified book copy not found".into()))?;
+
+        if checked_out && event.withdrawn {
+            return Err(AppError::UnprocessableEntiry(format!(
+                "冊子（{}）は貸し出し中のため貸出の対象から外せません。",
+                event.copy_id
+            )));
+        }
+
+        // すでに外している冊子を外し直した場合は、外した日時を変えない
+        sqlx::query!(
+            r#"
+                UPDATE book_copies
+                SET withdrawn_at = CASE
+                    WHEN $2 THEN COALESCE(withdrawn_at, CURRENT_TIMESTAMP(3))
+                    ELSE NULL
+                END
+                WHERE copy_id = $1
+            "#,
+            event.copy_id as _,
+            event.withdrawn
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+
+        let res = sqlx::query!(
+            r#"
+                SELECT
+                bc.copy_id,
+                EXISTS (
+                    SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id
+                ) AS "checked_out!"
+                FROM book_copies AS bc
+                WHERE bc.copy_id = $1
+                AND   bc.book_id = $2
+            "#,
+            event.copy_id as _,
+            event.book_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        match res {
+            None => {
+                return Err(AppError::EntityNotFound(
+                    "specified book copy not found".into(),
+                ))
+            }
+            Some(r) if r.checked_out => {
+                return Err(AppError::UnprocessableEntiry(format!(
+                    "冊子（{}）は貸し出し中のため削除できません。",
+                    event.copy_id
+                )))
+            }
+            _ => {}
+        }
+
+        sqlx::query!(
+            "DELETE FROM book_copies WHERE copy_id = $1",
+            event.copy_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_checked_out)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+impl BookRepositoryImpl {
+    // 指定した book_id の蔵書を、貸出情報と合わせて book_ids の順に取得する
+    async fn find_by_ids(&self, book_ids: &[BookId]) -> AppResult<Vec<Book>> {
+        let rows: Vec<BookRow> = sqlx::query_as!(
+            BookRow,
+            r#"
+                SELECT
+                    b.book_id AS book_id,
+                    b.title AS title,
+                    b.author AS author,
+                    b.isbn AS isbn,
+                    b.description AS description,
+                    b.publisher,
+                    b.published_year,
+                    u.user_id AS owned_by,
+                    u.name AS owner_name,
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc WHERE bc.book_id = b.book_id
+                    ) AS "total_copies!",
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND   bc.withdrawn_at IS NOT NULL
+                    ) AS "withdrawn_copies!",
+                    b.cover_format IS NOT NULL AS "has_cover!",
+                    (
+                        SELECT AVG(r.rating)::float8 FROM book_reviews AS r
+                        WHERE r.book_id = b.book_id
+                    ) AS average_rating,
+                    (
+                        SELECT COUNT(*) FROM book_reviews AS r WHERE r.book_id = b.book_id
+                    ) AS "review_count!",
+                    b.series_id AS "series_id: SeriesId",
+                    s.title AS "series_title?",
+                    b.series_volume,
+                    b.updated_at,
+                    b.deleted_at
+                FROM books AS b
+                INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN series AS s ON s.series_id = b.series_id
+                WHERE b.book_id IN (SELECT * FROM UNNEST($1::uuid[]))
+                ORDER BY ARRAY_POSITION($1::uuid[], b.book_id)
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        self.assemble_books(rows).await
+    }
+
+    // すべての蔵書を DB から 1 行ずつ読み出し、EXPORT_CHUNK_SIZE 件ごとに
+    // 貸出情報とタグをまとめて取得してから tx に送る
+    async fn send_all_books(&self, tx: &mpsc::Sender<AppResult<Book>>) -> AppResult<()> {
+        let mut rows = sqlx::query_as!(
+            BookRow,
+            r#"
+                SELECT
+                    b.book_id AS book_id,
+                    b.title AS title,
+                    b.author AS author,
+                    b.isbn AS isbn,
+                    b.description AS description,
+                    b.publisher,
+                    b.published_year,
+                    u.user_id AS owned_by,
+                    u.name AS owner_name,
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc WHERE bc.book_id = b.book_id
+                    ) AS "total_copies!",
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND   bc.withdrawn_at IS NOT NULL
+                    ) AS "withdrawn_copies!",
+                    b.cover_format IS NOT NULL AS "has_cover!",
+                    (
+                        SELECT AVG(r.rating)::float8 FROM book_reviews AS r
+                        WHERE r.book_id = b.book_id
+                    ) AS average_rating,
+                    (
+                        SELECT COUNT(*) FROM book_reviews AS r WHERE r.book_id = b.book_id
+                    ) AS "review_count!",
+                    b.series_id AS "series_id: SeriesId",
+                    s.title AS "series_title?",
+                    b.series_volume,
+                    b.updated_at,
+                    b.deleted_at
+                FROM books AS b
+                INNER JOIN users AS u USING(user_id)
+                LEFT OUTER JOIN series AS s ON s.series_id = b.series_id
+                WHERE b.deleted_at IS NULL
+                ORDER BY b.created_at ASC, b.book_id ASC
+            "#
+        )
+        .fetch(self.db.inner_ref());
+
+        let mut chunk = Vec::with_capacity(EXPORT_CHUNK_SIZE);
+        while let Some(row) = rows.next().await {
+            chunk.push(row.map_err(AppError::SpecificOperationError)?);
+            if chunk.len() == EXPORT_CHUNK_SIZE {
+                for book in self.assemble_books(std::mem::take(&mut chunk)).await? {
+                    // 受け取り側がいなくなった場合は読み出しをやめる
+                    if tx.send(Ok(book)).await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        for book in self.assemble_books(chunk).await? {
+            if tx.send(Ok(book)).await.is_err() {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    // 蔵書の行に貸出情報・タグ・著者を付けて、行の順に Book に変換する
+    async fn assemble_books(&self, rows: Vec<BookRow>) -> AppResult<Vec<Book>> {
+        let book_ids = rows.iter().map(|book| book.book_id).collect::<Vec<_>>();
+        let mut checkouts = self.find_checkouts(&book_ids).await?;
+        let mut tags = self.find_tags(&book_ids).await?;
+        let mut authors = self.find_authors(&book_ids).await?;
+        let books = rows
+            .into_iter()
+            .map(|row| {
+                let checkouts = checkouts.remove(&row.book_id).unwrap_or_default();
+                let tags = tags.remove(&row.book_id).unwrap_or_default();
+                let authors = authors.remove(&row.book_id).unwrap_or_default();
+                row.into_book(checkouts, tags, authors)
+            })
+            .collect();
+
+        Ok(books)
+    }
+
+    // 指定された book_id の蔵書の冊子のうち、貸出中のものの貸出情報を蔵書ごとにまとめて返す
+    async fn find_checkouts(
+        &self,
+        book_ids: &[BookId],
+    ) -> AppResult<HashMap<BookId, Vec<Checkout>>> {
+        let rows = sqlx::query_as!(
+            BookCheckoutRow,
+            r#"
+                SELECT
+                c.checkout_id,
+                c.book_id,
+                c.copy_id,
+                bc.barcode,
+                u.user_id,
+                u.name AS user_name,
+                c.checked_out_at,
+                c.due_at
+                FROM checkouts AS c
+                INNER JOIN users AS u USING(user_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
+                WHERE c.book_id = ANY($1)
+                ORDER BY c.checked_out_at ASC;
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<Checkout>> = HashMap::new();
+        for row in rows {
+            res.entry(row.book_id)
+                .or_default()
+                .push(Checkout::from(row));
+        }
+
+        Ok(res)
+    }
+
+    // 指定された book_id の蔵書に付けられたタグを、蔵書ごとにまとめて返す
+    async fn find_tags(&self, book_ids: &[BookId]) -> AppResult<HashMap<BookId, Vec<Tag>>> {
+        let rows = sqlx::query_as!(
+            BookTagRow,
+            r#"
+                SELECT
+                bt.book_id,
+                t.tag_id,
+                t.name
+                FROM book_tags AS bt
+                INNER JOIN tags AS t USING(tag_id)
+                WHERE bt.book_id = ANY($1)
+                ORDER BY t.name ASC;
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            res.entry(row.book_id).or_default().push(Tag::from(row));
+        }
+
+        Ok(res)
+    }
+
+    // 指定された book_id の蔵書の著者を、蔵書ごとに並び順どおりにまとめて返す
+    async fn find_authors(
+        &self,
+        book_ids: &[BookId],
+    ) -> AppResult<HashMap<BookId, Vec<BookAuthor>>> {
+        let rows = sqlx::query_as!(
+            BookAuthorRow,
+            r#"
+                SELECT
+                ba.book_id,
+                a.author_id,
+                a.name,
+                ba.role
+                FROM book_authors AS ba
+                INNER JOIN authors AS a USING(author_id)
+                WHERE ba.book_id = ANY($1)
+                ORDER BY ba.position ASC;
+            "#,
+            book_ids as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut res: HashMap<BookId, Vec<BookAuthor>> = HashMap::new();
+        for row in rows {
+            let book_id = row.book_id;
+            res.entry(book_id)
+                .or_default()
+                .push(BookAuthor::try_from(row)?);
+        }
+
+        Ok(res)
+    }
+}
+
+// 蔵書の行をロックし、操作を要求したユーザーに変更・削除の権限があるか確認する
+// 所有者以外による操作は監査のためにログに残し、楽観的排他制御のために蔵書の最終更新日時を返す
+pub(crate) async fn authorize_book_mutation(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    actor: &Actor,
+) -> AppResult<DateTime<Utc>> {
+    let row = sqlx::query!(
+        r#"
+            SELECT user_id AS "user_id: UserId", updated_at FROM books
+            WHERE book_id = $1
+            AND   deleted_at IS NULL
+            FOR UPDATE
+        "#,
+        book_id as _
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+    if BookPermission::check(actor, row.user_id)? == BookPermission::Override {
+        tracing::info!(
+            book_id = %book_id,
+            user_id = %actor.id,
+            role = actor.role.as_ref(),
+            owner_id = %row.user_id,
+            "book mutated by a non-owner with role override"
+        );
+    }
+
+    Ok(row.updated_at)
+}
+
+// 蔵書の最終更新日時が、クライアントが前提とするもののいずれとも一致しなければエラーにする
+fn check_updated_at(
+    updated_at: DateTime<Utc>,
+    expected: Option<&[DateTime<Utc>]>,
+) -> AppResult<()> {
+    match expected {
+        Some(expected) if !expected.contains(&updated_at) => Err(AppError::PreconditionFailed(
+            "specified book has been modified".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// 蔵書を登録し、あわせて冊子を 1 冊登録する
+async fn insert_book(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    event: CreateBook,
+    user_id: UserId,
+) -> AppResult<BookId> {
+    let book_id = BookId::new();
+    sqlx::query!(
+        r#"
+            INSERT INTO books (
+                book_id, title, author, isbn, description, publisher, published_year, user_id
+            )
+            VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+        book_id as _,
+        event.title,
+        credit_line(&event.authors),
+        event.isbn as _,
+        event.description,
+        event.publisher,
+        event.published_year,
+        user_id as _
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(map_isbn_conflict)?;
+
+    insert_copy(tx, book_id, None).await?;
+    replace_tags(tx, book_id, &event.tags).await?;
+    replace_authors(tx, book_id, &event.authors).await?;
+    if let Some(series) = &event.series {
+        set_series(tx, book_id, Some(series)).await?;
+    }
+    record_revision(tx, book_id, user_id).await?;
+
+    Ok(book_id)
+}
+
+// 蔵書の現在の書誌情報を、著者の一覧とともに新しい版として記録する
+// 直前の版から変わっていない場合は記録しない
+// 著者の一覧は books.author にまとめた文字列に反映されているため、その比較で変更を判定できる
+async fn record_revision(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    user_id: UserId,
+) -> AppResult<()> {
+    let revision = sqlx::query_scalar!(
+        r#"
+            INSERT INTO book_revisions
+            (book_id, revision, user_id, title, author, isbn, description, publisher, published_year)
+            SELECT
+                b.book_id, COALESCE(r.revision, 0) + 1, $2,
+                b.title, b.author, b.isbn, b.description, b.publisher, b.published_year
+            FROM books AS b
+            LEFT OUTER JOIN LATERAL (
+                SELECT * FROM book_revisions
+                WHERE book_id = b.book_id
+                ORDER BY revision DESC
+                LIMIT 1
+            ) AS r ON TRUE
+            WHERE b.book_id = $1
+            AND (r.revision IS NULL
+                OR (r.title, r.author, r.isbn, r.description, r.publisher, r.published_year)
+                    IS DISTINCT FROM
+                    (b.title, b.author, b.isbn, b.description, b.publisher, b.published_year))
+            RETURNING revision
+        "#,
+        book_id as _,
+        user_id as _
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    if let Some(revision) = revision {
+        sqlx::query!(
+            r#"
+                INSERT INTO book_revision_authors (book_id, revision, author_id, role, position)
+                SELECT book_id, $2, author_id, role, position
+                FROM book_authors
+                WHERE book_id = $1
+            "#,
+            book_id as _,
+            revision
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+    }
+
+    Ok(())
+}
+
+// 蔵書の著者を credits に置き換える。まだ登録されていない名前の著者は登録する
+// 同じ著者が同じ役割で重複して指定された場合は、最初のものだけを残す
+async fn replace_authors(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    credits: &[AuthorCredit],
+) -> AppResult<()> {
+    sqlx::query!("DELETE FROM book_authors WHERE book_id = $1", book_id as _)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+    let names = credits.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+    let roles = credits
+        .iter()
+        .map(|c| c.role.as_ref().to_string())
+        .collect::<Vec<_>>();
+
+    sqlx::query!(
+        r#"
+            INSERT INTO authors (name)
+            SELECT DISTINCT name FROM UNNEST($1::varchar[]) AS c(name)
+            ON CONFLICT (name) DO NOTHING
+        "#,
+        &names
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO book_authors (book_id, author_id, role, position)
+            SELECT $1, a.author_id, c.role, MIN(c.position)::integer
+            FROM UNNEST($2::varchar[], $3::varchar[]) WITH ORDINALITY AS c(name, role, position)
+            INNER JOIN authors AS a ON a.name = c.name
+            GROUP BY a.author_id, c.role
+        "#,
+        book_id as _,
+        &names,
+        &roles
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+// 蔵書に付けるタグを tag_ids に置き換える
+async fn replace_tags(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    tag_ids: &[TagId],
+) -> AppResult<()> {
+    sqlx::query!("DELETE FROM book_tags WHERE book_id = $1", book_id as _)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO book_tags (book_id, tag_id)
+            SELECT DISTINCT $1::uuid, t.tag_id
+            FROM UNNEST($2::uuid[]) AS t(tag_id)
+        "#,
+        book_id as _,
+        tag_ids as _
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(
+        |e| match e.as_database_error().and_then(|e| e.constraint()) {
+            Some("book_tags_tag_id_fkey") => {
+                AppError::UnprocessableEntiry("specified tag not found".into())
+            }
+            _ => AppError::SpecificOperationError(e),
+        },
+    )?;
+
+    Ok(())
+}
+
+// 蔵書が属するシリーズと巻数を設定する。None の場合はシリーズから外す
+// まだ登録されていないタイトルのシリーズは登録する
+async fn set_series(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    series: Option<&SeriesMembership>,
+) -> AppResult<()> {
+    let series_id = match series {
+        Some(series) => {
+            sqlx::query!(
+                r#"
+                    INSERT INTO series (title) VALUES ($1)
+                    ON CONFLICT (title) DO NOTHING
+                "#,
+                series.title
+            )
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            let series_id = sqlx::query_scalar!(
+                r#"SELECT series_id AS "series_id: SeriesId" FROM series WHERE title = $1"#,
+                series.title
+            )
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            Some(series_id)
+        }
+        None => None,
+    };
+
+    sqlx::query!(
+        r#"
+            UPDATE books
+            SET series_id = $2, series_volume = $3
+            WHERE book_id = $1
+        "#,
+        book_id as _,
+        series_id as _,
+        series.map(|s| s.volume)
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(())
+}
+
+// 蔵書に冊子を 1 冊登録する
+// バーコードが指定されない場合は冊子 ID から生成する
+async fn insert_copy(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+    barcode: Option<String>,
+) -> AppResult<CopyId> {
+    let copy_id = CopyId::new();
+    let barcode = barcode.unwrap_or_else(|| copy_id.to_string());
+    sqlx::query!(
+        r#"
+            INSERT INTO book_copies (copy_id, book_id, barcode)
+            VALUES ($1, $2, $3)
+        "#,
+        copy_id as _,
+        book_id as _,
+        barcode
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(
+        |e| match e.as_database_error().and_then(|e| e.constraint()) {
+            Some("book_copies_barcode_key") => {
+                AppError::ConflictError("specified barcode is already registered".into())
+            }
+            _ => AppError::SpecificOperationError(e),
+        },
+    )?;
+
+    Ok(copy_id)
+}
+
+// ISBN の一意制約に違反した場合は、登録済みの蔵書と重複している旨のエラーにする
+fn map_isbn_conflict(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("books_isbn_key") => {
+            AppError::ConflictError("specified isbn is already registered".into())
+        }
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+// 貸し出し中の冊子・蔵書を削除しようとした場合のエラーにする
+// 貸出の外部キーは RESTRICT のため、事前のチェックをすり抜けてもデータベースが削除を拒否する
+fn map_checked_out(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("checkouts_copy_id_fkey" | "checkouts_book_id_fkey") => {
+            AppError::UnprocessableEntiry("貸し出し中の冊子があるため削除できません。".into())
+        }
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+// 蔵書 b の冊子のうち、貸し出し中でないもの
+const AVAILABLE_COPY: &str = r#"
+    SELECT 1 FROM book_copies AS bc
+    WHERE bc.book_id = b.book_id
+    AND bc.withdrawn_at IS NULL
+    AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+"#;
+
+// 蔵書 b のレビューの評価の平均。レビューがない場合は 0 とする
+const AVERAGE_RATING: &str = r#"
+    COALESCE((SELECT AVG(r.rating) FROM book_reviews AS r WHERE r.book_id = b.book_id), 0)
+"#;
+
+// 蔵書 b の冊子のうち、場所が設定されているもの
+// 続けて lp.ancestor_ids に対する条件を付けて使う
+const COPY_AT_LOCATION: &str = r#"
+    SELECT 1 FROM book_copies AS bc
+    INNER JOIN location_paths AS lp ON lp.location_id = bc.location_id
+    WHERE bc.book_id = b.book_id
+    AND "#;
+
+// 蔵書 b にタグ t.tag_id が付けられているか
+const HAS_TAG: &str = r#"
+    SELECT 1 FROM book_tags AS bt
+    WHERE bt.book_id = b.book_id
+    AND bt.tag_id = t.tag_id
+"#;
+
+// キーワード k.term が蔵書のいずれかの項目に部分一致するか
+// 日本語のタイトルでも検索できるよう、分かち書きには頼らず部分一致で判定する
+const KEYWORD_MATCH: &str = r#"
+    b.title ILIKE '%' || k.term || '%'
+    OR b.author ILIKE '%' || k.term || '%'
+    OR REPLACE(b.isbn, '-', '') ILIKE '%' || REPLACE(k.term, '-', '') || '%'
+    OR b.description ILIKE '%' || k.term || '%'
+"#;
+
+// キーワード k.term に対する関連度
+// タイトル > 著者名 > ISBN > 説明文 の順に重み付けし、タイトルの前方一致を優遇する
+const KEYWORD_SCORE: &str = r#"
+    CASE WHEN b.title ILIKE k.term || '%' THEN 2 ELSE 0 END
+    + CASE WHEN b.title ILIKE '%' || k.term || '%' THEN 8 ELSE 0 END
+    + CASE WHEN b.author ILIKE '%' || k.term || '%' THEN 4 ELSE 0 END
+    + CASE WHEN REPLACE(b.isbn, '-', '') ILIKE '%' || REPLACE(k.term, '-', '') || '%' THEN 2 ELSE 0 END
+    + CASE WHEN b.description ILIKE '%' || k.term || '%' THEN 1 ELSE 0 END
+"#;
+
+// 蔵書一覧の並び替えに使う項目
+struct OrderColumn {
+    // カーソルに記録する項目名
+    name: &'static str,
+    key: OrderKey,
+    // カーソルに文字列として記録した値を比較するときの型
+    key_type: KeyType,
+    direction: SortOrder,
+}
+
+// 並び替えのキーの値の型
+#[derive(Clone, Copy)]
+enum KeyType {
+    Text,
+    BigInt,
+    Numeric,
+    Timestamp,
+    Uuid,
+}
+
+impl KeyType {
+    // カーソルに記録した値をこの型として解釈してクエリにバインドする
+    // カーソルはクライアントが書き換えられるため、解釈できない値は不正なカーソルとして扱う
+    fn push_bind(self, query: &mut QueryBuilder<'static, Postgres>, value: &str) -> AppResult<()> {
+        let invalid = || AppError::InvalidCursorError("不正なカーソルです。".into());
+        match self {
+            KeyType::Text if !value.contains('\0') => {
+                query.push_bind(value.to_string());
+            }
+            KeyType::BigInt => {
+                query.push_bind(value.parse::<i64>().map_err(|_| invalid())?);
+            }
+            // 平均の評価は小数点以下の桁数が多く f64 では丸められてしまうため、
+            // 書式だけを確認して文字列のまま numeric として比較する
+            KeyType::Numeric if is_decimal(value) => {
+                query.push_bind(value.to_string()).push("::numeric");
+            }
+            // 日時は PostgreSQL が text に変換した書式（例: 2024-10-04 12:34:56.789+00）で記録している
+            KeyType::Timestamp => {
+                let at = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z")
+                    .map_err(|_| invalid())?;
+                query.push_bind(at.with_timezone(&Utc));
+            }
+            KeyType::Uuid => {
+                query.push_bind(Uuid::from_str(value).map_err(|_| invalid())?);
+            }
+            _ => return Err(invalid()),
+        }
+        Ok(())
+    }
+}
+
+// 符号と小数点を含む 10 進数の書式かどうか
+fn is_decimal(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, "0"));
+    !int.is_empty()
+        && !frac.is_empty()
+        && int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+}
+
+enum OrderKey {
+    Column(&'static str),
+    // 検索キーワードとの関連度
+    Relevance,
+}
+
+// find_all・find_all_by_cursor で蔵書 ID を取得するクエリを組み立てるための型
+// 絞り込み条件や並び順はリクエストごとに変わるため QueryBuilder で組み立てる
+struct BookListSql<'a> {
+    options: &'a BookListOptions,
+    keywords: Vec<String>,
+    order_columns: Vec<OrderColumn>,
+}
+
+impl<'a> BookListSql<'a> {
+    fn new(options: &'a BookListOptions) -> Self {
+        let keywords = split_keywords(options.keyword.as_deref());
+        let mut order_columns = Vec::new();
+        let direction = match options.sort {
+            Some(key) => {
+                let (name, column, key_type) = match key {
+                    BookSortKey::Title => ("title", "b.title", KeyType::Text),
+                    BookSortKey::Author => ("author", "b.author", KeyType::Text),
+                    BookSortKey::CreatedAt => ("created_at", "b.created_at", KeyType::Timestamp),
+                    BookSortKey::UpdatedAt => ("updated_at", "b.updated_at", KeyType::Timestamp),
+                    BookSortKey::Rating => ("rating", AVERAGE_RATING, KeyType::Numeric),
+                };
+                let direction = options.order.unwrap_or(key.default_order());
+                order_columns.push(OrderColumn {
+                    name,
+                    key: OrderKey::Column(column),
+                    key_type,
+                    direction,
+                });
+                direction
+            }
+            None => {
+                if !keywords.is_empty() {
+                    order_columns.push(OrderColumn {
+                        name: "relevance",
+                        key: OrderKey::Relevance,
+                        key_type: KeyType::BigInt,
+                        direction: SortOrder::Desc,
+                    });
+                }
+                let direction = options.order.unwrap_or(SortOrder::Desc);
+                order_columns.push(OrderColumn {
+                    name: "created_at",
+                    key: OrderKey::Column("b.created_at"),
+                    key_type: KeyType::Timestamp,
+                    direction,
+                });
+                direction
+            }
+        };
+        // 並び替えのキーが同じ値の蔵書があってもページ間で順序が揺れないよう、
+        // 最後に book_id で並べる
+        order_columns.push(OrderColumn {
+            name: "book_id",
+            key: OrderKey::Column("b.book_id"),
+            key_type: KeyType::Uuid,
+            direction,
+        });
+
+        Self {
+            options,
+            keywords,
+            order_columns,
+        }
+    }
+
+    // オフセットによるページネーションで、蔵書 ID の一覧と総件数を取得するクエリ
+    fn offset_query(&self) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new(
+            r#"
+                SELECT
+                    COUNT(*) OVER() AS total,
+                    b.book_id AS id
+                FROM books AS b
+                WHERE TRUE
+            "#,
+        );
+        self.push_conditions(&mut query);
+        self.push_order_by(&mut query, false);
+        query
+            .push(" LIMIT ")
+            .push_bind(self.options.limit)
+            .push(" OFFSET ")
+            .push_bind(self.options.offset);
+        query
+    }
+
+    // カーソルによるページネーションで、蔵書 ID と並び替えのキーの値を取得するクエリ
+    // 次のページの有無を判定するため limit + 1 件取得する
+    // 総件数の計算はテーブルが大きくなると重いため行わない
+    fn cursor_query(
+        &self,
+        cursor: Option<&PageCursor>,
+    ) -> AppResult<QueryBuilder<'static, Postgres>> {
+        let mut query = QueryBuilder::new("SELECT b.book_id AS id, ARRAY[");
+        for (i, column) in self.order_columns.iter().enumerate() {
+            if i > 0 {
+                query.push(", ");
+            }
+            self.push_order_key(&mut query, &column.key);
+            query.push("::text");
+        }
+        query.push("] AS keys FROM books AS b WHERE TRUE");
+        self.push_conditions(&mut query);
+
+        let reverse = matches!(cursor, Some(PageCursor::Before(_)));
+        if let Some(PageCursor::After(c) | PageCursor::Before(c)) = cursor {
+            self.push_keyset_condition(&mut query, c, reverse)?;
+        }
+        self.push_order_by(&mut query, reverse);
+        query
+            .push(" LIMIT ")
+            .push_bind(self.options.limit.saturating_add(1));
+        Ok(query)
+    }
+
+    fn push_conditions(&self, query: &mut QueryBuilder<'static, Postgres>) {
+        let BookListOptions {
+            owner,
+            isbn,
+            author,
+            author_id,
+            author_role,
+            availability,
+            tags,
+            location,
+            ..
+        } = self.options;
+
+        // 削除済みの蔵書は一覧や検索の対象にしない
+        query.push(" AND b.deleted_at IS NULL");
+        // キーワードはすべてを含む蔵書に絞り込む
+        if !self.keywords.is_empty() {
+            query
+                .push(" AND NOT EXISTS (SELECT 1 FROM UNNEST(")
+                .push_bind(self.keywords.clone())
+                .push("::text[]) AS k(term) WHERE NOT (")
+                .push(KEYWORD_MATCH)
+                .push("))");
+        }
+        if let Some(owner) = owner {
+            query.push(" AND b.user_id = ").push_bind(*owner);
+        }
+        if let Some(isbn) = isbn {
+            query.push(" AND b.isbn = ").push_bind(isbn.clone());
+        }
+        if let Some(author) = author {
+            query
+                .push(" AND b.author ILIKE ")
+                .push_bind(format!("%{}%", escape_like(author)));
+        }
+        if let Some(author_id) = author_id {
+            query
+                .push(" AND EXISTS (SELECT 1 FROM book_authors AS ba WHERE ba.book_id = b.book_id AND ba.author_id = ")
+                .push_bind(*author_id);
+            if let Some(role) = author_role {
+                query
+                    .push(" AND ba.role = ")
+                    .push_bind(role.as_ref().to_string());
+            }
+            query.push(")");
+        }
+        // タグはすべてを付けられた蔵書に絞り込む
+        if !tags.is_empty() {
+            query
+                .push(" AND NOT EXISTS (SELECT 1 FROM UNNEST(")
+                .push_bind(tags.clone())
+                .push("::uuid[]) AS t(tag_id) WHERE NOT EXISTS (")
+                .push(HAS_TAG)
+                .push("))");
+        }
+        if let Some(location) = location {
+            query
+                .push(" AND EXISTS (")
+                .push(COPY_AT_LOCATION)
+                .push_bind(*location)
+                .push(" = ANY(lp.ancestor_ids))");
+        }
+        match availability {
+            Some(BookAvailability::Available) => {
+                query.push(" AND EXISTS (").push(AVAILABLE_COPY).push(")");
+            }
+            Some(BookAvailability::CheckedOut) => {
+                query
+                    .push(" AND NOT EXISTS (")
+                    .push(AVAILABLE_COPY)
+                    .push(")");
+            }
+            None => {}
+        }
+    }
+
+    fn push_order_key(&self, query: &mut QueryBuilder<'static, Postgres>, key: &OrderKey) {
+        match key {
+            OrderKey::Column(column) => {
+                query.push(column);
+            }
+            OrderKey::Relevance => {
+                query
+                    .push("(SELECT COALESCE(SUM(")
+                    .push(KEYWORD_SCORE)
+                    .push("), 0) FROM UNNEST(")
+                    .push_bind(self.keywords.clone())
+                    .push("::text[]) AS k(term))");
+            }
+        }
+    }
+
+    // reverse が true の場合は逆順に並べる（前のページを取得する場合）
+    fn push_order_by(&self, query: &mut QueryBuilder<'static, Postgres>, reverse: bool) {
+        query.push(" ORDER BY ");
+        for (i, column) in self.order_columns.iter().enumerate() {
+            if i > 0 {
+                query.push(", ");
+            }
+            self.push_order_key(query, &column.key);
+            let direction = match (column.direction, reverse) {
+                (SortOrder::Asc, false) | (SortOrder::Desc, true) => " ASC",
+                (SortOrder::Desc, false) | (SortOrder::Asc, true) => " DESC",
+            };
+            query.push(direction);
+        }
+    }
+
+    // カーソルの位置より後ろ（reverse が true の場合は前）にある行に絞り込む
+    // 項目ごとに並び順が異なりうるので、辞書式順序の比較を展開して組み立てる
+    // (k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...
+    fn push_keyset_condition(
+        &self,
+        query: &mut QueryBuilder<'static, Postgres>,
+        cursor: &Cursor,
+        reverse: bool,
+    ) -> AppResult<()> {
+        if cursor.order_by != self.order_by() || cursor.keys.len() != self.order_columns.len() {
+            return Err(AppError::InvalidCursorError(
+                "並び順が異なるカーソルは使用できません。".into(),
+            ));
+        }
+
+        query.push(" AND (FALSE");
+        for i in 0..self.order_columns.len() {
+            query.push(" OR (TRUE");
+            for (column, value) in self.order_columns.iter().zip(&cursor.keys).take(i + 1) {
+                let is_last = std::ptr::eq(column, &self.order_columns[i]);
+                let op = match (is_last, column.direction, reverse) {
+                    (false, _, _) => " = ",
+                    (true, SortOrder::Asc, false) | (true, SortOrder::Desc, true) => " > ",
+                    (true, SortOrder::Desc, false) | (true, SortOrder::Asc, true) => " < ",
+                };
+                query.push(" AND ");
+                self.push_order_key(query, &column.key);
+                query.push(op);
+                column.key_type.push_bind(query, value)?;
+            }
+            query.push(")");
+        }
+        query.push(")");
+        Ok(())
+    }
+
+    // カーソルに記録する並び順の表現
+    fn order_by(&self) -> String {
+        self.order_columns
+            .iter()
+            .map(|c| {
+                let direction = match c.direction {
+                    SortOrder::Asc => "asc",
+                    SortOrder::Desc => "desc",
+                };
+                format!("{}:{}", c.name, direction)
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+// 検索キーワードを空白（全角スペースを含む）で分割し、
+// LIKE のパターンとして使えるようにエスケープする
+fn split_keywords(keyword: Option<&str>) -> Vec<String> {
+    keyword
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(escape_like)
+        .collect()
+}
+
+// LIKE のワイルドカード文字をエスケープする
+pub(crate) fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, str::FromStr};
+
+    use chrono::Utc;
+    use kernel::{
+        model::{
+            author::AuthorRole,
+            book::{
+                duplicate::DuplicateReason,
+                revision::{BookField, BookFieldChange},
+                CopyCondition,
+            },
+            checkout::event::{CreateCheckout, UpdateReturned},
+            location::{
+                event::{CreateLocation, DeleteLocation},
+                LocationKind,
+            },
+            role::Role,
+            tag::event::{CreateTag, DeleteTag},
+            user::event::CreateUser,
+        },
+        repository::{
+            checkout::CheckoutRepository, location::LocationRepository, tag::TagRepository,
+            user::UserRepository,
+        },
+    };
+
+    use crate::repository::{
+        checkout::CheckoutRepositoryImpl, location::LocationRepositoryImpl, tag::TagRepositoryImpl,
+        user::UserRepositoryImpl,
+    };
+
+    use super::*;
+
+    // 管理者の権限を持たないユーザーとして操作する
+    fn as_user(id: UserId) -> Actor {
+        Actor {
+            id,
+            role: Role::User,
+        }
+    }
+
+    // 1 人の著者だけを指定する
+    fn authored_by(name: &str) -> Vec<AuthorCredit> {
+        vec![AuthorCredit {
+            name: name.into(),
+            role: AuthorRole::Author,
+        }]
+    }
+
+    #[sqlx::test]
+    async fn test_register_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        // 蔵書のデータを追加・取得するためにはユーザー情報がないといけないため
+        // テストコードの方でもロール及びユーザー情報を追加するコードがいる
+        sqlx::query!(r#"INSERT INTO roles(name) VALUES ('Admin'), ('User');"#)
+            .execute(&pool)
+            .await?;
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let user = user_repo
+            .create(CreateUser {
+                name: "Test User".into(),
+                email: "test@exapmle.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+        let book = CreateBook {
+            title: "Test Title".into(),
+            authors: authored_by("Test Author"),
+            // ISBN-10 で登録しても ISBN-13 に正規化される
+            isbn: "4-7980-6170-0".parse()?,
+            description: "Test Description".into(),
+            publisher: None,
+            published_year: None,
+            tags: vec![],
+            series: None,
+        };
+
+        repo.create(book, user.id).await?;
+        // find_all を実行するためには BookListOptions 型が必要なので作る。
+        let options = BookListOptions {
+            limit: 20,
+            offset: 0,
+            ..Default::default()
+        };
+        let res = repo.find_all(options).await?;
+        assert_eq!(res.items.len(), 1);
+
+        let book_id = res.items[0].id;
+        let res = repo.find_by_id(book_id).await?;
+        assert!(res.is_some());
+
+        let Book {
+            id,
+            title,
+            author,
+            isbn,
+            description,
+            owner,
+            ..
+        } = res.unwrap();
+        assert_eq!(id, book_id);
+        assert_eq!(title, "Test Title");
+        assert_eq!(author, "Test Author");
+        assert_eq!(isbn, "9784798061702");
+        assert_eq!(description, "Test Description");
+        assert_eq!(owner.name, "Test User");
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_update_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        // 2. fixture/book.sql で作成済みの書籍を取得
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b").unwrap();
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        const NEW_AUTHOR: &str = "更新後の著者名";
+        assert_ne!(book.author, NEW_AUTHOR);
+
+        // 3. 書籍の更新用のパラメーターを作成し、更新を行う
+        let update_book = UpdateBook {
+            book_id: book.id,
+            title: book.title,
+            authors: authored_by(NEW_AUTHOR),
+            isbn: book.isbn.parse()?,
+            description: book.description,
+            publisher: None,
+            published_year: None,
+            tags: None,
+            series: None,
+            requested_by: as_user(
+                UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c").unwrap(),
+            ),
+            expected_updated_at: None,
+        };
+        repo.update(update_book).await.unwrap();
+
+        // 4. 更新後の書籍を取得し、期待通りに更新されていることを検証する
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.author, NEW_AUTHOR);
+
+        // 5. ほかの蔵書と同じ ISBN には更新できない
+        let res = repo
+            .update(UpdateBook {
+                book_id: book.id,
+                title: book.title,
+                authors: authored_by(&book.author),
+                isbn: "978-4-06-530195-1".parse()?,
+                description: book.description,
+                publisher: None,
+                published_year: None,
+                tags: None,
+                series: None,
+                requested_by: as_user(UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_expected_updated_at(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let fetched = repo.find_by_id(book_id).await?.unwrap();
+
+        let update = |expected_updated_at| UpdateBook {
+            book_id,
+            title: "Updated Title".into(),
+            authors: authored_by(&fetched.author),
+            isbn: "9784798061702".parse().unwrap(),
+            description: fetched.description.clone(),
+            publisher: None,
+            published_year: None,
+            tags: None,
+            series: None,
+            requested_by: as_user(owner),
+            expected_updated_at,
+        };
+
+        // 取得時の最終更新日時と一致しない場合は更新できない
+        let stale = fetched.updated_at - chrono::Duration::milliseconds(1);
+        let res = repo.update(update(Some(vec![stale]))).await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+
+        // いずれかと一致すれば更新でき、最終更新日時が変わる
+        repo.update(update(Some(vec![stale, fetched.updated_at])))
+            .await?;
+        let updated = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(updated.title, "Updated Title");
+        assert_ne!(updated.updated_at, fetched.updated_at);
+
+        // 更新前に取得した最終更新日時では削除できない
+        let res = repo
+            .delete(DeleteBook {
+                book_id,
+                requested_by: as_user(owner),
+                expected_updated_at: Some(vec![fetched.updated_at]),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+        repo.delete(DeleteBook {
+            book_id,
+            requested_by: as_user(owner),
+            expected_updated_at: Some(vec![updated.updated_at]),
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_revisions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = user_repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+
+        let update = |title: &str| UpdateBook {
+            book_id,
+            title: title.into(),
+            authors: authored_by("初田直也他"),
+            isbn: "9784798061702".parse().unwrap(),
+            description: "C/C++の代わりとなるべき最新言語その独特な仕様をわかりやすく解説。".into(),
+            publisher: None,
+            published_year: None,
+            tags: None,
+            series: None,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        };
+
+        // 変更した項目のみが差分として記録される
+        repo.update(update("実践Rustプログラミング入門 第2版"))
+            .await?;
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].revision, 2);
+        assert_eq!(revisions[0].edited_by.as_ref().unwrap().id, owner);
+        assert_eq!(
+            revisions[0].changes,
+            vec![BookFieldChange {
+                field: BookField::Title,
+                before: Some("実践Rustプログラミング入門".into()),
+                after: Some("実践Rustプログラミング入門 第2版".into()),
+            }]
+        );
+        // 最初の版はすべての項目が変更点になる
+        assert_eq!(revisions[1].changes.len(), 4);
+        assert!(revisions[1].changes.iter().all(|c| c.before.is_none()));
+
+        // 内容が変わらない更新は版として記録しない
+        repo.update(update("実践Rustプログラミング入門 第2版"))
+            .await?;
+        assert_eq!(repo.find_revisions(book_id).await?.len(), 2);
+
+        // 所有者でも管理者でもないユーザーは戻せない
+        let res = repo
+            .revert(RevertBook {
+                book_id,
+                revision: 1,
+                requested_by: as_user(other.id),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        // 管理者は所有者でなくても戻せ、戻した内容が新しい版になる
+        repo.revert(RevertBook {
+            book_id,
+            revision: 1,
+            requested_by: Actor {
+                id: other.id,
+                role: Role::Admin,
+            },
+            expected_updated_at: None,
+        })
+        .await?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.title, "実践Rustプログラミング入門");
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[0].edited_by.as_ref().unwrap().id, other.id);
+
+        // 存在しない版には戻せない
+        let res = repo
+            .revert(RevertBook {
+                book_id,
+                revision: 99,
+                requested_by: as_user(owner),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_revisions_publication(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+
+        let update = |publisher: Option<&str>, published_year: Option<i32>| UpdateBook {
+            book_id,
+            title: book.title.clone(),
+            authors: authored_by(&book.author),
+            isbn: book.isbn.parse().unwrap(),
+            description: book.description.clone(),
+            publisher: Some(publisher.map(String::from)),
+            published_year: Some(published_year),
+            tags: None,
+            series: None,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        };
+
+        // 出版社・出版年のみの変更も版として記録される
+        repo.update(update(Some("秀和システム"), Some(2020)))
+            .await?;
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(
+            revisions[0].changes,
+            vec![
+                BookFieldChange {
+                    field: BookField::Publisher,
+                    before: None,
+                    after: Some("秀和システム".into()),
+                },
+                BookFieldChange {
+                    field: BookField::PublishedYear,
+                    before: None,
+                    after: Some("2020".into()),
+                },
+            ]
+        );
+
+        repo.update(update(Some("技術評論社"), Some(2020))).await?;
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(
+            revisions[0].changes,
+            vec![BookFieldChange {
+                field: BookField::Publisher,
+                before: Some("秀和システム".into()),
+                after: Some("技術評論社".into()),
+            }]
+        );
+
+        // 版を戻すと出版社・出版年もその版の時点のものに戻る
+        repo.revert(RevertBook {
+            book_id,
+            revision: 2,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        let reverted = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(reverted.publisher.as_deref(), Some("秀和システム"));
+        assert_eq!(reverted.published_year, Some(2020));
+
+        repo.revert(RevertBook {
+            book_id,
+            revision: 1,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        let reverted = repo.find_by_id(book_id).await?.unwrap();
+        assert!(reverted.publisher.is_none());
+        assert!(reverted.published_year.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_revert_book_isbn(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let revert = |revision, expected_updated_at| RevertBook {
+            book_id,
+            revision,
+            requested_by: as_user(owner),
+            expected_updated_at,
+        };
+
+        // 移行時に印を付けた蔵書と、区切り文字を含む ISBN・正しくない ISBN の版を用意する
+        sqlx::query!(
+            r#"
+                UPDATE books SET isbn = '978-4-7980-6170-2', isbn_issue = 'invalid'
+                WHERE book_id = $1
+            "#,
+            book_id as _
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query!(
+            r#"
+                INSERT INTO book_revisions (book_id, revision, title, author, isbn, description)
+                SELECT book_id, revision + 1, title, author, '978-4-7980-6170-2', description
+                FROM book_revisions WHERE book_id = $1 AND revision = 1
+                UNION ALL
+                SELECT book_id, revision + 2, title, author, 'aaa', description
+                FROM book_revisions WHERE book_id = $1 AND revision = 1
+            "#,
+            book_id as _
+        )
+        .execute(&pool)
+        .await?;
+
+        // 正しくない ISBN の版には戻せない
+        let res = repo.revert(revert(3, None)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 最終更新日時が一致しない場合は戻せない
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        let stale = book.updated_at - chrono::Duration::milliseconds(1);
+        let res = repo.revert(revert(2, Some(vec![stale]))).await;
+        assert!(matches!(res, Err(AppError::PreconditionFailed(_))));
+
+        // 戻すと ISBN は正規化され、移行時の印は外れる
+        repo.revert(revert(2, Some(vec![book.updated_at]))).await?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.isbn, "9784798061702");
+        let issue = sqlx::query_scalar!(
+            "SELECT isbn_issue FROM books WHERE book_id = $1",
+            book_id as _
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert!(issue.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_authors(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        // 登録済みの著者名は 1 人の著者として移行されている
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.authors.len(), 1);
+        assert_eq!(book.authors[0].name, "高野祐輝");
+        let takano = book.authors[0].id;
+
+        let credit = |name: &str, role| AuthorCredit {
+            name: name.into(),
+            role,
+        };
+        repo.update(UpdateBook {
+            book_id,
+            title: book.title.clone(),
+            authors: vec![
+                credit("高野祐輝", AuthorRole::Author),
+                credit("山田太郎", AuthorRole::Editor),
+                credit("John Smith", AuthorRole::Translator),
+                // 同じ著者・役割の重複は 1 つにまとめる
+                credit("山田太郎", AuthorRole::Editor),
+            ],
+            isbn: book.isbn.parse()?,
+            description: book.description.clone(),
+            publisher: None,
+            published_year: None,
+            tags: None,
+            series: None,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+
+        // 指定した順に並び、登録済みの名前は同じ著者として扱われる
+        let updated = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(
+            updated
+                .authors
+                .iter()
+                .map(|a| (a.name.as_str(), a.role))
+                .collect::<Vec<_>>(),
+            vec![
+                ("高野祐輝", AuthorRole::Author),
+                ("山田太郎", AuthorRole::Editor),
+                ("John Smith", AuthorRole::Translator),
+            ]
+        );
+        assert_eq!(updated.authors[0].id, takano);
+        assert_eq!(updated.author, "高野祐輝, 山田太郎 (編), John Smith (訳)");
+
+        // 著者と役割で蔵書を絞り込める
+        let translator = updated.authors[2].id;
+        let find = |author_id, author_role| {
+            repo.find_all(BookListOptions {
+                limit: 20,
+                author_id: Some(author_id),
+                author_role,
+                ..Default::default()
+            })
+        };
+        let res = find(takano, None).await?;
+        assert_eq!(res.items.len(), 1);
+        assert_eq!(res.items[0].id, book_id);
+        assert_eq!(
+            find(translator, Some(AuthorRole::Translator)).await?.total,
+            1
+        );
+        assert_eq!(find(translator, Some(AuthorRole::Author)).await?.total, 0);
+
+        // 版を戻すと著者の一覧も戻る
+        repo.revert(RevertBook {
+            book_id,
+            revision: 1,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        let reverted = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(reverted.author, "高野祐輝");
+        assert_eq!(reverted.authors.len(), 1);
+        assert_eq!(reverted.authors[0].id, takano);
+        assert_eq!(find(translator, None).await?.total, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_publication(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        // 登録済みの蔵書では出版社・出版年は未設定になっている
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.publisher, None);
+        assert_eq!(book.published_year, None);
+
+        let update = |publisher, published_year| UpdateBook {
+            book_id,
+            title: book.title.clone(),
+            authors: authored_by("初田直也他"),
+            isbn: "9784798061702".parse().unwrap(),
+            description: book.description.clone(),
+            publisher,
+            published_year,
+            tags: None,
+            series: None,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        };
+        repo.update(update(Some(Some("翔泳社".into())), Some(Some(2021))))
+            .await?;
+        let updated = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(updated.publisher.as_deref(), Some("翔泳社"));
+        assert_eq!(updated.published_year, Some(2021));
+
+        // 省略した項目は変更せず、null を指定した項目は未設定に戻す
+        repo.update(update(None, Some(None))).await?;
+        let updated = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(updated.publisher.as_deref(), Some("翔泳社"));
+        assert_eq!(updated.published_year, None);
+
+        // 指定した順に取得し、存在しない ID と削除済みの蔵書は含めない
+        let res = repo
+            .find_all_by_ids(&[other_id, BookId::new(), book_id])
+            .await?;
+        assert_eq!(
+            res.iter().map(|b| b.id).collect::<Vec<_>>(),
+            vec![other_id, book_id]
+        );
+        repo.delete(DeleteBook {
+            book_id: other_id,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        let res = repo.find_all_by_ids(&[other_id, book_id]).await?;
+        assert_eq!(res.iter().map(|b| b.id).collect::<Vec<_>>(), vec![book_id]);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_find_duplicates(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let zero_rust = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let event = |title: &str, author: &str, isbn: &str| -> anyhow::Result<CreateBook> {
+            Ok(CreateBook {
+                title: title.into(),
+                authors: authored_by(author),
+                isbn: isbn.parse()?,
+                description: "".into(),
+                publisher: None,
+                published_year: None,
+                tags: vec![],
+                series: None,
+            })
+        };
+
+        // 表記の揺れを除いてタイトルの先頭が一致し、共通する著者がいる蔵書は候補になる
+        let res = repo
+            .find_duplicates(&event(
+                "ゼロから学ぶＲＵＳＴ",
+                "高野 祐輝",
+                "9784297141738",
+            )?)
+            .await?;
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].book_id, zero_rust);
+        assert_eq!(res[0].reason, DuplicateReason::TitleAndAuthor);
+
+        // タイトルが似ていても、著者が異なれば候補にならない
+        let res = repo
+            .find_duplicates(&event("ゼロから学ぶRust", "山田太郎", "9784297141738")?)
+            .await?;
+        assert!(res.is_empty());
+
+        // ISBN が同じ蔵書は、タイトルや著者が異なっても候補になる
+        let res = repo
+            .find_duplicates(&event("別のタイトル", "山田太郎", "4-06-530195-5")?)
+            .await?;
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].book_id, zero_rust);
+        assert_eq!(res[0].reason, DuplicateReason::Isbn);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_isbn(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        // ISBN-10 で指定しても、ISBN-13 で登録された蔵書が見つかる
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                isbn: Some("4-7980-6170-0".parse()?),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].title, "実践Rustプログラミング入門");
+
+        // 同じ ISBN の蔵書は登録できない
+        let res = repo
+            .create(
+                CreateBook {
+                    title: "実践Rustプログラミング入門".into(),
+                    authors: authored_by("初田直也他"),
+                    isbn: "978-4-7980-6170-2".parse()?,
+                    description: "".into(),
+                    publisher: None,
+                    published_year: None,
+                    tags: vec![],
+                    series: None,
+                },
+                owner,
+            )
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_import_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        // 3 行目はすでに登録されている ISBN なので登録できない
+        let rows = || -> anyhow::Result<Vec<ImportBookRow>> {
+            [
+                (2, "4-87311-865-4", "新しい本"),
+                (3, "978-4-7980-6170-2", "実践Rustプログラミング入門"),
+                (4, "4-297-11054-7", "もう一冊の新しい本"),
+            ]
+            .into_iter()
+            .map(|(line, isbn, title)| {
+                Ok(ImportBookRow {
+                    line,
+                    book: CreateBook {
+                        title: title.into(),
+                        authors: authored_by("著者"),
+                        isbn: isbn.parse()?,
+                        description: "".into(),
+                        publisher: None,
+                        published_year: None,
+                        tags: vec![],
+                        series: None,
+                    },
+                })
+            })
+            .collect()
+        };
+        let count = || async {
+            repo.find_all(BookListOptions {
+                limit: 20,
+                ..Default::default()
+            })
+            .await
+            .map(|res| res.total)
+        };
+        let before = count().await?;
+
+        // 失敗した行があれば、他の行も登録しない
+        let results = repo
+            .import(ImportBooks {
+                rows: rows()?,
+                mode: ImportMode::AllOrNothing,
+                requested_user: owner,
+            })
+            .await?;
+        assert_eq!(
+            results.iter().map(|r| r.line).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert_eq!(results[0].status, BookImportStatus::Skipped);
+        assert!(matches!(results[1].status, BookImportStatus::Failed(_)));
+        assert_eq!(results[2].status, BookImportStatus::Skipped);
+        assert_eq!(count().await?, before);
+
+        // 登録できる行だけを登録する
+        let results = repo
+            .import(ImportBooks {
+                rows: rows()?,
+                mode: ImportMode::BestEffort,
+                requested_user: owner,
+            })
+            .await?;
+        assert!(matches!(results[0].status, BookImportStatus::Imported(_)));
+        assert!(matches!(results[1].status, BookImportStatus::Failed(_)));
+        assert!(matches!(results[2].status, BookImportStatus::Imported(_)));
+        assert_eq!(count().await?, before + 2);
+
+        // 登録した蔵書には冊子も 1 冊登録される
+        let BookImportStatus::Imported(book_id) = results[0].status else {
+            unreachable!();
+        };
+        assert_eq!(repo.find_copies(book_id).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_delete_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        repo.delete(DeleteBook {
+            book_id,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        let book = repo.find_by_id(book_id).await?;
+        assert!(book.is_none());
+
+        // 削除した蔵書は一覧に現れず、削除済みの一覧に現れる
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                ..Default::default()
+            })
+            .await?;
+        assert!(res.items.iter().all(|b| b.id != book_id));
+        let res = repo.find_deleted(20, 0).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, book_id);
+        assert!(res.items[0].deleted_at.is_some());
+
+        // 削除済みの蔵書は更新できない
+        let res = repo
+            .update(UpdateBook {
+                book_id,
+                title: "Updated Title".into(),
+                authors: authored_by("Updated Author"),
+                isbn: "9784798061702".parse()?,
+                description: "".into(),
+                publisher: None,
+                published_year: None,
+                tags: None,
+                series: None,
+                requested_by: as_user(owner),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 復元すると再び取得できる
+        repo.restore(RestoreBook { book_id }).await?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert!(book.deleted_at.is_none());
+
+        // 削除されていない蔵書は完全に削除できない
+        let res = repo.purge(PurgeBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 削除中に同じ ISBN の蔵書を登録した場合は、元の蔵書を復元できない
+        repo.delete(DeleteBook {
+            book_id,
+            requested_by: as_user(owner),
+            expected_updated_at: None,
+        })
+        .await?;
+        repo.create(
+            CreateBook {
+                title: "実践Rustプログラミング入門".into(),
+                authors: authored_by("初田直也他"),
+                isbn: "9784798061702".parse()?,
+                description: "".into(),
+                publisher: None,
+                published_year: None,
+                tags: vec![],
+                series: None,
+            },
+            owner,
+        )
+        .await?;
+        let res = repo.restore(RestoreBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        // 完全に削除すると削除済みの一覧からも消える
+        repo.purge(PurgeBook { book_id }).await?;
+        let res = repo.find_deleted(20, 0).await?;
+        assert_eq!(res.total, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_mutation_permission(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other = user_repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?;
+
+        let update = |requested_by| UpdateBook {
+            book_id,
+            title: "実践Rustプログラミング入門 改訂版".into(),
+            authors: authored_by("初田直也他"),
+            isbn: "9784798061702".parse().unwrap(),
+            description: "".into(),
+            publisher: None,
+            published_year: None,
+            tags: None,
+            series: None,
+            requested_by,
+            expected_updated_at: None,
+        };
+
+        // 所有者でないユーザーは変更・削除できない
+        let res = repo.update(update(as_user(other.id))).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo
+            .delete(DeleteBook {
+                book_id,
+                requested_by: as_user(other.id),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        // 管理者は所有者でなくても変更・削除でき、変更は管理者の版として記録される
+        let admin = Actor {
+            id: other.id,
+            role: Role::Admin,
+        };
+        repo.update(update(admin)).await?;
+        let revisions = repo.find_revisions(book_id).await?;
+        assert_eq!(revisions[0].edited_by.as_ref().unwrap().id, other.id);
+        repo.delete(DeleteBook {
+            book_id,
+            requested_by: admin,
+            expected_updated_at: None,
+        })
+        .await?;
+        assert!(repo.find_by_id(book_id).await?.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book_checkout"))]
+    async fn test_delete_checked_out_book(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: UserId::from_str("9582f9de-0fd1-4892-b20c-70139a7eb95b")?,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+
+        // 貸出中の蔵書は削除できない
+        let res = book_repo
+            .delete(DeleteBook {
+                book_id,
+                requested_by: as_user(owner),
+                expected_updated_at: None,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        assert!(book_repo.find_by_id(book_id).await?.is_some());
+
+        // 事前のチェックを経ずに削除しようとしても、データベースが貸出を残したまま拒否する
+        let res = sqlx::query!("DELETE FROM book_copies WHERE book_id = $1", book_id as _)
+            .execute(&pool)
+            .await;
+        assert!(res.is_err());
+        sqlx::query!(
+            "UPDATE books SET deleted_at = CURRENT_TIMESTAMP(3) WHERE book_id = $1",
+            book_id as _
+        )
+        .execute(&pool)
+        .await?;
+        let res = book_repo.purge(PurgeBook { book_id }).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        assert_eq!(checkout_repo.find_unreturned_all().await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_copies(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        // 冊子を 2 冊追加して 3 冊にする
+        let copy_id = repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: Some("LIB-0001".into()),
+                requested_by: as_user(owner),
+            })
+            .await?;
+        repo.add_copy(CreateBookCopy {
+            book_id,
+            barcode: None,
+            requested_by: as_user(owner),
+        })
+        .await?;
+
+        // 同じバーコードの冊子は追加できない
+        let res = repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: Some("LIB-0001".into()),
+                requested_by: as_user(owner),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        // 所有者以外は冊子を追加できない
+        let res = repo
+            .add_copy(CreateBookCopy {
+                book_id,
+                barcode: None,
+                requested_by: as_user(UserId::new()),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
 
-        repo.delete(DeleteBook {
+        // 3 冊とも貸し出すと、貸出可能な冊子がなくなる
+        for _ in 0..3 {
+            checkout_repo
+                .create(CreateCheckout {
+                    book_id,
+                    checked_out_by: owner,
+                    checked_out_at: Utc::now(),
+                })
+                .await?;
+        }
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.total_copies, 3);
+        assert_eq!(book.available_copies, 0);
+        assert_eq!(book.checkouts.len(), 3);
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 10,
+                availability: Some(BookAvailability::CheckedOut),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 1);
+
+        // 貸し出し中の冊子は削除できない
+        let copies = repo.find_copies(book_id).await?;
+        assert_eq!(copies.len(), 3);
+        assert!(copies.iter().all(|copy| copy.checkout.is_some()));
+        let res = repo
+            .delete_copy(DeleteBookCopy {
+                book_id,
+                copy_id,
+                requested_by: as_user(owner),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 返却後は削除できる
+        let checkout = copies
+            .into_iter()
+            .find(|copy| copy.id == copy_id)
+            .and_then(|copy| copy.checkout)
+            .unwrap();
+        checkout_repo
+            .update_returned(UpdateReturned {
+                checkout_id: checkout.checkout_id,
+                book_id,
+                returned_by: owner,
+                returned_at: Utc::now(),
+                location_id: None,
+            })
+            .await?;
+        repo.delete_copy(DeleteBookCopy {
             book_id,
-            requested_user: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?,
+            copy_id,
+            requested_by: as_user(owner),
         })
         .await?;
-        let book = repo.find_by_id(book_id).await?;
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.total_copies, 2);
+        assert_eq!(book.available_copies, 0);
+
+        // 冊子を管理する前の、copy_id のない返却済みの貸出
+        sqlx::query!(
+            r#"
+                INSERT INTO returned_checkouts (checkout_id, book_id, user_id, checked_out_at, due_at)
+                VALUES (gen_random_uuid(), $1, $2, now() - INTERVAL '30 days', now() - INTERVAL '16 days')
+            "#,
+            book_id as _,
+            owner as _
+        )
+        .execute(&pool)
+        .await?;
+
+        // 削除した冊子の貸出や copy_id のない貸出も、冊子の情報なしで貸出履歴に残る
+        let history = checkout_repo.find_history_by_book_id(book_id).await?;
+        assert_eq!(history.len(), 4);
+        let deleted = history
+            .iter()
+            .find(|co| co.id == checkout.checkout_id)
+            .unwrap();
+        assert_eq!(deleted.book.copy_id, Some(copy_id));
+        assert_eq!(deleted.book.barcode, None);
+        assert_eq!(
+            history
+                .iter()
+                .filter(|co| co.book.copy_id.is_none())
+                .count(),
+            1
+        );
+
+        let page = checkout_repo
+            .find_history_by_book_id_by_cursor(
+                book_id,
+                CursorOptions {
+                    limit: 10,
+                    cursor: None,
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.items.iter().map(|co| co.id).collect::<HashSet<_>>(),
+            history.iter().map(|co| co.id).collect::<HashSet<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_copy_condition_and_withdrawal(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let withdrawal = |copy_id, withdrawn| UpdateBookCopyWithdrawal {
+            book_id,
+            copy_id,
+            withdrawn,
+            requested_by: as_user(owner),
+        };
+
+        let copy = repo.find_copies(book_id).await?.remove(0);
+        assert_eq!(copy.condition, CopyCondition::Good);
+        assert!(copy.withdrawn_at.is_none());
+
+        repo.update_copy_condition(UpdateBookCopyCondition {
+            book_id,
+            copy_id: copy.id,
+            condition: CopyCondition::Poor,
+            requested_by: as_user(owner),
+        })
+        .await?;
+        let res = repo
+            .update_copy_condition(UpdateBookCopyCondition {
+                book_id,
+                copy_id: copy.id,
+                condition: CopyCondition::Fair,
+                requested_by: as_user(UserId::new()),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        assert_eq!(
+            repo.find_copies(book_id).await?[0].condition,
+            CopyCondition::Poor
+        );
+
+        // 貸出の対象から外すと貸出可能な冊子の数に含まれず、貸し出せなくなる
+        repo.update_copy_withdrawal(withdrawal(copy.id, true))
+            .await?;
+        let withdrawn_at = repo.find_copies(book_id).await?[0].withdrawn_at;
+        assert!(withdrawn_at.is_some());
+        repo.update_copy_withdrawal(withdrawal(copy.id, true))
+            .await?;
+        assert_eq!(
+            repo.find_copies(book_id).await?[0].withdrawn_at,
+            withdrawn_at
+        );
+        let book = repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.total_copies, 1);
+        assert_eq!(book.available_copies, 0);
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 10,
+                availability: Some(BookAvailability::CheckedOut),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(
+            res.items.iter().map(|b| b.id).collect::<Vec<_>>(),
+            vec![book_id]
+        );
+        let res = checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: owner,
+                checked_out_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 戻すと再び貸し出せるが、貸し出し中の冊子は外せない
+        repo.update_copy_withdrawal(withdrawal(copy.id, false))
+            .await?;
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: owner,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        let res = repo.update_copy_withdrawal(withdrawal(copy.id, true)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        let res = repo
+            .update_copy_withdrawal(withdrawal(CopyId::new(), true))
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_copy_locations(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let location_repo = LocationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        let building = location_repo
+            .create(CreateLocation {
+                parent_id: None,
+                name: "本館".into(),
+                kind: LocationKind::Building,
+            })
+            .await?;
+        let room = location_repo
+            .create(CreateLocation {
+                parent_id: Some(building.id),
+                name: "閲覧室".into(),
+                kind: LocationKind::Room,
+            })
+            .await?;
+        let shelf = location_repo
+            .create(CreateLocation {
+                parent_id: Some(room.id),
+                name: "A-1".into(),
+                kind: LocationKind::Shelf,
+            })
+            .await?;
+        let in_location = |location| BookListOptions {
+            limit: 10,
+            location: Some(location),
+            ..Default::default()
+        };
+
+        // 場所が設定されていない冊子は、どの場所で絞り込んでも含まれない
+        let copy_id = repo.find_copies(book_id).await?[0].id;
+        assert!(repo.find_copies(book_id).await?[0].location.is_none());
+        assert_eq!(repo.find_all(in_location(building.id)).await?.total, 0);
+
+        // 書架に置くと、書架とその上位の部屋・建物のいずれで絞り込んでも含まれる
+        repo.update_copy_location(UpdateBookCopyLocation {
+            book_id,
+            copy_id,
+            location_id: Some(shelf.id),
+            requested_by: as_user(owner),
+        })
+        .await?;
+        let location = repo.find_copies(book_id).await?[0]
+            .location
+            .clone()
+            .unwrap();
+        assert_eq!(location.id, shelf.id);
+        assert_eq!(location.path, vec!["本館", "閲覧室", "A-1"]);
+        for id in [building.id, room.id, shelf.id] {
+            let res = repo.find_all(in_location(id)).await?;
+            assert_eq!(res.total, 1);
+            assert_eq!(res.items[0].id, book_id);
+        }
+
+        // 所有者以外は場所を変更できず、登録されていない場所は指定できない
+        let res = repo
+            .update_copy_location(UpdateBookCopyLocation {
+                book_id,
+                copy_id,
+                location_id: Some(room.id),
+                requested_by: as_user(UserId::new()),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo
+            .update_copy_location(UpdateBookCopyLocation {
+                book_id,
+                copy_id,
+                location_id: Some(LocationId::new()),
+                requested_by: as_user(owner),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 返却時に場所を指定すると、冊子はその場所に移る
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: owner,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        let checkout = repo.find_copies(book_id).await?.remove(0).checkout.unwrap();
+        let res = checkout_repo
+            .update_returned(UpdateReturned {
+                checkout_id: checkout.checkout_id,
+                book_id,
+                returned_by: owner,
+                returned_at: Utc::now(),
+                location_id: Some(LocationId::new()),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        checkout_repo
+            .update_returned(UpdateReturned {
+                checkout_id: checkout.checkout_id,
+                book_id,
+                returned_by: owner,
+                returned_at: Utc::now(),
+                location_id: Some(room.id),
+            })
+            .await?;
+        let copies = repo.find_copies(book_id).await?;
+        assert!(copies[0].checkout.is_none());
+        assert_eq!(copies[0].location.as_ref().map(|l| l.id), Some(room.id));
+        assert_eq!(repo.find_all(in_location(shelf.id)).await?.total, 0);
+        assert_eq!(repo.find_all(in_location(room.id)).await?.total, 1);
+
+        // 場所を削除すると、置かれていた冊子の場所は未設定に戻る
+        location_repo
+            .delete(DeleteLocation {
+                location_id: shelf.id,
+            })
+            .await?;
+        location_repo
+            .delete(DeleteLocation {
+                location_id: room.id,
+            })
+            .await?;
+        assert!(repo.find_copies(book_id).await?[0].location.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_tags(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let tag_repo = TagRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let rust = tag_repo
+            .create(CreateTag {
+                name: "Rust".into(),
+            })
+            .await?;
+        let beginner = tag_repo
+            .create(CreateTag {
+                name: "入門".into(),
+            })
+            .await?;
+
+        // タグを付けて更新する
+        let tag_book = |book: Book, tags: Option<Vec<TagId>>| -> anyhow::Result<UpdateBook> {
+            Ok(UpdateBook {
+                book_id: book.id,
+                title: book.title,
+                authors: authored_by(&book.author),
+                isbn: book.isbn.parse()?,
+                description: book.description,
+                publisher: None,
+                published_year: None,
+                tags,
+                series: None,
+                requested_by: as_user(owner),
+                expected_updated_at: None,
+            })
+        };
+        let book1 = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let book2 = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let book = repo.find_by_id(book1).await?.unwrap();
+        repo.update(tag_book(book, Some(vec![rust.id, beginner.id]))?)
+            .await?;
+        let book = repo.find_by_id(book2).await?.unwrap();
+        repo.update(tag_book(book, Some(vec![rust.id]))?).await?;
+
+        let book = repo.find_by_id(book1).await?.unwrap();
+        assert_eq!(book.tags, vec![rust.clone(), beginner.clone()]);
+
+        // tags を指定しない更新ではタグは変わらない
+        repo.update(tag_book(book, None)?).await?;
+        let book = repo.find_by_id(book1).await?.unwrap();
+        assert_eq!(book.tags.len(), 2);
+
+        // 存在しないタグは付けられない
+        let res = repo.update(tag_book(book, Some(vec![TagId::new()]))?).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 指定したタグをすべて付けられた蔵書に絞り込む
+        let tagged = |tags: Vec<TagId>| BookListOptions {
+            limit: 10,
+            tags,
+            ..Default::default()
+        };
+        let res = repo.find_all(tagged(vec![rust.id])).await?;
+        assert_eq!(res.total, 2);
+        let res = repo.find_all(tagged(vec![rust.id, beginner.id])).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, book1);
+        assert_eq!(res.items[0].tags.len(), 2);
+
+        // タグを削除すると蔵書からも外れる
+        tag_repo
+            .delete(DeleteTag {
+                tag_id: beginner.id,
+            })
+            .await?;
+        let book = repo.find_by_id(book1).await?.unwrap();
+        assert_eq!(book.tags, vec![rust]);
 
-        assert!(book.is_none());
         Ok(())
     }
 
@@ -347,6 +3211,7 @@ mod tests {
             .find_all(BookListOptions {
                 limit: 10,
                 offset: 0,
+                ..Default::default()
             })
             .await?;
         assert_eq!(res.total, LEN);
@@ -358,6 +3223,7 @@ mod tests {
             .find_all(BookListOptions {
                 limit: 10,
                 offset: 10,
+                ..Default::default()
             })
             .await?;
         assert_eq!(res.total, LEN);
@@ -369,6 +3235,7 @@ mod tests {
             .find_all(BookListOptions {
                 limit: 10,
                 offset: 100,
+                ..Default::default()
             })
             .await?;
         assert_eq!(res.total, 0); // offset が total を超える場合は 0 になる
@@ -379,10 +3246,325 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_search_books(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let search = |keyword: &str| BookListOptions {
+            limit: 20,
+            offset: 0,
+            keyword: Some(keyword.into()),
+            ..Default::default()
+        };
+
+        // 日本語のタイトルも部分一致で検索できる
+        let res = repo.find_all(search("線形型")).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].author, "高野祐輝");
+
+        // 空白区切りの複数キーワードはすべてを含むものに絞り込む
+        let res = repo.find_all(search("Rust　入門")).await?;
+        assert_eq!(res.total, 2);
+        assert_eq!(res.items[0].title, "実践Rustプログラミング入門");
+
+        // ISBN はハイフンの有無を問わず検索できる
+        let res = repo.find_all(search("9784065301951")).await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].author, "高野祐輝");
+
+        // 説明文のみに一致するものよりタイトルに一致するものが先にくる
+        let res = repo.find_all(search("Rust")).await?;
+        assert_eq!(res.total, 3);
+        let res = repo.find_all(search("実践")).await?;
+        assert_eq!(res.total, 2);
+        assert_eq!(res.items[0].title, "実践Rustプログラミング入門");
+
+        // LIKE のワイルドカードは文字として扱う
+        let res = repo.find_all(search("%")).await?;
+        assert_eq!(res.total, 0);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book_list"))]
+    async fn test_list_sort(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let sorted = |sort: BookSortKey, order: Option<SortOrder>| BookListOptions {
+            limit: 10,
+            offset: 0,
+            sort: Some(sort),
+            order,
+            ..Default::default()
+        };
+
+        // 文字列のキーは既定で昇順
+        let res = repo.find_all(sorted(BookSortKey::Title, None)).await?;
+        assert_eq!(res.items[0].title, "title001");
+        let res = repo
+            .find_all(sorted(BookSortKey::Author, Some(SortOrder::Desc)))
+            .await?;
+        assert_eq!(res.items[0].author, "author050");
+
+        // 日時のキーは既定で降順
+        let res = repo.find_all(sorted(BookSortKey::UpdatedAt, None)).await?;
+        assert_eq!(res.items[0].title, "title050");
+        let res = repo
+            .find_all(sorted(BookSortKey::CreatedAt, Some(SortOrder::Asc)))
+            .await?;
+        assert_eq!(res.items[0].title, "title001");
+
+        // 2 ページ目も同じ並び順の続きになる
+        let res = repo
+            .find_all(BookListOptions {
+                offset: 10,
+                ..sorted(BookSortKey::Title, Some(SortOrder::Asc))
+            })
+            .await?;
+        assert_eq!(res.total, 50);
+        assert_eq!(res.items[0].title, "title011");
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book_list"))]
+    async fn test_list_by_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let sorted = || BookListOptions {
+            limit: 20,
+            sort: Some(BookSortKey::Title),
+            ..Default::default()
+        };
+
+        // 先頭のページには前のページがない
+        let first = repo.find_all_by_cursor(sorted(), None).await?;
+        assert_eq!(first.items.len(), 20);
+        assert_eq!(first.items[0].title, "title001");
+        assert!(first.prev_cursor.is_none());
+        let next = first.next_cursor.unwrap();
+
+        // 次のページは前のページの続きから始まる
+        let second = repo
+            .find_all_by_cursor(sorted(), Some(PageCursor::After(next)))
+            .await?;
+        assert_eq!(second.items[0].title, "title021");
+        assert_eq!(second.items[19].title, "title040");
+        let prev = second.prev_cursor.unwrap();
+
+        // 最後のページには次のページがない
+        let last = repo
+            .find_all_by_cursor(
+                sorted(),
+                Some(PageCursor::After(second.next_cursor.unwrap())),
+            )
+            .await?;
+        assert_eq!(last.items.len(), 10);
+        assert!(last.next_cursor.is_none());
+
+        // 前のページに戻ると先頭のページと同じ内容になる
+        let back = repo
+            .find_all_by_cursor(sorted(), Some(PageCursor::Before(prev.clone())))
+            .await?;
+        assert_eq!(back.items[0].title, "title001");
+        assert_eq!(back.items[19].title, "title020");
+        assert!(back.prev_cursor.is_none());
+
+        // 並び順の異なるリクエストでカーソルを使うとエラーになる
+        let res = repo
+            .find_all_by_cursor(
+                BookListOptions {
+                    sort: Some(BookSortKey::CreatedAt),
+                    ..sorted()
+                },
+                Some(PageCursor::After(prev)),
+            )
+            .await;
+        assert!(matches!(res, Err(AppError::InvalidCursorError(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book_list"))]
+    async fn test_list_by_tampered_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let sorted = |sort| BookListOptions {
+            limit: 20,
+            sort: Some(sort),
+            ..Default::default()
+        };
+
+        // 日時・評価のキーを持つカーソルでも続きのページを取得できる
+        for sort in [BookSortKey::CreatedAt, BookSortKey::Rating] {
+            let first = repo.find_all_by_cursor(sorted(sort), None).await?;
+            let second = repo
+                .find_all_by_cursor(
+                    sorted(sort),
+                    Some(PageCursor::After(first.next_cursor.unwrap())),
+                )
+                .await?;
+            assert_eq!(second.items.len(), 20);
+            assert!(first.items.iter().all(|b| b.id != second.items[0].id));
+        }
+
+        // 並び順は正しいが、キーの値を型として解釈できないカーソルはエラーになる
+        let cursor = repo
+            .find_all_by_cursor(sorted(BookSortKey::CreatedAt), None)
+            .await?
+            .next_cursor
+            .unwrap();
+        let rating = repo
+            .find_all_by_cursor(sorted(BookSortKey::Rating), None)
+            .await?
+            .next_cursor
+            .unwrap();
+        let title = repo
+            .find_all_by_cursor(sorted(BookSortKey::Title), None)
+            .await?
+            .next_cursor
+            .unwrap();
+        let tampered = [
+            (
+                BookSortKey::CreatedAt,
+                &cursor,
+                vec!["abc", &cursor.keys[1]],
+            ),
+            (
+                BookSortKey::CreatedAt,
+                &cursor,
+                vec![&cursor.keys[0], "abc"],
+            ),
+            (BookSortKey::Rating, &rating, vec!["1e5", &rating.keys[1]]),
+            (BookSortKey::Title, &title, vec!["title\0", &title.keys[1]]),
+        ];
+        for (sort, cursor, keys) in tampered {
+            let cursor = Cursor {
+                order_by: cursor.order_by.clone(),
+                keys: keys.into_iter().map(String::from).collect(),
+            };
+            let res = repo
+                .find_all_by_cursor(sorted(sort), Some(PageCursor::After(cursor)))
+                .await;
+            assert!(matches!(res, Err(AppError::InvalidCursorError(_))));
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_list_conditions(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let checked_out = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        sqlx::query!(
+            r#"
+                INSERT INTO checkouts (book_id, copy_id, user_id, due_at)
+                SELECT book_id, copy_id, $2, now() + INTERVAL '14 days' FROM book_copies WHERE book_id = $1
+            "#,
+            checked_out as _,
+            owner as _
+        )
+        .execute(&pool)
+        .await?;
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                owner: Some(owner),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 3);
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                owner: Some(UserId::new()),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 0);
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                author: Some("高野".into()),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].author, "高野祐輝");
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                availability: Some(BookAvailability::CheckedOut),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 1);
+        assert_eq!(res.items[0].id, checked_out);
+        assert_eq!(res.items[0].checkouts.len(), 1);
+
+        let res = repo
+            .find_all(BookListOptions {
+                limit: 20,
+                availability: Some(BookAvailability::Available),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.total, 2);
+        assert!(res.items.iter().all(|b| b.checkouts.is_empty()));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book_list"))]
+    async fn test_export_all(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        let total = repo
+            .find_all(BookListOptions {
+                limit: 1,
+                ..Default::default()
+            })
+            .await?
+            .total;
+        let book_id = repo
+            .find_all(BookListOptions {
+                limit: 1,
+                ..Default::default()
+            })
+            .await?
+            .into_inner()
+            .remove(0)
+            .id;
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: user_id,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+
+        let books = repo.export_all().collect::<AppResult<Vec<_>>>().await?;
+        assert_eq!(books.len() as i64, total);
+
+        // 貸出情報も合わせて出力される
+        let book = books.iter().find(|b| b.id == book_id).unwrap();
+        assert_eq!(book.checkouts.len(), 1);
+        assert_eq!(book.checkouts[0].checked_out_by.id, user_id);
+        assert!(books
+            .iter()
+            .filter(|b| b.id != book_id)
+            .all(|b| b.checkouts.is_empty()));
+
+        Ok(())
+    }
+
     #[sqlx::test(fixtures("common", "book_checkout"))]
     async fn test_book_checkout(pool: sqlx::PgPool) -> anyhow::Result<()> {
         let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
-        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
 
         // 事前登録したユーザーの ID (fixtures/book_checkout.sql参照)
         let user_id1 = UserId::from_str("9582f9de-0fd1-4892-b20c-70139a7eb95b").unwrap();
@@ -392,6 +3574,7 @@ mod tests {
             .find_all(BookListOptions {
                 limit: 20,
                 offset: 0,
+                ..Default::default()
             })
             .await?
             .into_inner()
@@ -399,7 +3582,7 @@ mod tests {
             .unwrap();
 
         // 初期の貸出状態が none であることを確認
-        assert!(book.checkout.is_none());
+        assert!(book.checkouts.is_empty());
 
         // 1回目の貸出の蔵書の戻り値のテスト
         {
@@ -412,9 +3595,9 @@ mod tests {
                 .await?;
 
             // 貸出がある状態での蔵書の戻り値
-            let book_co = book_repo.find_by_id(book.id).await?.unwrap();
-            assert!(book_co.checkout.is_some());
-            let co = book_co.checkout.unwrap();
+            let mut book_co = book_repo.find_by_id(book.id).await?.unwrap();
+            assert_eq!(book_co.checkouts.len(), 1);
+            let co = book_co.checkouts.remove(0);
             assert_eq!(co.checked_out_by.id, user_id1);
 
             // 返却
@@ -424,13 +3607,14 @@ mod tests {
                     book_id: book_co.id,
                     returned_by: user_id1,
                     returned_at: Utc::now(),
+                    location_id: None,
                 })
                 .await?;
 
             // 返却後の蔵書の戻り値
             // -> Book#checkout が存在し、貸出時に指定したユーザーIDになってる
             let book_re = book_repo.find_by_id(book.id).await?.unwrap();
-            assert!(book_re.checkout.is_none());
+            assert!(book_re.checkouts.is_empty());
         }
 
         // 2回目の貸出の蔵書の戻り値のテスト
@@ -444,9 +3628,9 @@ mod tests {
                 })
                 .await?;
 
-            let book_co = book_repo.find_by_id(book.id).await?.unwrap();
-            assert!(book_co.checkout.is_some());
-            let co = book_co.checkout.unwrap();
+            let mut book_co = book_repo.find_by_id(book.id).await?.unwrap();
+            assert_eq!(book_co.checkouts.len(), 1);
+            let co = book_co.checkouts.remove(0);
             assert_eq!(co.checked_out_by.id, user_id2);
 
             // 返却
@@ -456,12 +3640,13 @@ mod tests {
                     book_id: book_co.id,
                     returned_by: user_id2,
                     returned_at: Utc::now(),
+                    location_id: None,
                 })
                 .await?;
 
             // 返却後の蔵書の戻り値
             let book_re = book_repo.find_by_id(book.id).await?.unwrap();
-            assert!(book_re.checkout.is_none());
+            assert!(book_re.checkouts.is_empty());
         }
 
         Ok(())
@@ -1,25 +1,42 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
 use derive_new::new;
 use kernel::{
     model::{
         checkout::{
-            event::{CreateCheckout, UpdateReturned},
-            Checkout,
+            event::{CreateCheckout, CreateHold, CreateRenewal, DeleteHold, UpdateReturned},
+            Checkout, CheckoutRenewal,
         },
-        id::{BookId, CheckoutId, UserId},
+        id::{BookId, CheckoutId, CopyId, UserId},
+        list::{CursorOptions, CursorPaginatedList},
     },
     repository::checkout::CheckoutRepository,
 };
 use shared::error::{AppError, AppResult};
 
-use crate::database::{
-    model::checkout::{CheckoutRow, CheckoutStateRow, ReturnedCheckoutRow},
-    ConnectionPool,
+use crate::{
+    database::{
+        model::{
+            checkout::{
+                CheckoutHistoryRow, CheckoutRenewalRow, CheckoutRow, CheckoutStateRow,
+                RenewStateRow, ReturnStateRow, ReturnedCheckoutRow,
+            },
+            list::TimestampCursor,
+        },
+        ConnectionPool,
+    },
+    repository::location::map_location_not_found,
 };
 
 #[derive(new)]
 pub struct CheckoutRepositoryImpl {
     db: ConnectionPool,
+    // 貸出日から返却期限までの日数
+    loan_period_days: i64,
+    // 1 回の貸出で延長できる回数の上限
+    max_renewals: i64,
 }
 
 #[async_trait]
@@ -33,20 +50,29 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
 
         // 事前のチェックとして以下を調べる
         // - 指定の蔵書の ID を持つ蔵書が存在するか
-        // - 存在した場合、この蔵書は貸し出し中でないか
+        // - 存在した場合、この蔵書に貸し出し中でなく、貸出の対象から外してもいない冊子があるか
         //
-        // 上記の両方が Yes だった場合、このブロック以降の処理に進む
-        {
+        // 上記の両方が Yes だった場合、その冊子を貸し出す
+        let copy_id = {
             let res = sqlx::query_as!(
                 CheckoutStateRow,
                 r#"
                     SELECT
                     b.book_id,
-                    c.checkout_id AS "checkout_id?: CheckoutId",
-                    NULL AS "user_id?: UserId"
+                    (
+                        SELECT bc.copy_id
+                        FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND bc.withdrawn_at IS NULL
+                        AND NOT EXISTS (
+                            SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id
+                        )
+                        ORDER BY bc.created_at ASC, bc.copy_id ASC
+                        LIMIT 1
+                    ) AS "copy_id?: CopyId"
                     FROM books AS b
-                    LEFT OUTER JOIN checkouts AS c USING(book_id)
-                    WHERE book_id = $1;
+                    WHERE b.book_id = $1
+                    AND   b.deleted_at IS NULL;
                 "#,
                 event.book_id as _
             )
@@ -62,31 +88,36 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                         event.book_id
                     )))
                 }
-                Some(CheckoutStateRow {
-                    checkout_id: Some(_),
-                    ..
-                }) => {
+                // すべての冊子が貸し出し中の場合
+                Some(CheckoutStateRow { copy_id: None, .. }) => {
                     return Err(AppError::UnprocessableEntiry(format!(
-                        "書籍（{}）に対する貸出が既に存在します。",
+                        "書籍（{}）に貸出可能な冊子がありません。",
                         event.book_id
                     )))
                 }
-                _ => {}
+                Some(CheckoutStateRow {
+                    copy_id: Some(copy_id),
+                    ..
+                }) => copy_id,
             }
-        }
+        };
 
         // 貸し出し処理を行う
+        // 返却期限は貸出日から既定の貸出期間が経過した日時とする
         let checkout_id = CheckoutId::new();
+        let due_at = event.checked_out_at + Duration::days(self.loan_period_days);
         let res = sqlx::query!(
             r#"
                 INSERT INTO checkouts
-                (checkout_id, book_id, user_id, checked_out_at)
-                VALUES ($1, $2, $3, $4);
+                (checkout_id, book_id, copy_id, user_id, checked_out_at, due_at)
+                VALUES ($1, $2, $3, $4, $5, $6);
             "#,
             checkout_id as _,
             event.book_id as _,
+            copy_id as _,
             event.checked_out_by as _,
             event.checked_out_at,
+            due_at,
         )
         .execute(&mut *tx)
         .await
@@ -98,11 +129,239 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
             ));
         }
 
+        // 借りたユーザーがこの蔵書を予約していた場合、その予約は果たされたので削除する
+        sqlx::query!(
+            r#"
+                DELETE FROM book_holds WHERE book_id = $1 AND user_id = $2;
+            "#,
+            event.book_id as _,
+            event.checked_out_by as _,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    // 延長操作
+    async fn renew(&self, event: CreateRenewal) -> AppResult<CheckoutRenewal> {
+        let mut tx = self.db.begin().await?;
+
+        self.set_transaction_serializable(&mut tx).await?;
+
+        // 延長操作時は事前のチェックとして、以下を調べる
+        // - 指定の蔵書 ID を持つ蔵書が存在するか
+        // - 存在した場合
+        //   - 指定の貸出 ID の貸出がこの蔵書の冊子に対する貸し出し中のものであり
+        //   - かつ借りたユーザーが指定のユーザーであり
+        //   - かつ延長した回数が上限に達していないか
+        //   - かつほかのユーザーがこの蔵書を予約していないか
+        // すべて Yes だった場合、延長前の返却期限を取り出して以降の処理に進む
+        let previous_due_at = {
+            let res = sqlx::query_as!(
+                RenewStateRow,
+                r#"
+                    SELECT
+                    b.book_id,
+                    c.user_id AS "user_id?: UserId",
+                    c.due_at AS "due_at?",
+                    (
+                        SELECT COUNT(*)
+                        FROM checkout_renewals AS r
+                        WHERE r.checkout_id = c.checkout_id
+                    ) AS "renewal_count!",
+                    EXISTS (
+                        SELECT 1
+                        FROM book_holds AS h
+                        WHERE h.book_id = b.book_id
+                        AND h.user_id <> c.user_id
+                    ) AS "held_by_others!"
+                    FROM books AS b
+                    LEFT OUTER JOIN checkouts AS c
+                        ON c.book_id = b.book_id
+                        AND c.checkout_id = $2
+                    WHERE b.book_id = $1;
+                "#,
+                event.book_id as _,
+                event.checkout_id as _,
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            match res {
+                None => {
+                    return Err(AppError::EntityNotFound(format!(
+                        "書籍（{}）が見つかりませんでした。",
+                        event.book_id
+                    )))
+                }
+                Some(RenewStateRow {
+                    user_id: Some(user_id),
+                    due_at: Some(due_at),
+                    renewal_count,
+                    held_by_others,
+                    ..
+                }) if user_id == event.renewed_by => {
+                    if renewal_count >= self.max_renewals {
+                        return Err(AppError::UnprocessableEntiry(format!(
+                            "指定の貸出（ID（{}））は延長できる回数の上限（{} 回）に達しています。",
+                            event.checkout_id, self.max_renewals
+                        )));
+                    }
+                    if held_by_others {
+                        return Err(AppError::UnprocessableEntiry(format!(
+                            "書籍（{}）はほかのユーザーが予約しているため、貸出（ID（{}））は延長できません。",
+                            event.book_id, event.checkout_id
+                        )));
+                    }
+                    due_at
+                }
+                _ => {
+                    return Err(AppError::UnprocessableEntiry(format!(
+                        "指定の貸出（ID（{}）、ユーザー（{}）、書籍（{}））は延長できません。",
+                        event.checkout_id, event.renewed_by, event.book_id
+                    )))
+                }
+            }
+        };
+
+        // 返却期限を既定の貸出期間だけ後ろにずらす
+        let due_at = previous_due_at + Duration::days(self.loan_period_days);
+        let res = sqlx::query!(
+            r#"
+                UPDATE checkouts SET due_at = $2 WHERE checkout_id = $1;
+            "#,
+            event.checkout_id as _,
+            due_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::NoRowAffectedError(
+                "No checkout record has been updated".into(),
+            ));
+        }
+
+        // 延長したことを記録する
+        sqlx::query!(
+            r#"
+                INSERT INTO checkout_renewals
+                (checkout_id, renewed_by, previous_due_at, due_at, renewed_at)
+                VALUES ($1, $2, $3, $4, $5);
+            "#,
+            event.checkout_id as _,
+            event.renewed_by as _,
+            previous_due_at,
+            due_at,
+            event.renewed_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(CheckoutRenewal {
+            renewed_at: event.renewed_at,
+            previous_due_at,
+            due_at,
+        })
+    }
+
+    // 予約操作
+    async fn create_hold(&self, event: CreateHold) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        self.set_transaction_serializable(&mut tx).await?;
+
+        // 予約操作時は事前のチェックとして、以下を調べる
+        // - 指定の蔵書 ID を持つ削除されていない蔵書が存在するか
+        // - 存在した場合、指定のユーザーがこの蔵書をすでに予約していないか
+        {
+            let res = sqlx::query!(
+                r#"
+                    SELECT
+                    EXISTS (
+                        SELECT 1
+                        FROM book_holds AS h
+                        WHERE h.book_id = b.book_id
+                        AND h.user_id = $2
+                    ) AS "held!"
+                    FROM books AS b
+                    WHERE b.book_id = $1
+                    AND   b.deleted_at IS NULL;
+                "#,
+                event.book_id as _,
+                event.held_by as _,
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+            match res {
+                None => {
+                    return Err(AppError::EntityNotFound(format!(
+                        "書籍（{}）が見つかりませんでした。",
+                        event.book_id
+                    )))
+                }
+                Some(res) if res.held => {
+                    return Err(AppError::UnprocessableEntiry(format!(
+                        "書籍（{}）はすでに予約しています。",
+                        event.book_id
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO book_holds (book_id, user_id, held_at)
+                VALUES ($1, $2, $3);
+            "#,
+            event.book_id as _,
+            event.held_by as _,
+            event.held_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
         tx.commit().await.map_err(AppError::TransactionError)?;
 
         Ok(())
     }
 
+    // 予約の取り消し操作
+    async fn delete_hold(&self, event: DeleteHold) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM book_holds WHERE book_id = $1 AND user_id = $2;
+            "#,
+            event.book_id as _,
+            event.held_by as _,
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(format!(
+                "書籍（{}）の予約が見つかりませんでした。",
+                event.book_id
+            )));
+        }
+
+        Ok(())
+    }
+
     // 返却処理を行う
     // Q. ここの event.checkout_id はどこから来てるか調べよう
     async fn update_returned(&self, event: UpdateReturned) -> AppResult<()> {
@@ -113,22 +372,24 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         // 返却操作時は事前のチェックとして、以下を調べる
         // - 指定の蔵書 ID を持つ蔵書が存在するか
         // - 存在した場合
-        //   - この蔵書は貸し出し中であり
+        //   - 指定の貸出 ID の貸出がこの蔵書の冊子に対する貸し出し中のものであり
         //   - かつ借りたユーザーが指定のユーザーであるか
         // 上記の両方が Yes だった場合、このブロック以降の処理に進む
         {
             let res = sqlx::query_as!(
-                CheckoutStateRow,
+                ReturnStateRow,
                 r#"
                     SELECT
                     b.book_id,
-                    c.checkout_id AS "checkout_id?: CheckoutId",
                     c.user_id AS "user_id?: UserId"
                     FROM books AS b
-                    LEFT OUTER JOIN checkouts AS c USING(book_id)
-                    WHERE book_id = $1;
+                    LEFT OUTER JOIN checkouts AS c
+                        ON c.book_id = b.book_id
+                        AND c.checkout_id = $2
+                    WHERE b.book_id = $1;
                 "#,
                 event.book_id as _,
+                event.checkout_id as _,
             )
             .fetch_optional(&mut *tx)
             .await
@@ -141,19 +402,12 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
                         event.book_id
                     )))
                 }
-                Some(CheckoutStateRow {
-                    checkout_id: Some(c),
-                    user_id: Some(u),
-                    ..
-                }) if (c, u) != (event.checkout_id, event.returned_by) => {
+                Some(ReturnStateRow { user_id, .. }) if user_id != Some(event.returned_by) => {
                     return Err(AppError::UnprocessableEntiry(format!(
                         "指定の貸出（ID（{}）、ユーザー（{}）、書籍（{}））は返却できません。",
                         event.checkout_id, event.returned_by, event.book_id
                     )))
                 }
-                // あれ、checkout_id とかが None の場合とかはここでは検査しない？
-                // うまく表現できないけど、検査はしていないように見えるけど、
-                // 実質的に見ないといけないのが上の条件だけみたいなパターンがありそう
                 _ => {}
             }
         }
@@ -161,8 +415,8 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         let res = sqlx::query!(
             r#"
                 INSERT INTO returned_checkouts
-                (checkout_id, book_id, user_id, checked_out_at, returned_at)
-                SELECT checkout_id, book_id, user_id, checked_out_at, $2
+                (checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, returned_at)
+                SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, $2
                 FROM checkouts
                 WHERE checkout_id = $1;
             "#,
@@ -179,6 +433,22 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
             ));
         }
 
+        // 返却先の場所が指定された場合は、冊子をその場所に置いたものとする
+        if let Some(location_id) = event.location_id {
+            sqlx::query!(
+                r#"
+                    UPDATE book_copies
+                    SET location_id = $2
+                    WHERE copy_id = (SELECT copy_id FROM checkouts WHERE checkout_id = $1);
+                "#,
+                event.checkout_id as _,
+                location_id as _,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(map_location_not_found)?;
+        }
+
         // 上記処理が成功したら checkouts テーブルから該当貸出 ID のレコードを削除する
         let res = sqlx::query!(
             r#"
@@ -205,45 +475,92 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         // checkouts テーブルにあるレコードを全権抽出する
         // books テーブルと INNER JOIN して、蔵書の情報も一緒に抽出する
         // 出力するレコードは貸出日の古い順に並べる
-        sqlx::query_as!(
+        let checkouts = sqlx::query_as!(
             CheckoutRow,
             r#"
                 SELECT
                 c.checkout_id,
                 c.book_id,
+                c.copy_id,
+                bc.barcode,
                 c.user_id,
                 c.checked_out_at,
+                c.due_at,
                 b.title,
                 b.author,
                 b.isbn
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
                 ORDER BY c.checked_out_at ASC;
             "#,
         )
         .fetch_all(self.db.inner_ref())
         .await
-        .map(|rows| rows.into_iter().map(Checkout::from).collect())
-        .map_err(AppError::SpecificOperationError)
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Checkout::from)
+        .collect();
+
+        self.attach_renewals(checkouts).await
+    }
+
+    async fn find_overdue_all(&self, now: DateTime<Utc>) -> AppResult<Vec<Checkout>> {
+        // find_unreturned_all の SQL に
+        // 返却期限で絞り込む WHERE を追加したもの
+        let checkouts = sqlx::query_as!(
+            CheckoutRow,
+            r#"
+                SELECT
+                c.checkout_id,
+                c.book_id,
+                c.copy_id,
+                bc.barcode,
+                c.user_id,
+                c.checked_out_at,
+                c.due_at,
+                b.title,
+                b.author,
+                b.isbn
+                FROM checkouts AS c
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
+                WHERE c.due_at < $1
+                ORDER BY c.checked_out_at ASC;
+            "#,
+            now
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Checkout::from)
+        .collect();
+
+        self.attach_renewals(checkouts).await
     }
 
     // ユーザー　ID に紐づく未返却の貸出情報を取得する
     async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>> {
         // find_unreturned_all の SQL に
         // ユーザー ID で絞り込む WHERE を追加したもの
-        sqlx::query_as!(
+        let checkouts = sqlx::query_as!(
             CheckoutRow,
             r#"
                 SELECT
                 c.checkout_id,
                 c.book_id,
+                c.copy_id,
+                bc.barcode,
                 c.user_id,
                 c.checked_out_at,
+                c.due_at,
                 b.title,
                 b.author,
                 b.isbn
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
                 WHERE c.user_id = $1
                 ORDER BY c.checked_out_at ASC;
             "#,
@@ -251,8 +568,12 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         )
         .fetch_all(self.db.inner_ref())
         .await
-        .map(|rows| rows.into_iter().map(Checkout::from).collect())
-        .map_err(AppError::SpecificOperationError)
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Checkout::from)
+        .collect();
+
+        self.attach_renewals(checkouts).await
     }
 
     // 蔵書の貸出履歴（返却済みも含む）を取得する
@@ -262,22 +583,27 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         // そのため、未返却の貸出情報と返却済みの貸出情報をそれぞれ取得し、
         // 未返却の貸出情報があれば Vec に挿入して返す、という実装とする
         // 未返却の貸出情報を取得
-        let checkout: Option<Checkout> = self.find_unreturned_by_book_id(book_id).await?;
+        // 冊子が複数ある場合は、複数の貸出が同時に貸し出し中となりうる
+        let mut checkouts: Vec<Checkout> = self.find_unreturned_by_book_id(book_id).await?;
         // 返却済みの貸出情報を取得
-        let mut checkout_histories: Vec<Checkout> = sqlx::query_as!(
+        let checkout_histories: Vec<Checkout> = sqlx::query_as!(
             ReturnedCheckoutRow,
             r#"
                 SELECT
                 rc.checkout_id,
                 rc.book_id,
+                rc.copy_id AS "copy_id?: CopyId",
+                bc.barcode AS "barcode?",
                 rc.user_id,
                 rc.checked_out_at,
+                rc.due_at,
                 rc.returned_at,
                 b.title,
                 b.author,
                 b.isbn
                 FROM returned_checkouts AS rc
                 INNER JOIN books AS b USING(book_id)
+                LEFT OUTER JOIN book_copies AS bc ON bc.copy_id = rc.copy_id
                 WHERE rc.book_id = $1
                 ORDER BY rc.checked_out_at DESC
             "#,
@@ -291,16 +617,111 @@ impl CheckoutRepository for CheckoutRepositoryImpl {
         .collect();
 
         // 貸出中である場合は返却済みの履歴の先頭に追加する
-        if let Some(co) = checkout {
-            checkout_histories.insert(0, co);
-        }
+        // 貸出中のものを返却済みの履歴の先頭に並べる
+        checkouts.extend(checkout_histories);
+
+        self.attach_renewals(checkouts).await
+    }
+
+    async fn find_unreturned_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        self.find_unreturned_by_cursor(None, None, options).await
+    }
 
-        Ok(checkout_histories)
+    async fn find_overdue_all_by_cursor(
+        &self,
+        now: DateTime<Utc>,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        self.find_unreturned_by_cursor(None, Some(now), options)
+            .await
+    }
+
+    async fn find_unreturned_by_user_id_by_cursor(
+        &self,
+        user_id: UserId,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        self.find_unreturned_by_cursor(Some(user_id), None, options)
+            .await
+    }
+
+    async fn find_history_by_book_id_by_cursor(
+        &self,
+        book_id: BookId,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        const ORDER_BY: &str = "checked_out_at:desc,checkout_id:desc";
+        let (after, before) = TimestampCursor::split(options.cursor.as_ref(), ORDER_BY)?;
+        let (after_at, after_id) = after.map(|c| (c.at, c.id)).unzip();
+        let (before_at, before_id) = before.map(|c| (c.at, c.id)).unzip();
+
+        // 貸出中・返却済みの貸出情報をまとめて、貸出日の新しい順に並べる
+        // 前のページを取得する場合は逆順に取得し、CursorPaginatedList::from_rows で並べ直す
+        let rows = sqlx::query_as!(
+            CheckoutHistoryRow,
+            r#"
+                SELECT
+                h.checkout_id AS "checkout_id!: CheckoutId",
+                h.book_id AS "book_id!: BookId",
+                h.copy_id AS "copy_id?: CopyId",
+                bc.barcode AS "barcode?",
+                h.user_id AS "user_id!: UserId",
+                h.checked_out_at AS "checked_out_at!",
+                h.due_at AS "due_at!",
+                h.returned_at,
+                b.title,
+                b.author,
+                b.isbn
+                FROM (
+                    SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, NULL::timestamptz AS returned_at
+                    FROM checkouts
+                    WHERE book_id = $1
+                    UNION ALL
+                    SELECT checkout_id, book_id, copy_id, user_id, checked_out_at, due_at, returned_at
+                    FROM returned_checkouts
+                    WHERE book_id = $1
+                ) AS h
+                INNER JOIN books AS b USING(book_id)
+                LEFT OUTER JOIN book_copies AS bc ON bc.copy_id = h.copy_id
+                WHERE ($2::timestamptz IS NULL OR (h.checked_out_at, h.checkout_id) < ($2, $3::uuid))
+                AND   ($4::timestamptz IS NULL OR (h.checked_out_at, h.checkout_id) > ($4, $5::uuid))
+                ORDER BY
+                    CASE WHEN $4::timestamptz IS NOT NULL THEN h.checked_out_at END ASC,
+                    CASE WHEN $4::timestamptz IS NOT NULL THEN h.checkout_id END ASC,
+                    h.checked_out_at DESC,
+                    h.checkout_id DESC
+                LIMIT $6
+            "#,
+            book_id as _,
+            after_at,
+            after_id,
+            before_at,
+            before_id,
+            options.limit.saturating_add(1),
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut list = CursorPaginatedList::from_rows(rows, &options, |row| {
+            TimestampCursor {
+                at: row.checked_out_at,
+                id: row.checkout_id.raw(),
+            }
+            .into_cursor(ORDER_BY)
+        })
+        .map(Checkout::from);
+        list.items = self.attach_renewals(list.items).await?;
+
+        Ok(list)
     }
 }
 
 impl CheckoutRepositoryImpl {
-    // create, update_returned メソッドでのトランザクションを利用するにあたり
+    // create, renew, update_returned メソッドでのトランザクションを利用するにあたり
     // トランザクション分離レベルを SERIALIZABLE にするために
     // 内部的に使うメソッド
     async fn set_transaction_serializable(
@@ -315,30 +736,144 @@ impl CheckoutRepositoryImpl {
         Ok(())
     }
 
+    // 未返却の貸出情報をカーソルによるページネーションで取得する
+    // user_id が指定された場合はそのユーザーの貸出のみに、
+    // overdue_at が指定された場合は返却期限がその日時より前の貸出のみに絞り込む
+    async fn find_unreturned_by_cursor(
+        &self,
+        user_id: Option<UserId>,
+        overdue_at: Option<DateTime<Utc>>,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>> {
+        const ORDER_BY: &str = "checked_out_at:asc,checkout_id:asc";
+        let (after, before) = TimestampCursor::split(options.cursor.as_ref(), ORDER_BY)?;
+        let (after_at, after_id) = after.map(|c| (c.at, c.id)).unzip();
+        let (before_at, before_id) = before.map(|c| (c.at, c.id)).unzip();
+
+        // 貸出日の古い順に並べる
+        // 前のページを取得する場合は逆順に取得し、CursorPaginatedList::from_rows で並べ直す
+        let rows = sqlx::query_as!(
+            CheckoutRow,
+            r#"
+                SELECT
+                c.checkout_id,
+                c.book_id,
+                c.copy_id,
+                bc.barcode,
+                c.user_id,
+                c.checked_out_at,
+                c.due_at,
+                b.title,
+                b.author,
+                b.isbn
+                FROM checkouts AS c
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
+                WHERE ($1::uuid IS NULL OR c.user_id = $1)
+                AND   ($7::timestamptz IS NULL OR c.due_at < $7)
+                AND   ($2::timestamptz IS NULL OR (c.checked_out_at, c.checkout_id) > ($2, $3::uuid))
+                AND   ($4::timestamptz IS NULL OR (c.checked_out_at, c.checkout_id) < ($4, $5::uuid))
+                ORDER BY
+                    CASE WHEN $4::timestamptz IS NOT NULL THEN c.checked_out_at END DESC,
+                    CASE WHEN $4::timestamptz IS NOT NULL THEN c.checkout_id END DESC,
+                    c.checked_out_at ASC,
+                    c.checkout_id ASC
+                LIMIT $6
+            "#,
+            user_id as _,
+            after_at,
+            after_id,
+            before_at,
+            before_id,
+            options.limit.saturating_add(1),
+            overdue_at,
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut list = CursorPaginatedList::from_rows(rows, &options, |row| {
+            TimestampCursor {
+                at: row.checked_out_at,
+                id: row.checkout_id.raw(),
+            }
+            .into_cursor(ORDER_BY)
+        })
+        .map(Checkout::from);
+        list.items = self.attach_renewals(list.items).await?;
+
+        Ok(list)
+    }
+
+    // 貸出ごとの延長の記録を取得して設定する
+    async fn attach_renewals(&self, mut checkouts: Vec<Checkout>) -> AppResult<Vec<Checkout>> {
+        if checkouts.is_empty() {
+            return Ok(checkouts);
+        }
+        let checkout_ids = checkouts.iter().map(|co| co.id).collect::<Vec<_>>();
+        let rows = sqlx::query_as!(
+            CheckoutRenewalRow,
+            r#"
+                SELECT
+                checkout_id,
+                renewed_at,
+                previous_due_at,
+                due_at
+                FROM checkout_renewals
+                WHERE checkout_id = ANY($1)
+                ORDER BY renewed_at ASC;
+            "#,
+            checkout_ids.as_slice() as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let mut renewals: HashMap<CheckoutId, Vec<CheckoutRenewal>> = HashMap::new();
+        for row in rows {
+            renewals
+                .entry(row.checkout_id)
+                .or_default()
+                .push(CheckoutRenewal::from(row));
+        }
+        for checkout in &mut checkouts {
+            checkout.renewals = renewals.remove(&checkout.id).unwrap_or_default();
+        }
+
+        Ok(checkouts)
+    }
+
     // find_history_by_book_id で未返却の貸し出し情報を取得するために
     // 内部的に使うメソッド
-    async fn find_unreturned_by_book_id(&self, book_id: BookId) -> AppResult<Option<Checkout>> {
+    async fn find_unreturned_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>> {
         let res = sqlx::query_as!(
             CheckoutRow,
             r#"
                 SELECT
                 c.checkout_id,
                 c.book_id,
+                c.copy_id,
+                bc.barcode,
                 c.user_id,
                 c.checked_out_at,
+                c.due_at,
                 b.title,
                 b.author,
                 b.isbn
                 FROM checkouts AS c
                 INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = c.copy_id
                 WHERE c.book_id = $1
+                ORDER BY c.checked_out_at DESC
             "#,
             book_id as _,
         )
-        .fetch_optional(self.db.inner_ref())
+        .fetch_all(self.db.inner_ref())
         .await
         .map_err(AppError::SpecificOperationError)?
-        .map(Checkout::from);
+        .into_iter()
+        .map(Checkout::from)
+        .collect();
 
         Ok(res)
     }
@@ -349,12 +884,12 @@ mod tests {
     use std::str::FromStr;
 
     use chrono::Utc;
-    use kernel::model::{checkout::CheckoutBook, id::BookId};
+    use kernel::model::{checkout::CheckoutBook, id::BookId, list::PageCursor};
 
     use super::*;
 
     fn init_repo(pool: sqlx::PgPool) -> (CheckoutRepositoryImpl, UserId, UserId, BookId) {
-        let repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool));
+        let repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool), 14, 2);
 
         // 事前登録したユーザー & 蔵書の ID (fixtures/checkout.sql参照)
         let user_id1 = UserId::from_str("9582f9de-0fd1-4892-b20c-70139a7eb95b").unwrap();
@@ -375,7 +910,7 @@ mod tests {
         let res = repo.find_unreturned_by_user_id(user_id2).await?;
         assert!(res.is_empty());
 
-        let co = repo.find_unreturned_by_book_id(book_id1).await?;
+        let co = repo.find_unreturned_by_book_id(book_id1).await?.pop();
         assert!(co.is_none());
 
         // 存在しない書籍への貸出は失敗する
@@ -399,7 +934,7 @@ mod tests {
             })
             .await?;
 
-            let co = repo.find_unreturned_by_book_id(book_id1).await?;
+            let co = repo.find_unreturned_by_book_id(book_id1).await?.pop();
             assert!(matches!(co, Some(
                     Checkout{
                         checked_out_by,
@@ -427,6 +962,7 @@ mod tests {
                     book_id: BookId::new(),
                     returned_by: user_id1,
                     returned_at: Utc::now(),
+                    location_id: None,
                 })
                 .await;
             assert!(matches!(res, Err(AppError::EntityNotFound(_))));
@@ -438,6 +974,7 @@ mod tests {
                     book_id: book_id1,
                     returned_by: user_id1,
                     returned_at: Utc::now(),
+                    location_id: None,
                 })
                 .await;
             assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
@@ -449,6 +986,7 @@ mod tests {
                     book_id: book_id1,
                     returned_by: user_id2,
                     returned_at: Utc::now(),
+                    location_id: None,
                 })
                 .await;
             assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
@@ -459,6 +997,7 @@ mod tests {
                 book_id: book_id1,
                 returned_by: user_id1,
                 returned_at: Utc::now(),
+                location_id: None,
             })
             .await?;
         }
@@ -478,7 +1017,11 @@ mod tests {
             })
             .await?;
 
-            let co = repo.find_unreturned_by_book_id(book_id1).await?.unwrap();
+            let co = repo
+                .find_unreturned_by_book_id(book_id1)
+                .await?
+                .pop()
+                .unwrap();
 
             // リスト出力
             {
@@ -500,6 +1043,7 @@ mod tests {
                 book_id: book_id1,
                 returned_by: user_id1,
                 returned_at: Utc::now(),
+                location_id: None,
             })
             .await?;
 
@@ -528,7 +1072,11 @@ mod tests {
             })
             .await?;
 
-            let co = repo.find_unreturned_by_book_id(book_id1).await?.unwrap();
+            let co = repo
+                .find_unreturned_by_book_id(book_id1)
+                .await?
+                .pop()
+                .unwrap();
 
             // リストの出力
             {
@@ -550,6 +1098,7 @@ mod tests {
                 book_id: book_id1,
                 returned_by: user_id2,
                 returned_at: Utc::now(),
+                location_id: None,
             })
             .await?;
 
@@ -571,4 +1120,419 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test(fixtures("common", "checkout"))]
+    async fn test_checkout_history_by_cursor(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let (repo, user_id1, _, book_id1) = init_repo(pool);
+
+        // 3 回貸し出して返却する
+        for _ in 0..3 {
+            repo.create(CreateCheckout {
+                book_id: book_id1,
+                checked_out_by: user_id1,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+            let co = repo
+                .find_unreturned_by_book_id(book_id1)
+                .await?
+                .pop()
+                .unwrap();
+            repo.update_returned(UpdateReturned {
+                checkout_id: co.id,
+                book_id: book_id1,
+                returned_by: user_id1,
+                returned_at: Utc::now(),
+                location_id: None,
+            })
+            .await?;
+        }
+        let all = repo.find_history_by_book_id(book_id1).await?;
+        assert_eq!(all.len(), 3);
+
+        let first = repo
+            .find_history_by_book_id_by_cursor(
+                book_id1,
+                CursorOptions {
+                    limit: 2,
+                    cursor: None,
+                },
+            )
+            .await?;
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.items[0].id, all[0].id);
+        assert!(first.prev_cursor.is_none());
+
+        let second = repo
+            .find_history_by_book_id_by_cursor(
+                book_id1,
+                CursorOptions {
+                    limit: 2,
+                    cursor: first.next_cursor.map(PageCursor::After),
+                },
+            )
+            .await?;
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].id, all[2].id);
+        assert!(second.next_cursor.is_none());
+
+        let back = repo
+            .find_history_by_book_id_by_cursor(
+                book_id1,
+                CursorOptions {
+                    limit: 2,
+                    cursor: second.prev_cursor.map(PageCursor::Before),
+                },
+            )
+            .await?;
+        assert_eq!(
+            back.items.iter().map(|co| co.id).collect::<Vec<_>>(),
+            vec![all[0].id, all[1].id]
+        );
+
+        // 貸出中の一覧は空になる
+        let res = repo
+            .find_unreturned_all_by_cursor(CursorOptions {
+                limit: 2,
+                cursor: None,
+            })
+            .await?;
+        assert!(res.items.is_empty());
+        assert!(res.next_cursor.is_none());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "checkout"))]
+    async fn test_checkout_multiple_copies(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let (repo, user_id1, user_id2, book_id1) = init_repo(pool.clone());
+
+        // 2 冊目の冊子を登録する
+        sqlx::query!(
+            "INSERT INTO book_copies (book_id, barcode) VALUES ($1, 'second-copy')",
+            book_id1 as _
+        )
+        .execute(&pool)
+        .await?;
+
+        // 冊子が 2 冊あるので、2 人に同時に貸し出せる
+        for user_id in [user_id1, user_id2] {
+            repo.create(CreateCheckout {
+                book_id: book_id1,
+                checked_out_by: user_id,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        }
+        let res = repo.find_unreturned_all().await?;
+        assert_eq!(res.len(), 2);
+        assert_ne!(res[0].book.copy_id, res[1].book.copy_id);
+
+        // 3 人目への貸出は失敗する
+        let res = repo
+            .create(CreateCheckout {
+                book_id: book_id1,
+                checked_out_by: user_id1,
+                checked_out_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 返却するとその冊子を再び貸し出せる
+        let co = repo.find_unreturned_by_user_id(user_id2).await?.remove(0);
+        repo.update_returned(UpdateReturned {
+            checkout_id: co.id,
+            book_id: book_id1,
+            returned_by: user_id2,
+            returned_at: Utc::now(),
+            location_id: None,
+        })
+        .await?;
+        repo.create(CreateCheckout {
+            book_id: book_id1,
+            checked_out_by: user_id1,
+            checked_out_at: Utc::now(),
+        })
+        .await?;
+        let res = repo.find_unreturned_by_user_id(user_id1).await?;
+        assert_eq!(res.len(), 2);
+        assert!(res.iter().any(|c| c.book.copy_id == co.book.copy_id));
+
+        let res = repo.find_history_by_book_id(book_id1).await?;
+        assert_eq!(res.len(), 3);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "checkout"))]
+    async fn test_checkout_due_dates(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let (repo, user_id1, user_id2, book_id1) = init_repo(pool.clone());
+        sqlx::query!(
+            "INSERT INTO book_copies (book_id, barcode) VALUES ($1, 'second-copy')",
+            book_id1 as _
+        )
+        .execute(&pool)
+        .await?;
+
+        // user_id1 には 20 日前に、user_id2 には今日貸し出す
+        let now = Utc::now();
+        for (user_id, checked_out_at) in [(user_id1, now - Duration::days(20)), (user_id2, now)] {
+            repo.create(CreateCheckout {
+                book_id: book_id1,
+                checked_out_by: user_id,
+                checked_out_at,
+            })
+            .await?;
+        }
+
+        // 返却期限は貸出日から既定の貸出期間が経過した日時になる
+        let res = repo.find_unreturned_all().await?;
+        assert_eq!(res.len(), 2);
+        for co in &res {
+            assert_eq!(co.due_at - co.checked_out_at, Duration::days(14));
+        }
+        assert!(res[0].is_overdue(now));
+        assert!(!res[1].is_overdue(now));
+
+        // 返却期限を過ぎた貸出のみを返す
+        let overdue = repo.find_overdue_all(now).await?;
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].checked_out_by, user_id1);
+
+        let page = repo
+            .find_overdue_all_by_cursor(
+                now,
+                CursorOptions {
+                    limit: 10,
+                    cursor: None,
+                },
+            )
+            .await?;
+        assert_eq!(
+            page.items.iter().map(|co| co.id).collect::<Vec<_>>(),
+            vec![overdue[0].id]
+        );
+
+        // 返却すると延滞の一覧から外れ、履歴には返却期限が残る
+        repo.update_returned(UpdateReturned {
+            checkout_id: overdue[0].id,
+            book_id: book_id1,
+            returned_by: user_id1,
+            returned_at: now,
+            location_id: None,
+        })
+        .await?;
+        assert!(repo.find_overdue_all(now).await?.is_empty());
+
+        let history = repo.find_history_by_book_id(book_id1).await?;
+        let returned = history.iter().find(|co| co.id == overdue[0].id).unwrap();
+        assert_eq!(returned.due_at, overdue[0].due_at);
+        assert!(!returned.is_overdue(now));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "checkout"))]
+    async fn test_checkout_renewals(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let (repo, user_id1, user_id2, book_id1) = init_repo(pool);
+
+        repo.create(CreateCheckout {
+            book_id: book_id1,
+            checked_out_by: user_id1,
+            checked_out_at: Utc::now(),
+        })
+        .await?;
+        let co = repo.find_unreturned_by_user_id(user_id1).await?.remove(0);
+        assert!(co.renewals.is_empty());
+
+        // 存在しない書籍の貸出は延長できない
+        let res = repo
+            .renew(CreateRenewal {
+                checkout_id: co.id,
+                book_id: BookId::new(),
+                renewed_by: user_id1,
+                renewed_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 借りたユーザー以外は延長できない
+        let res = repo
+            .renew(CreateRenewal {
+                checkout_id: co.id,
+                book_id: book_id1,
+                renewed_by: user_id2,
+                renewed_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        // 上限の 2 回までは、延長するたびに返却期限が貸出期間だけ延びる
+        let mut due_at = co.due_at;
+        for _ in 0..2 {
+            let renewal = repo
+                .renew(CreateRenewal {
+                    checkout_id: co.id,
+                    book_id: book_id1,
+                    renewed_by: user_id1,
+                    renewed_at: Utc::now(),
+                })
+                .await?;
+            assert_eq!(renewal.previous_due_at, due_at);
+            assert_eq!(renewal.due_at, due_at + Duration::days(14));
+            due_at = renewal.due_at;
+        }
+
+        // 3 回目の延長は失敗する
+        let res = repo
+            .renew(CreateRenewal {
+                checkout_id: co.id,
+                book_id: book_id1,
+                renewed_by: user_id1,
+                renewed_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        let res = repo.find_unreturned_all().await?;
+        assert_eq!(res[0].due_at, due_at);
+        assert_eq!(res[0].renewals.len(), 2);
+
+        // 返却後も延長の記録は貸出履歴に残り、返却済みの貸出は延長できない
+        repo.update_returned(UpdateReturned {
+            checkout_id: co.id,
+            book_id: book_id1,
+            returned_by: user_id1,
+            returned_at: Utc::now(),
+            location_id: None,
+        })
+        .await?;
+        let history = repo.find_history_by_book_id(book_id1).await?;
+        assert_eq!(history[0].due_at, due_at);
+        assert_eq!(
+            history[0]
+                .renewals
+                .iter()
+                .map(|r| r.due_at)
+                .collect::<Vec<_>>(),
+            vec![co.due_at + Duration::days(14), due_at]
+        );
+
+        let res = repo
+            .renew(CreateRenewal {
+                checkout_id: co.id,
+                book_id: book_id1,
+                renewed_by: user_id1,
+                renewed_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "checkout"))]
+    async fn test_checkout_holds(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let (repo, user_id1, user_id2, book_id1) = init_repo(pool);
+
+        repo.create(CreateCheckout {
+            book_id: book_id1,
+            checked_out_by: user_id1,
+            checked_out_at: Utc::now(),
+        })
+        .await?;
+        let co = repo.find_unreturned_by_user_id(user_id1).await?.remove(0);
+        let renew = || CreateRenewal {
+            checkout_id: co.id,
+            book_id: book_id1,
+            renewed_by: user_id1,
+            renewed_at: Utc::now(),
+        };
+
+        // 存在しない書籍は予約できない
+        let res = repo
+            .create_hold(CreateHold {
+                book_id: BookId::new(),
+                held_by: user_id2,
+                held_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // ほかのユーザーが予約すると延長できない
+        repo.create_hold(CreateHold {
+            book_id: book_id1,
+            held_by: user_id2,
+            held_at: Utc::now(),
+        })
+        .await?;
+        let res = repo
+            .create_hold(CreateHold {
+                book_id: book_id1,
+                held_by: user_id2,
+                held_at: Utc::now(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        let res = repo.renew(renew()).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        let res = repo.find_unreturned_by_user_id(user_id1).await?;
+        assert_eq!(res[0].due_at, co.due_at);
+        assert!(res[0].renewals.is_empty());
+
+        // 自分の予約は延長を妨げない
+        repo.delete_hold(DeleteHold {
+            book_id: book_id1,
+            held_by: user_id2,
+        })
+        .await?;
+        repo.create_hold(CreateHold {
+            book_id: book_id1,
+            held_by: user_id1,
+            held_at: Utc::now(),
+        })
+        .await?;
+        repo.renew(renew()).await?;
+
+        // 予約していない場合は取り消せない
+        let res = repo
+            .delete_hold(DeleteHold {
+                book_id: book_id1,
+                held_by: user_id2,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 予約したユーザーが貸し出すと、その予約は削除される
+        repo.create_hold(CreateHold {
+            book_id: book_id1,
+            held_by: user_id2,
+            held_at: Utc::now(),
+        })
+        .await?;
+        repo.update_returned(UpdateReturned {
+            checkout_id: co.id,
+            book_id: book_id1,
+            returned_by: user_id1,
+            returned_at: Utc::now(),
+            location_id: None,
+        })
+        .await?;
+        repo.create(CreateCheckout {
+            book_id: book_id1,
+            checked_out_by: user_id2,
+            checked_out_at: Utc::now(),
+        })
+        .await?;
+        let res = repo
+            .delete_hold(DeleteHold {
+                book_id: book_id1,
+                held_by: user_id2,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,421 @@
+use std::{io::Cursor, sync::Arc};
+
+use async_trait::async_trait;
+use derive_new::new;
+use image::ImageFormat;
+use kernel::{
+    model::{
+        cover::{
+            event::{DeleteCover, UploadCover},
+            CoverImage, CoverImageFormat, CoverImageSize, MAX_COVER_IMAGE_BYTES,
+            THUMBNAIL_MAX_DIMENSION,
+        },
+        id::BookId,
+    },
+    repository::cover::CoverImageRepository,
+};
+use shared::error::{AppError, AppResult};
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use crate::{
+    database::ConnectionPool, repository::book::authorize_book_mutation, storage::LocalStorage,
+};
+
+#[derive(new)]
+pub struct CoverImageRepositoryImpl {
+    db: ConnectionPool,
+    storage: Arc<LocalStorage>,
+}
+
+#[async_trait]
+impl CoverImageRepository for CoverImageRepositoryImpl {
+    async fn store(&self, event: UploadCover) -> AppResult<()> {
+        if event.data.len() > MAX_COVER_IMAGE_BYTES {
+            return Err(AppError::UnprocessableEntiry(format!(
+                "cover image must be at most {MAX_COVER_IMAGE_BYTES} bytes"
+            )));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        let previous = lock_cover(&mut tx, event.book_id).await?;
+
+        // 画像のデコードとリサイズは重いので、非同期ランタイムのスレッドを塞がないようにする
+        let format = event.format;
+        let original = event.data;
+        let (original, thumbnail) =
+            tokio::task::spawn_blocking(move || -> AppResult<(Vec<u8>, Vec<u8>)> {
+                let thumbnail = make_thumbnail(format, &original)?;
+                Ok((original, thumbnail))
+            })
+            .await
+            .map_err(|e| AppError::StorageError(std::io::Error::other(e)))??;
+
+        // 現在の表紙画像を上書きしないよう、新しいキーに書き込んでから DB の記録を切り替える
+        // コミットまでに失敗した場合は新しいファイルを削除し、元の表紙画像をそのまま残す
+        let cover_id = Uuid::new_v4();
+        let res = async {
+            self.storage
+                .put(
+                    &cover_key(event.book_id, Some(cover_id), CoverImageSize::Original),
+                    &original,
+                )
+                .await?;
+            self.storage
+                .put(
+                    &cover_key(event.book_id, Some(cover_id), CoverImageSize::Thumbnail),
+                    &thumbnail,
+                )
+                .await?;
+            sqlx::query!(
+                r#"
+                    UPDATE books
+                    SET cover_format = $1, cover_id = $2
+                    WHERE book_id = $3
+                "#,
+                event.format.as_ref(),
+                cover_id,
+                event.book_id as _
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            tx.commit().await.map_err(AppError::TransactionError)
+        }
+        .await;
+        if let Err(e) = res {
+            self.remove_files(event.book_id, Some(cover_id)).await;
+            return Err(e);
+        }
+
+        // 差し替える前の表紙画像のファイルは、コミットしてから削除する
+        if let Some(previous) = previous {
+            self.remove_files(event.book_id, previous).await;
+        }
+
+        Ok(())
+    }
+
+    async fn find(&self, book_id: BookId, size: CoverImageSize) -> AppResult<Option<CoverImage>> {
+        let row = sqlx::query!(
+            r#"
+                SELECT cover_format, cover_id
+                FROM books
+                WHERE book_id = $1
+                AND   deleted_at IS NULL
+            "#,
+            book_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let Some((format, cover_id)) =
+            row.and_then(|r| r.cover_format.map(|format| (format, r.cover_id)))
+        else {
+            return Ok(None);
+        };
+        let format = format
+            .parse::<CoverImageFormat>()
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+
+        Ok(self
+            .storage
+            .get(&cover_key(book_id, cover_id, size))
+            .await?
+            .map(|data| CoverImage { format, data }))
+    }
+
+    async fn delete(&self, event: DeleteCover) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_book_mutation(&mut tx, event.book_id, &event.requested_by).await?;
+        let Some(cover_id) = lock_cover(&mut tx, event.book_id).await? else {
+            return Err(AppError::EntityNotFound(
+                "specified cover image not found".into(),
+            ));
+        };
+        sqlx::query!(
+            r#"
+                UPDATE books
+                SET cover_format = NULL, cover_id = NULL
+                WHERE book_id = $1
+            "#,
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        // コミットに失敗した場合に表紙画像が失われないよう、ファイルはコミットしてから削除する
+        self.remove_files(event.book_id, cover_id).await;
+
+        Ok(())
+    }
+
+    async fn purge(&self, book_id: BookId) -> AppResult<()> {
+        self.storage.delete_all(&cover_dir(book_id)).await
+    }
+}
+
+impl CoverImageRepositoryImpl {
+    // 表紙画像のファイルを削除する
+    // DB の記録はコミット済みのため、削除に失敗してもエラーにはせず、ファイルが残るだけとする
+    async fn remove_files(&self, book_id: BookId, cover_id: Option<Uuid>) {
+        let res = match cover_id {
+            Some(cover_id) => {
+                self.storage
+                    .delete_all(&format!("{}/{cover_id}", cover_dir(book_id)))
+                    .await
+            }
+            None => {
+                async {
+                    for size in [CoverImageSize::Original, CoverImageSize::Thumbnail] {
+                        self.storage.delete(&cover_key(book_id, None, size)).await?;
+                    }
+                    AppResult::Ok(())
+                }
+                .await
+            }
+        };
+        if let Err(e) = res {
+            tracing::warn!(error.message = %e, "failed to delete cover image files");
+        }
+    }
+}
+
+// 同じ蔵書の表紙画像を同時に差し替えないよう行をロックし、現在の表紙画像の ID を取得する
+// 表紙画像が登録されていない場合は None、ID を記録する前に登録した表紙画像の場合は Some(None) を返す
+async fn lock_cover(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    book_id: BookId,
+) -> AppResult<Option<Option<Uuid>>> {
+    let row = sqlx::query!(
+        r#"
+            SELECT cover_format, cover_id
+            FROM books
+            WHERE book_id = $1
+            FOR UPDATE
+        "#,
+        book_id as _
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?;
+
+    Ok(row.cover_format.map(|_| row.cover_id))
+}
+
+fn cover_dir(book_id: BookId) -> String {
+    format!("covers/{}", book_id.raw())
+}
+
+// 表紙画像を保存するファイルのキー。形式は DB に記録するので拡張子は付けない
+// 表紙画像の ID を記録する前に登録したものは、ID を含まないキーに保存されている
+fn cover_key(book_id: BookId, cover_id: Option<Uuid>, size: CoverImageSize) -> String {
+    match cover_id {
+        Some(cover_id) => format!("{}/{cover_id}/{}", cover_dir(book_id), size.as_ref()),
+        None => format!("{}/{}", cover_dir(book_id), size.as_ref()),
+    }
+}
+
+pub(crate) fn image_format(format: CoverImageFormat) -> ImageFormat {
+    match format {
+        CoverImageFormat::Png => ImageFormat::Png,
+        CoverImageFormat::Jpeg => ImageFormat::Jpeg,
+        CoverImageFormat::Webp => ImageFormat::WebP,
+    }
+}
+
+// 画像の中身が指定された形式であることを確かめ、同じ形式のサムネイルを生成する
+fn make_thumbnail(format: CoverImageFormat, data: &[u8]) -> AppResult<Vec<u8>> {
+    let format = image_format(format);
+    if image::guess_format(data).ok() != Some(format) {
+        return Err(AppError::UnsupportedMediaType(
+            "cover image content does not match its content type".into(),
+        ));
+    }
+    let image = image::load_from_memory_with_format(data, format)
+        .map_err(|e| AppError::UnprocessableEntiry(format!("invalid cover image: {e}")))?;
+
+    let thumbnail = image.thumbnail(THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION);
+    let mut buf = Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut buf, format)
+        .map_err(|e| AppError::UnprocessableEntiry(format!("invalid cover image: {e}")))?;
+    Ok(buf.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use image::{DynamicImage, GenericImageView};
+    use kernel::model::{id::UserId, role::Role, user::Actor};
+    use shared::config::StorageConfig;
+
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        DynamicImage::new_rgba8(width, height)
+            .write_to(&mut buf, ImageFormat::Png)
+            .unwrap();
+        buf.into_inner()
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_cover_image(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join(format!("covers-{}", uuid::Uuid::new_v4()));
+        let storage = Arc::new(LocalStorage::new(&StorageConfig { root: root.clone() }));
+        let repo = CoverImageRepositoryImpl::new(ConnectionPool::new(pool.clone()), storage);
+
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = Actor {
+            id: UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?,
+            role: Role::User,
+        };
+
+        assert!(repo
+            .find(book_id, CoverImageSize::Original)
+            .await?
+            .is_none());
+
+        // 所有者以外はアップロードできない
+        let res = repo
+            .store(UploadCover {
+                book_id,
+                format: CoverImageFormat::Png,
+                data: png(800, 400),
+                requested_by: Actor {
+                    id: UserId::new(),
+                    role: Role::User,
+                },
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        // Content-Type と中身の形式が一致しない場合は受け付けない
+        let res = repo
+            .store(UploadCover {
+                book_id,
+                format: CoverImageFormat::Jpeg,
+                data: png(800, 400),
+                requested_by: owner,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnsupportedMediaType(_))));
+        assert!(repo
+            .find(book_id, CoverImageSize::Original)
+            .await?
+            .is_none());
+
+        repo.store(UploadCover {
+            book_id,
+            format: CoverImageFormat::Png,
+            data: png(800, 400),
+            requested_by: owner,
+        })
+        .await?;
+
+        let original = repo.find(book_id, CoverImageSize::Original).await?.unwrap();
+        assert_eq!(original.format, CoverImageFormat::Png);
+        assert_eq!(
+            image::load_from_memory(&original.data)?.dimensions(),
+            (800, 400)
+        );
+
+        // サムネイルは縦横比を保って縮小される
+        let thumbnail = repo
+            .find(book_id, CoverImageSize::Thumbnail)
+            .await?
+            .unwrap();
+        assert_eq!(
+            image::load_from_memory(&thumbnail.data)?.dimensions(),
+            (THUMBNAIL_MAX_DIMENSION, THUMBNAIL_MAX_DIMENSION / 2)
+        );
+
+        // 差し替えると新しい画像が返り、差し替える前のファイルは削除される
+        repo.store(UploadCover {
+            book_id,
+            format: CoverImageFormat::Png,
+            data: png(400, 800),
+            requested_by: owner,
+        })
+        .await?;
+        let original = repo.find(book_id, CoverImageSize::Original).await?.unwrap();
+        assert_eq!(
+            image::load_from_memory(&original.data)?.dimensions(),
+            (400, 800)
+        );
+        let dir = root.join(cover_dir(book_id));
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 1);
+
+        // 同時に差し替えても、どちらかの画像が揃った状態で残る
+        let upload = |width| UploadCover {
+            book_id,
+            format: CoverImageFormat::Png,
+            data: png(width, 100),
+            requested_by: owner,
+        };
+        let (a, b) = tokio::join!(repo.store(upload(300)), repo.store(upload(500)));
+        a?;
+        b?;
+        let original = repo.find(book_id, CoverImageSize::Original).await?.unwrap();
+        let thumbnail = repo
+            .find(book_id, CoverImageSize::Thumbnail)
+            .await?
+            .unwrap();
+        let (width, _) = image::load_from_memory(&original.data)?.dimensions();
+        assert!(width == 300 || width == 500);
+        assert_eq!(
+            image::load_from_memory(&thumbnail.data)?.dimensions().0,
+            width.min(THUMBNAIL_MAX_DIMENSION)
+        );
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 1);
+
+        repo.delete(DeleteCover {
+            book_id,
+            requested_by: owner,
+        })
+        .await?;
+        assert!(repo
+            .find(book_id, CoverImageSize::Original)
+            .await?
+            .is_none());
+        assert!(repo
+            .find(book_id, CoverImageSize::Thumbnail)
+            .await?
+            .is_none());
+
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 0);
+
+        // ID を記録する前に登録した表紙画像も取得・削除できる
+        std::fs::write(dir.join("original"), png(800, 400))?;
+        std::fs::write(dir.join("thumbnail"), png(100, 50))?;
+        sqlx::query!(
+            "UPDATE books SET cover_format = 'png' WHERE book_id = $1",
+            book_id as _
+        )
+        .execute(&pool)
+        .await?;
+        assert!(repo
+            .find(book_id, CoverImageSize::Original)
+            .await?
+            .is_some());
+        repo.delete(DeleteCover {
+            book_id,
+            requested_by: owner,
+        })
+        .await?;
+        assert_eq!(std::fs::read_dir(&dir)?.count(), 0);
+
+        std::fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        cover::CoverImageFormat,
+        damage::{
+            event::{CreateDamageReport, ResolveDamageReport, UploadDamagePhoto},
+            DamagePhoto, DamageReport, DamageReportStatus, MAX_DAMAGE_PHOTO_BYTES,
+        },
+        id::{CopyId, DamageReportId, UserId},
+    },
+    repository::damage::DamageReportRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    database::{model::damage::DamageReportRow, ConnectionPool},
+    repository::cover::image_format,
+    storage::LocalStorage,
+};
+
+#[derive(new)]
+pub struct DamageReportRepositoryImpl {
+    db: ConnectionPool,
+    storage: Arc<LocalStorage>,
+}
+
+#[async_trait]
+impl DamageReportRepository for DamageReportRepositoryImpl {
+    async fn create(&self, event: CreateDamageReport) -> AppResult<DamageReport> {
+        // 貸し出し中・返却済みのどちらの貸出に対しても報告できる
+        let checkout = sqlx::query!(
+            r#"
+                SELECT user_id AS "user_id!: UserId", copy_id AS "copy_id?: CopyId"
+                FROM checkouts
+                WHERE checkout_id = $1 AND book_id = $2
+                UNION ALL
+                SELECT user_id, copy_id
+                FROM returned_checkouts
+                WHERE checkout_id = $1 AND book_id = $2
+            "#,
+            event.checkout_id as _,
+            event.book_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified checkout not found".into()))?;
+
+        if checkout.user_id != event.reported_by {
+            return Err(AppError::ForbiddenOperation);
+        }
+        let copy_id = checkout.copy_id.ok_or_else(|| {
+            AppError::UnprocessableEntiry(format!(
+                "貸出（{}）の冊子を特定できないため報告できません。",
+                event.checkout_id
+            ))
+        })?;
+
+        let damage_report_id = DamageReportId::new();
+        sqlx::query!(
+            r#"
+                INSERT INTO damage_reports
+                (damage_report_id, checkout_id, book_id, copy_id, reported_by, description)
+                VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            damage_report_id as _,
+            event.checkout_id as _,
+            event.book_id as _,
+            copy_id as _,
+            event.reported_by as _,
+            event.description
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(
+            |e| match e.as_database_error().and_then(|e| e.constraint()) {
+                // 返却後に冊子が削除されていた場合
+                Some("damage_reports_copy_id_fkey") => AppError::UnprocessableEntiry(format!(
+                    "冊子（{copy_id}）は削除されているため報告できません。"
+                )),
+                _ => AppError::SpecificOperationError(e),
+            },
+        )?;
+
+        self.find_by_id(damage_report_id).await?.ok_or_else(|| {
+            AppError::EntityNotFound("created damage report could not be found".into())
+        })
+    }
+
+    async fn find_by_id(
+        &self,
+        damage_report_id: DamageReportId,
+    ) -> AppResult<Option<DamageReport>> {
+        sqlx::query_as!(
+            DamageReportRow,
+            r#"
+                SELECT
+                    d.damage_report_id,
+                    d.checkout_id,
+                    d.book_id,
+                    b.title,
+                    d.copy_id,
+                    bc.barcode,
+                    d.reported_by,
+                    u.name AS reporter_name,
+                    d.description,
+                    d.photo_format,
+                    d.created_at,
+                    d.resolved_by AS "resolved_by: UserId",
+                    d.resolution_note,
+                    d.resolved_at
+                FROM damage_reports AS d
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = d.copy_id
+                INNER JOIN users AS u ON u.user_id = d.reported_by
+                WHERE d.damage_report_id = $1
+            "#,
+            damage_report_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .map(DamageReport::try_from)
+        .transpose()
+    }
+
+    async fn find_all(&self, status: DamageReportStatus) -> AppResult<Vec<DamageReport>> {
+        sqlx::query_as!(
+            DamageReportRow,
+            r#"
+                SELECT
+                    d.damage_report_id,
+                    d.checkout_id,
+                    d.book_id,
+                    b.title,
+                    d.copy_id,
+                    bc.barcode,
+                    d.reported_by,
+                    u.name AS reporter_name,
+                    d.description,
+                    d.photo_format,
+                    d.created_at,
+                    d.resolved_by AS "resolved_by: UserId",
+                    d.resolution_note,
+                    d.resolved_at
+                FROM damage_reports AS d
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN book_copies AS bc ON bc.copy_id = d.copy_id
+                INNER JOIN users AS u ON u.user_id = d.reported_by
+                WHERE (d.resolved_at IS NULL) = $1
+                ORDER BY d.created_at ASC, d.damage_report_id ASC
+            "#,
+            status == DamageReportStatus::Open
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(DamageReport::try_from)
+        .collect()
+    }
+
+    async fn store_photo(&self, event: UploadDamagePhoto) -> AppResult<()> {
+        if event.data.len() > MAX_DAMAGE_PHOTO_BYTES {
+            return Err(AppError::UnprocessableEntiry(format!(
+                "damage photo must be at most {MAX_DAMAGE_PHOTO_BYTES} bytes"
+            )));
+        }
+        if image::guess_format(&event.data).ok() != Some(image_format(event.format)) {
+            return Err(AppError::UnsupportedMediaType(
+                "damage photo content does not match its content type".into(),
+            ));
+        }
+
+        let mut tx = self.db.begin().await?;
+
+        let report = sqlx::query!(
+            r#"
+                SELECT reported_by AS "reported_by: UserId", resolved_at
+                FROM damage_reports
+                WHERE damage_report_id = $1
+                FOR UPDATE
+            "#,
+            event.damage_report_id as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified damage report not found".into()))?;
+
+        if report.reported_by != event.requested_user {
+            return Err(AppError::ForbiddenOperation);
+        }
+        if report.resolved_at.is_some() {
+            return Err(AppError::UnprocessableEntiry(format!(
+                "報告（{}）は対応済みのため写真を添付できません。",
+                event.damage_report_id
+            )));
+        }
+
+        sqlx::query!(
+            r#"
+                UPDATE damage_reports
+                SET photo_format = $2
+                WHERE damage_report_id = $1
+            "#,
+            event.damage_report_id as _,
+            event.format.as_ref()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        self.storage
+            .put(&photo_key(event.damage_report_id), &event.data)
+            .await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn find_photo(&self, damage_report_id: DamageReportId) -> AppResult<Option<DamagePhoto>> {
+        let format = sqlx::query_scalar!(
+            r#"
+                SELECT photo_format
+                FROM damage_reports
+                WHERE damage_report_id = $1
+            "#,
+            damage_report_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .flatten();
+
+        let Some(format) = format else {
+            return Ok(None);
+        };
+        let format = format
+            .parse::<CoverImageFormat>()
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+
+        Ok(self
+            .storage
+            .get(&photo_key(damage_report_id))
+            .await?
+            .map(|data| DamagePhoto { format, data }))
+    }
+
+    async fn resolve(&self, event: ResolveDamageReport) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                UPDATE damage_reports
+                SET resolved_by = $2, resolution_note = $3, resolved_at = CURRENT_TIMESTAMP(3)
+                WHERE damage_report_id = $1
+                AND   resolved_at IS NULL
+            "#,
+            event.damage_report_id as _,
+            event.resolved_by as _,
+            event.note
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            let exists = sqlx::query_scalar!(
+                r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM damage_reports WHERE damage_report_id = $1
+                    ) AS "exists!"
+                "#,
+                event.damage_report_id as _
+            )
+            .fetch_one(self.db.inner_ref())
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+            return Err(if exists {
+                AppError::UnprocessableEntiry(format!(
+                    "報告（{}）はすでに対応済みです。",
+                    event.damage_report_id
+                ))
+            } else {
+                AppError::EntityNotFound("specified damage report not found".into())
+            });
+        }
+        Ok(())
+    }
+}
+
+// 写真を保存するファイルのキー。形式は DB に記録するので拡張子は付けない
+fn photo_key(damage_report_id: DamageReportId) -> String {
+    format!("damage-reports/{}", damage_report_id.raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, str::FromStr};
+
+    use chrono::Utc;
+    use image::{DynamicImage, ImageFormat};
+    use kernel::{
+        model::{
+            checkout::event::{CreateCheckout, UpdateReturned},
+            id::BookId,
+        },
+        repository::{book::BookRepository, checkout::CheckoutRepository},
+    };
+    use shared::config::StorageConfig;
+
+    use super::*;
+    use crate::repository::{book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl};
+
+    fn png() -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        DynamicImage::new_rgba8(8, 8)
+            .write_to(&mut buf, ImageFormat::Png)
+            .unwrap();
+        buf.into_inner()
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_damage_reports(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join(format!("damage-{}", uuid::Uuid::new_v4()));
+        let storage = Arc::new(LocalStorage::new(&StorageConfig { root: root.clone() }));
+        let repo = DamageReportRepositoryImpl::new(ConnectionPool::new(pool.clone()), storage);
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: user_id,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        let checkout = book_repo
+            .find_copies(book_id)
+            .await?
+            .remove(0)
+            .checkout
+            .unwrap();
+        let report = |reported_by, description: &str| CreateDamageReport {
+            book_id,
+            checkout_id: checkout.checkout_id,
+            description: description.into(),
+            reported_by,
+        };
+
+        // 借りたユーザー以外は報告できない
+        let res = repo.create(report(UserId::new(), "表紙が破れている")).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+
+        // 貸し出し中でも返却後でも報告できる
+        let first = repo.create(report(user_id, "表紙が破れている")).await?;
+        assert_eq!(first.copy_id, checkout.copy_id);
+        assert_eq!(first.status(), DamageReportStatus::Open);
+        assert!(first.photo_format.is_none());
+        checkout_repo
+            .update_returned(UpdateReturned {
+                checkout_id: checkout.checkout_id,
+                book_id,
+                returned_by: user_id,
+                returned_at: Utc::now(),
+                location_id: None,
+            })
+            .await?;
+        let second = repo.create(report(user_id, "水に濡れた跡がある")).await?;
+
+        // 報告したユーザーのみ写真を添付でき、形式が Content-Type と一致しない場合は受け付けない
+        let upload = |requested_user, format, data| UploadDamagePhoto {
+            damage_report_id: first.id,
+            format,
+            data,
+            requested_user,
+        };
+        let res = repo
+            .store_photo(upload(UserId::new(), CoverImageFormat::Png, png()))
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo
+            .store_photo(upload(user_id, CoverImageFormat::Jpeg, png()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnsupportedMediaType(_))));
+        repo.store_photo(upload(user_id, CoverImageFormat::Png, png()))
+            .await?;
+        let photo = repo.find_photo(first.id).await?.unwrap();
+        assert_eq!(photo.format, CoverImageFormat::Png);
+        assert_eq!(photo.data, png());
+        assert!(repo.find_photo(second.id).await?.is_none());
+
+        // 対応済みにすると未対応の一覧から外れ、写真も添付し直せない
+        repo.resolve(ResolveDamageReport {
+            damage_report_id: first.id,
+            note: "補修済み".into(),
+            resolved_by: user_id,
+        })
+        .await?;
+        let res = repo
+            .resolve(ResolveDamageReport {
+                damage_report_id: first.id,
+                note: "補修済み".into(),
+                resolved_by: user_id,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        let res = repo
+            .store_photo(upload(user_id, CoverImageFormat::Png, png()))
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        let open = repo.find_all(DamageReportStatus::Open).await?;
+        assert_eq!(
+            open.iter().map(|r| r.id).collect::<Vec<_>>(),
+            vec![second.id]
+        );
+        let resolved = repo.find_all(DamageReportStatus::Resolved).await?;
+        assert_eq!(resolved.len(), 1);
+        let resolution = resolved[0].resolution.as_ref().unwrap();
+        assert_eq!(resolution.resolved_by, Some(user_id));
+        assert_eq!(resolution.note, "補修済み");
+        assert_eq!(resolved[0].photo_format, Some(CoverImageFormat::Png));
+
+        std::fs::remove_dir_all(root)?;
+
+        Ok(())
+    }
+}
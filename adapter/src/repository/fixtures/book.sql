@@ -14,7 +14,7 @@ VALUES
     '9890736e-a4e4-461a-a77d-eac3517ef11b',
     '実践Rustプログラミング入門',
     '初田直也他',
-    '978-4798061702',
+    '9784798061702',
     'C/C++の代わりとなるべき最新言語その独特な仕様をわかりやすく解説。',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
     now(),
@@ -24,7 +24,7 @@ VALUES
     'f397b83a-dd2a-4a01-9e77-db1eea7de5b6',
     'ゼロから学ぶRust　システムプログラミングの基礎から線形型システムまで',
     '高野祐輝',
-    '978-4065301951',
+    '9784065301951',
     '通読して学習する入門書！　単なる文法解説にはとどまらない。実践的なソフトウェア実装と、Rustの安全性を支える理論の学習を通して、ゼロから徹底的にマスターできる！',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
     now(),
@@ -34,9 +34,63 @@ VALUES
     '17afb850-c786-49c5-a303-a3a443a2212c',
     'RustによるWebアプリケーション開発　設計からリリース・運用まで',
     '豊田優貴他',
-    '978-4065369579',
+    '9784065369579',
     '「蔵書管理アプリケーション」の実装を通じて、設計、開発、保守、運用までハンズオンで学ぶ！　今こそ現場にRustを！',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
     now(),
     now()
   ) ON CONFLICT DO NOTHING;
+
+-- 各蔵書に 1 冊ずつ冊子を登録する
+INSERT INTO
+  book_copies (book_id, barcode)
+SELECT
+  book_id,
+  REPLACE(book_id::text, '-', '')
+FROM
+  books ON CONFLICT DO NOTHING;
+
+-- 各蔵書の現在の内容を最初の版として記録する
+INSERT INTO
+  book_revisions (book_id, revision, user_id, title, author, isbn, description)
+SELECT
+  book_id,
+  1,
+  user_id,
+  title,
+  author,
+  isbn,
+  description
+FROM
+  books ON CONFLICT DO NOTHING;
+
+-- 各蔵書の著者名を 1 人の著者として登録する
+INSERT INTO
+  authors (name)
+SELECT DISTINCT
+  author
+FROM
+  books ON CONFLICT DO NOTHING;
+
+INSERT INTO
+  book_authors (book_id, author_id, role, position)
+SELECT
+  b.book_id,
+  a.author_id,
+  'author',
+  0
+FROM
+  books AS b
+  INNER JOIN authors AS a ON a.name = b.author ON CONFLICT DO NOTHING;
+
+INSERT INTO
+  book_revision_authors (book_id, revision, author_id, role, position)
+SELECT
+  r.book_id,
+  r.revision,
+  a.author_id,
+  'author',
+  0
+FROM
+  book_revisions AS r
+  INNER JOIN authors AS a ON a.name = r.author ON CONFLICT DO NOTHING;
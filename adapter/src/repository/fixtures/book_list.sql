@@ -510,3 +510,31 @@ VALUES
     '2023-12-01 01:00:50.000',
     '2023-12-01 01:00:50.000'
   ) ON CONFLICT DO NOTHING;
+
+-- 各蔵書に 1 冊ずつ冊子を登録する
+INSERT INTO
+  book_copies (book_id, barcode)
+SELECT
+  book_id,
+  REPLACE(book_id::text, '-', '')
+FROM
+  books ON CONFLICT DO NOTHING;
+
+-- 各蔵書の著者名を 1 人の著者として登録する
+INSERT INTO
+  authors (name)
+SELECT DISTINCT
+  author
+FROM
+  books ON CONFLICT DO NOTHING;
+
+INSERT INTO
+  book_authors (book_id, author_id, role, position)
+SELECT
+  b.book_id,
+  a.author_id,
+  'author',
+  0
+FROM
+  books AS b
+  INNER JOIN authors AS a ON a.name = b.author ON CONFLICT DO NOTHING;
@@ -32,9 +32,18 @@ VALUES
     '9890736e-a4e4-461a-a77d-eac3517ef11b',
     '実践Rustプログラミング入門',
     '初田直也他',
-    '978-4798061702',
+    '9784798061702',
     'C/C++の代わりとなるべき最新言語その独特な仕様をわかりやすく解説。',
     '5b4c96ac-316a-4bee-8e69-cac5eb84ff4c',
     now(),
     now()
   ) ON CONFLICT DO NOTHING;
+
+-- 各蔵書に 1 冊ずつ冊子を登録する
+INSERT INTO
+  book_copies (book_id, barcode)
+SELECT
+  book_id,
+  REPLACE(book_id::text, '-', '')
+FROM
+  books ON CONFLICT DO NOTHING;
@@ -0,0 +1,302 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::LocationId,
+        location::{
+            event::{CreateLocation, DeleteLocation, UpdateLocation},
+            Location, LocationKind,
+        },
+    },
+    repository::location::LocationRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{model::location::LocationRow, ConnectionPool};
+
+#[derive(new)]
+pub struct LocationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl LocationRepository for LocationRepositoryImpl {
+    async fn find_all(&self) -> AppResult<Vec<Location>> {
+        sqlx::query_as!(
+            LocationRow,
+            r#"
+                SELECT
+                    location_id AS "location_id!: LocationId",
+                    parent_id AS "parent_id: LocationId",
+                    name AS "name!",
+                    kind AS "kind!",
+                    path AS "path!"
+                FROM location_paths
+                ORDER BY path ASC
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Location::try_from)
+        .collect()
+    }
+
+    async fn find_by_id(&self, location_id: LocationId) -> AppResult<Option<Location>> {
+        sqlx::query_as!(
+            LocationRow,
+            r#"
+                SELECT
+                    location_id AS "location_id!: LocationId",
+                    parent_id AS "parent_id: LocationId",
+                    name AS "name!",
+                    kind AS "kind!",
+                    path AS "path!"
+                FROM location_paths
+                WHERE location_id = $1
+            "#,
+            location_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .map(Location::try_from)
+        .transpose()
+    }
+
+    async fn create(&self, event: CreateLocation) -> AppResult<Location> {
+        // 親の種類が、作成する場所の種類の 1 つ上の階層であることを確かめる
+        match (event.kind.parent_kind(), event.parent_id) {
+            (None, None) => {}
+            (None, Some(_)) => {
+                return Err(AppError::UnprocessableEntiry(
+                    "建物は他の場所の下には作成できません。".into(),
+                ))
+            }
+            (Some(_), None) => {
+                return Err(AppError::UnprocessableEntiry(format!(
+                    "{} を作成するには親の場所を指定してください。",
+                    event.kind.as_ref()
+                )))
+            }
+            (Some(expected), Some(parent_id)) => {
+                let parent_kind = sqlx::query_scalar!(
+                    "SELECT kind FROM locations WHERE location_id = $1",
+                    parent_id as _
+                )
+                .fetch_optional(self.db.inner_ref())
+                .await
+                .map_err(AppError::SpecificOperationError)?
+                .ok_or_else(|| {
+                    AppError::UnprocessableEntiry(format!(
+                        "親の場所（{parent_id}）は登録されていません。"
+                    ))
+                })?;
+                let parent_kind = LocationKind::from_str(&parent_kind)
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+                if parent_kind != expected {
+                    return Err(AppError::UnprocessableEntiry(format!(
+                        "{} は {} の下にのみ作成できます。",
+                        event.kind.as_ref(),
+                        expected.as_ref()
+                    )));
+                }
+            }
+        }
+
+        let location_id = LocationId::new();
+        sqlx::query!(
+            r#"
+                INSERT INTO locations (location_id, parent_id, name, kind)
+                VALUES ($1, $2, $3, $4)
+            "#,
+            location_id as _,
+            event.parent_id as _,
+            event.name,
+            event.kind.as_ref()
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_location_error)?;
+
+        self.find_by_id(location_id)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("created location could not be found".into()))
+    }
+
+    async fn update(&self, event: UpdateLocation) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                UPDATE locations
+                SET name = $2
+                WHERE location_id = $1
+            "#,
+            event.location_id as _,
+            event.name
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_location_error)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified location not found".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, event: DeleteLocation) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM locations
+                WHERE location_id = $1
+            "#,
+            event.location_id as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_location_error)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified location not found".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+// 同じ親の下での名前の重複と、配下に場所が残っている場所の削除をエラーにする
+fn map_location_error(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("locations_parent_id_name_key") => {
+            AppError::ConflictError("specified location already exists".into())
+        }
+        Some("locations_parent_id_fkey") => {
+            AppError::UnprocessableEntiry("配下に場所が登録されているため削除できません。".into())
+        }
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+// 冊子に登録されていない場所を設定しようとした場合のエラーにする
+pub(crate) fn map_location_not_found(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("book_copies_location_id_fkey") => {
+            AppError::UnprocessableEntiry("指定の場所は登録されていません。".into())
+        }
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_location_hierarchy(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = LocationRepositoryImpl::new(ConnectionPool::new(pool));
+
+        let create = |parent_id, name: &str, kind| CreateLocation {
+            parent_id,
+            name: name.into(),
+            kind,
+        };
+
+        let building = repo
+            .create(create(None, "本館", LocationKind::Building))
+            .await?;
+        let room = repo
+            .create(create(Some(building.id), "2F 閲覧室", LocationKind::Room))
+            .await?;
+        let shelf = repo
+            .create(create(Some(room.id), "A-1", LocationKind::Shelf))
+            .await?;
+        assert_eq!(shelf.parent_id, Some(room.id));
+        assert_eq!(shelf.path, vec!["本館", "2F 閲覧室", "A-1"]);
+
+        // 階層の順序に合わない親は指定できない
+        for (parent_id, kind) in [
+            (None, LocationKind::Room),
+            (Some(building.id), LocationKind::Shelf),
+            (Some(shelf.id), LocationKind::Shelf),
+            (Some(room.id), LocationKind::Building),
+            (Some(LocationId::new()), LocationKind::Room),
+        ] {
+            let res = repo.create(create(parent_id, "B-1", kind)).await;
+            assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        }
+
+        // 同じ親の下に同じ名前の場所は作成できないが、親が異なれば作成できる
+        let res = repo
+            .create(create(Some(room.id), "A-1", LocationKind::Shelf))
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+        let res = repo
+            .create(create(None, "本館", LocationKind::Building))
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+        let annex = repo
+            .create(create(None, "別館", LocationKind::Building))
+            .await?;
+        let annex_room = repo
+            .create(create(Some(annex.id), "2F 閲覧室", LocationKind::Room))
+            .await?;
+
+        // 名前を変更すると配下の場所の経路にも反映される
+        repo.update(UpdateLocation {
+            location_id: building.id,
+            name: "中央館".into(),
+        })
+        .await?;
+        let shelf = repo.find_by_id(shelf.id).await?.unwrap();
+        assert_eq!(shelf.path, vec!["中央館", "2F 閲覧室", "A-1"]);
+
+        // 経路の順に並ぶ
+        let paths = repo
+            .find_all()
+            .await?
+            .into_iter()
+            .map(|l| l.path.join(" / "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "中央館",
+                "中央館 / 2F 閲覧室",
+                "中央館 / 2F 閲覧室 / A-1",
+                "別館",
+                "別館 / 2F 閲覧室",
+            ]
+        );
+
+        // 配下に場所がある場合は削除できない
+        let res = repo
+            .delete(DeleteLocation {
+                location_id: annex.id,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        repo.delete(DeleteLocation {
+            location_id: annex_room.id,
+        })
+        .await?;
+        repo.delete(DeleteLocation {
+            location_id: annex.id,
+        })
+        .await?;
+        let res = repo
+            .delete(DeleteLocation {
+                location_id: annex.id,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(repo.find_all().await?.len(), 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{book::metadata::BookMetadata, isbn::Isbn},
+    repository::metadata::BookMetadataProvider,
+};
+use serde::Deserialize;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    database::model::metadata::{BookMetadataKey, CachedBookMetadata},
+    redis::RedisClient,
+};
+
+// 外部サービスの応答を待つ時間の上限
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+// OpenLibrary の Books API (https://openlibrary.org/dev/docs/api/books) 互換の
+// エンドポイントに問い合わせる実装。国立国会図書館サーチなど、同じ形式の応答を返す
+// 中継サービスであれば endpoint を差し替えて使える
+pub struct OpenLibraryMetadataProvider {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl OpenLibraryMetadataProvider {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint,
+        }
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for OpenLibraryMetadataProvider {
+    async fn lookup(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        let res: OpenLibraryResponse = self
+            .client
+            .get(&self.endpoint)
+            .query(&[
+                ("bibkeys", format!("ISBN:{isbn}").as_str()),
+                ("format", "json"),
+                ("jscmd", "data"),
+            ])
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| AppError::ExternalServiceError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalServiceError(e.to_string()))?;
+
+        Ok(find_in_response(res, isbn))
+    }
+}
+
+// テスト向けに、OpenLibrary と同じ形式の JSON を読み込んで返す実装
+pub struct FixtureMetadataProvider {
+    books: HashMap<Isbn, BookMetadata>,
+}
+
+impl FixtureMetadataProvider {
+    pub fn from_json(json: &str) -> AppResult<Self> {
+        let res: OpenLibraryResponse = serde_json::from_str(json)
+            .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+        let books = res
+            .into_iter()
+            .filter_map(|(key, book)| {
+                let isbn = key.strip_prefix("ISBN:")?.parse::<Isbn>().ok()?;
+                Some((isbn.clone(), book.into_metadata(isbn)))
+            })
+            .collect();
+        Ok(Self { books })
+    }
+}
+
+#[async_trait]
+impl BookMetadataProvider for FixtureMetadataProvider {
+    async fn lookup(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        Ok(self.books.get(isbn).cloned())
+    }
+}
+
+// 問い合わせ結果を Redis にキャッシュする
+// キャッシュの読み書きに失敗しても、問い合わせ自体は続ける
+#[derive(new)]
+pub struct CachedMetadataProvider {
+    inner: Arc<dyn BookMetadataProvider>,
+    kv: Arc<RedisClient>,
+    ttl: u64,
+}
+
+#[async_trait]
+impl BookMetadataProvider for CachedMetadataProvider {
+    async fn lookup(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>> {
+        let key = BookMetadataKey::from(isbn);
+        // キャッシュがあれば、該当なしだった結果も含めてそのまま返す
+        let cached = self
+            .kv
+            .get(&key)
+            .await
+            .and_then(|cached| cached.map(Option::<BookMetadata>::try_from).transpose());
+        match cached {
+            Ok(Some(metadata)) => return Ok(metadata),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error.message = %e, "failed to read book metadata cache"),
+        }
+
+        let metadata = self.inner.lookup(isbn).await?;
+        let value = CachedBookMetadata::from(metadata.clone());
+        if let Err(e) = self.kv.set_ex(&key, &value, self.ttl).await {
+            tracing::warn!(error.message = %e, "failed to write book metadata cache");
+        }
+        Ok(metadata)
+    }
+}
+
+// bibkeys に指定したキー（"ISBN:..."）ごとに書誌情報が返る
+type OpenLibraryResponse = HashMap<String, OpenLibraryBook>;
+
+#[derive(Deserialize)]
+struct OpenLibraryBook {
+    title: Option<String>,
+    subtitle: Option<String>,
+    #[serde(default)]
+    authors: Vec<OpenLibraryAuthor>,
+    notes: Option<OpenLibraryText>,
+    #[serde(default)]
+    excerpts: Vec<OpenLibraryExcerpt>,
+}
+
+#[derive(Deserialize)]
+struct OpenLibraryAuthor {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct OpenLibraryExcerpt {
+    text: String,
+}
+
+// 説明文は文字列のまま、もしくは型情報付きのオブジェクトとして返る
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OpenLibraryText {
+    Plain(String),
+    Typed { value: String },
+}
+
+impl OpenLibraryBook {
+    fn into_metadata(self, isbn: Isbn) -> BookMetadata {
+        let title = match (self.title, self.subtitle) {
+            (Some(title), Some(subtitle)) => Some(format!("{title}: {subtitle}")),
+            (title, _) => title,
+        };
+        let authors = self.authors.into_iter().map(|a| a.name).collect();
+        let description = self
+            .notes
+            .map(|notes| match notes {
+                OpenLibraryText::Plain(s) | OpenLibraryText::Typed { value: s } => s,
+            })
+            .or_else(|| self.excerpts.into_iter().next().map(|e| e.text));
+        BookMetadata {
+            isbn,
+            title,
+            authors,
+            description,
+        }
+    }
+}
+
+fn find_in_response(mut res: OpenLibraryResponse, isbn: &Isbn) -> Option<BookMetadata> {
+    res.remove(&format!("ISBN:{isbn}"))
+        .map(|book| book.into_metadata(isbn.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fixture_metadata_provider() -> anyhow::Result<()> {
+        let provider = FixtureMetadataProvider::from_json(include_str!("fixtures/metadata.json"))?;
+
+        // ISBN-10 で引いても ISBN-13 で登録された書誌情報が見つかる
+        let metadata = provider.lookup(&"4-7980-6170-0".parse()?).await?.unwrap();
+        assert_eq!(metadata.isbn.as_str(), "9784798061702");
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("実践Rustプログラミング入門")
+        );
+        assert_eq!(metadata.authors, vec!["初田直也", "山口聖弘"]);
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("C/C++の代わりとなるべき最新言語その独特な仕様をわかりやすく解説。")
+        );
+
+        // 副題はタイトルにつなげ、説明文がなければ抜粋を使う
+        let metadata = provider.lookup(&"9784065301951".parse()?).await?.unwrap();
+        assert_eq!(
+            metadata.title.as_deref(),
+            Some("ゼロから学ぶRust: システムプログラミングの基礎から線形型システムまで")
+        );
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("通読して学習する入門書！")
+        );
+
+        // 項目が欠けていても読み込める
+        let metadata = provider.lookup(&"9784065369579".parse()?).await?.unwrap();
+        assert!(metadata.authors.is_empty());
+        assert!(metadata.description.is_none());
+
+        assert!(provider.lookup(&"4-87311-865-4".parse()?).await?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::{BookId, UserId},
+        recommendation::RecommendedBook,
+    },
+    repository::recommendation::RecommendationRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{model::recommendation::RecommendedBookRow, ConnectionPool};
+
+// 集計結果として蔵書・ユーザーごとに保存しておく件数
+const MAX_RECOMMENDATIONS: i64 = 50;
+
+#[derive(new)]
+pub struct RecommendationRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl RecommendationRepository for RecommendationRepositoryImpl {
+    async fn refresh(&self) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        // 集計中に取得された場合も、集計前の結果を返せるよう 1 つのトランザクションで入れ替える
+        sqlx::query!("DELETE FROM book_recommendations")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+        sqlx::query!("DELETE FROM related_books")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::SpecificOperationError)?;
+
+        // 同じユーザーに借りられた蔵書の組を数える
+        // 返却済みの貸出には外部キーがないため、完全に削除された蔵書を除く
+        sqlx::query!(
+            r#"
+                WITH borrowings AS (
+                    SELECT DISTINCT c.user_id, c.book_id
+                    FROM (
+                        SELECT user_id, book_id FROM checkouts
+                        UNION ALL
+                        SELECT user_id, book_id FROM returned_checkouts
+                    ) AS c
+                    INNER JOIN books AS b ON b.book_id = c.book_id
+                ),
+                pairs AS (
+                    SELECT
+                        x.book_id,
+                        y.book_id AS related_book_id,
+                        COUNT(*) AS score,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY x.book_id
+                            ORDER BY COUNT(*) DESC, y.book_id ASC
+                        ) AS rank
+                    FROM borrowings AS x
+                    INNER JOIN borrowings AS y
+                        ON y.user_id = x.user_id AND y.book_id <> x.book_id
+                    GROUP BY x.book_id, y.book_id
+                )
+                INSERT INTO related_books (book_id, related_book_id, score)
+                SELECT book_id, related_book_id, score
+                FROM pairs
+                WHERE rank <= $1
+            "#,
+            MAX_RECOMMENDATIONS
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        // ユーザーが借りた蔵書と一緒に借りられている蔵書のうち、まだ借りていないものを集める
+        sqlx::query!(
+            r#"
+                WITH borrowings AS (
+                    SELECT DISTINCT user_id, book_id
+                    FROM (
+                        SELECT user_id, book_id FROM checkouts
+                        UNION ALL
+                        SELECT user_id, book_id FROM returned_checkouts
+                    ) AS c
+                ),
+                candidates AS (
+                    SELECT
+                        bw.user_id,
+                        rb.related_book_id AS book_id,
+                        SUM(rb.score) AS score,
+                        ROW_NUMBER() OVER (
+                            PARTITION BY bw.user_id
+                            ORDER BY SUM(rb.score) DESC, rb.related_book_id ASC
+                        ) AS rank
+                    FROM borrowings AS bw
+                    INNER JOIN related_books AS rb ON rb.book_id = bw.book_id
+                    INNER JOIN users AS u ON u.user_id = bw.user_id
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM borrowings AS b2
+                        WHERE b2.user_id = bw.user_id
+                        AND   b2.book_id = rb.related_book_id
+                    )
+                    GROUP BY bw.user_id, rb.related_book_id
+                )
+                INSERT INTO book_recommendations (user_id, book_id, score)
+                SELECT user_id, book_id, score
+                FROM candidates
+                WHERE rank <= $1
+            "#,
+            MAX_RECOMMENDATIONS
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn find_related(&self, book_id: BookId, limit: i64) -> AppResult<Vec<RecommendedBook>> {
+        let book_exists = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+            "#,
+            book_id as _
+        )
+        .fetch_one(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !book_exists {
+            return Err(AppError::EntityNotFound(format!(
+                "蔵書（{book_id}）が見つかりませんでした。"
+            )));
+        }
+
+        sqlx::query_as!(
+            RecommendedBookRow,
+            r#"
+                SELECT
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.cover_format IS NOT NULL AS "has_cover!",
+                    rb.score
+                FROM related_books AS rb
+                INNER JOIN books AS b ON b.book_id = rb.related_book_id
+                WHERE rb.book_id = $1
+                AND   b.deleted_at IS NULL
+                ORDER BY rb.score DESC, b.book_id ASC
+                LIMIT $2
+            "#,
+            book_id as _,
+            limit
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)
+        .map(|rows| rows.into_iter().map(RecommendedBook::from).collect())
+    }
+
+    async fn find_for_user(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RecommendedBook>> {
+        // 前回の集計の後に借りた蔵書も除く
+        sqlx::query_as!(
+            RecommendedBookRow,
+            r#"
+                SELECT
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    b.cover_format IS NOT NULL AS "has_cover!",
+                    r.score
+                FROM book_recommendations AS r
+                INNER JOIN books AS b USING(book_id)
+                WHERE r.user_id = $1
+                AND   b.deleted_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM checkouts AS c
+                    WHERE c.user_id = r.user_id AND c.book_id = r.book_id
+                )
+                AND NOT EXISTS (
+                    SELECT 1 FROM returned_checkouts AS rc
+                    WHERE rc.user_id = r.user_id AND rc.book_id = r.book_id
+                )
+                ORDER BY r.score DESC, b.book_id ASC
+                LIMIT $2
+            "#,
+            user_id as _,
+            limit
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)
+        .map(|rows| rows.into_iter().map(RecommendedBook::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    async fn add_user(pool: &sqlx::PgPool, name: &str) -> anyhow::Result<UserId> {
+        let user_id = sqlx::query_scalar!(
+            r#"
+                INSERT INTO users (name, email, password_hash, role_id)
+                SELECT $1, $2, 'dummy', role_id
+                FROM roles WHERE name = 'User'
+                RETURNING user_id AS "user_id: UserId"
+            "#,
+            name,
+            format!("{name}@example.com")
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(user_id)
+    }
+
+    async fn add_returned_checkout(
+        pool: &sqlx::PgPool,
+        user_id: UserId,
+        book_id: BookId,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO returned_checkouts (checkout_id, book_id, user_id, due_at)
+                VALUES (gen_random_uuid(), $1, $2, now() + INTERVAL '14 days')
+            "#,
+            book_id as _,
+            user_id as _
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_recommendations(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = RecommendationRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book1 = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let book2 = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let book3 = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+        let user_a = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let user_b = add_user(&pool, "user-b").await?;
+        let user_c = add_user(&pool, "user-c").await?;
+
+        // a は 1・2 を、b は 1・2・3 を、c は 1 を借りたことがある
+        // 同じ蔵書を何度借りても 1 回として数える
+        for (user_id, book_id) in [
+            (user_a, book1),
+            (user_a, book2),
+            (user_a, book2),
+            (user_b, book1),
+            (user_b, book2),
+            (user_b, book3),
+            (user_c, book1),
+        ] {
+            add_returned_checkout(&pool, user_id, book_id).await?;
+        }
+
+        // 集計するまでは何も返さない
+        assert!(repo.find_related(book1, 10).await?.is_empty());
+
+        repo.refresh().await?;
+
+        let scores = |books: Vec<RecommendedBook>| {
+            books
+                .into_iter()
+                .map(|b| (b.book_id, b.score))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            scores(repo.find_related(book1, 10).await?),
+            vec![(book2, 2), (book3, 1)]
+        );
+        assert_eq!(scores(repo.find_related(book1, 1).await?), vec![(book2, 2)]);
+        let res = repo.find_related(BookId::new(), 10).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 借りたことのある蔵書はおすすめしない
+        assert_eq!(
+            scores(repo.find_for_user(user_c, 10).await?),
+            vec![(book2, 2), (book3, 1)]
+        );
+        assert_eq!(
+            scores(repo.find_for_user(user_a, 10).await?),
+            vec![(book3, 2)]
+        );
+        assert!(repo.find_for_user(user_b, 10).await?.is_empty());
+
+        // 集計の後に借りた蔵書も除く
+        add_returned_checkout(&pool, user_a, book3).await?;
+        assert!(repo.find_for_user(user_a, 10).await?.is_empty());
+
+        // 集計し直すと前回の結果は置き換わる
+        sqlx::query!("DELETE FROM returned_checkouts")
+            .execute(&pool)
+            .await?;
+        repo.refresh().await?;
+        assert!(repo.find_related(book1, 10).await?.is_empty());
+        assert!(repo.find_for_user(user_c, 10).await?.is_empty());
+
+        Ok(())
+    }
+}
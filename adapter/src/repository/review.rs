@@ -0,0 +1,360 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::{BookId, ReviewId, UserId},
+        list::PaginatedList,
+        review::{
+            event::{CreateReview, DeleteReview, UpdateReview},
+            BookReview, ReviewListOptions,
+        },
+    },
+    repository::review::ReviewRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{
+    model::review::{BookReviewRow, PaginatedBookReviewRow},
+    ConnectionPool,
+};
+
+#[derive(new)]
+pub struct ReviewRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl ReviewRepository for ReviewRepositoryImpl {
+    async fn create(&self, event: CreateReview) -> AppResult<BookReview> {
+        let mut tx = self.db.begin().await?;
+
+        let book_exists = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM books WHERE book_id = $1 AND deleted_at IS NULL
+                ) AS "exists!"
+            "#,
+            event.book_id as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !book_exists {
+            return Err(AppError::EntityNotFound(format!(
+                "蔵書（{}）が見つかりませんでした。",
+                event.book_id
+            )));
+        }
+
+        // 貸し出し中のものも含め、蔵書を借りたことがあるユーザーのみレビューを書ける
+        let borrowed = sqlx::query_scalar!(
+            r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM checkouts WHERE book_id = $1 AND user_id = $2
+                    UNION ALL
+                    SELECT 1 FROM returned_checkouts WHERE book_id = $1 AND user_id = $2
+                ) AS "borrowed!"
+            "#,
+            event.book_id as _,
+            event.requested_user as _
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        if !borrowed {
+            return Err(AppError::ForbiddenOperation);
+        }
+
+        let review_id = ReviewId::new();
+        sqlx::query!(
+            r#"
+                INSERT INTO book_reviews (review_id, book_id, user_id, rating, comment)
+                VALUES ($1, $2, $3, $4, $5)
+            "#,
+            review_id as _,
+            event.book_id as _,
+            event.requested_user as _,
+            event.rating,
+            event.comment
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(
+            |e| match e.as_database_error().and_then(|e| e.constraint()) {
+                Some("book_reviews_book_id_user_id_key") => AppError::ConflictError(format!(
+                    "蔵書（{}）のレビューはすでに登録されています。",
+                    event.book_id
+                )),
+                _ => AppError::SpecificOperationError(e),
+            },
+        )?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        self.find_by_id(review_id)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("created review could not be found".into()))
+    }
+
+    async fn find_by_book_id(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<BookReview>> {
+        let ReviewListOptions { limit, offset } = options;
+        let rows = sqlx::query_as!(
+            PaginatedBookReviewRow,
+            r#"
+                SELECT
+                    COUNT(*) OVER() AS "total!",
+                    r.review_id,
+                    r.book_id,
+                    r.user_id,
+                    u.name AS user_name,
+                    r.rating,
+                    r.comment,
+                    r.created_at,
+                    r.updated_at
+                FROM book_reviews AS r
+                INNER JOIN users AS u USING(user_id)
+                WHERE r.book_id = $1
+                ORDER BY r.created_at DESC, r.review_id ASC
+                LIMIT $2
+                OFFSET $3
+            "#,
+            book_id as _,
+            limit,
+            offset
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(PaginatedList {
+            total: rows.first().map(|r| r.total).unwrap_or_default(),
+            limit,
+            offset,
+            items: rows.into_iter().map(BookReview::from).collect(),
+        })
+    }
+
+    async fn update(&self, event: UpdateReview) -> AppResult<BookReview> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_review_mutation(
+            &mut tx,
+            event.book_id,
+            event.review_id,
+            event.requested_user,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE book_reviews
+                SET rating = $2, comment = $3
+                WHERE review_id = $1
+            "#,
+            event.review_id as _,
+            event.rating,
+            event.comment
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        self.find_by_id(event.review_id)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound("updated review could not be found".into()))
+    }
+
+    async fn delete(&self, event: DeleteReview) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        authorize_review_mutation(
+            &mut tx,
+            event.book_id,
+            event.review_id,
+            event.requested_user,
+        )
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM book_reviews WHERE review_id = $1",
+            event.review_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+impl ReviewRepositoryImpl {
+    async fn find_by_id(&self, review_id: ReviewId) -> AppResult<Option<BookReview>> {
+        sqlx::query_as!(
+            BookReviewRow,
+            r#"
+                SELECT
+                    r.review_id,
+                    r.book_id,
+                    r.user_id,
+                    u.name AS user_name,
+                    r.rating,
+                    r.comment,
+                    r.created_at,
+                    r.updated_at
+                FROM book_reviews AS r
+                INNER JOIN users AS u USING(user_id)
+                WHERE r.review_id = $1
+            "#,
+            review_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)
+        .map(|row| row.map(BookReview::from))
+    }
+}
+
+// レビューが存在し、操作するユーザーが書いたものであることを確かめる
+async fn authorize_review_mutation(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    review_id: ReviewId,
+    requested_user: UserId,
+) -> AppResult<()> {
+    let reviewer = sqlx::query_scalar!(
+        r#"
+            SELECT user_id AS "user_id: UserId"
+            FROM book_reviews
+            WHERE review_id = $1
+            AND   book_id = $2
+            FOR UPDATE
+        "#,
+        review_id as _,
+        book_id as _
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified review not found".into()))?;
+
+    if reviewer != requested_user {
+        return Err(AppError::ForbiddenOperation);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::Utc;
+    use kernel::{
+        model::{book::BookListOptions, book::BookSortKey, checkout::event::CreateCheckout},
+        repository::{book::BookRepository, checkout::CheckoutRepository},
+    };
+
+    use super::*;
+    use crate::repository::{book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl};
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_reviews(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = ReviewRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let other_book_id = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let user_id = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+
+        let create = |book_id, rating| CreateReview {
+            book_id,
+            rating,
+            comment: "入門に最適".into(),
+            requested_user: user_id,
+        };
+
+        // 借りたことのない蔵書や、存在しない蔵書にはレビューを書けない
+        let res = repo.create(create(book_id, 5)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo.create(create(BookId::new(), 5)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 貸し出し中でもレビューを書けるが、1 つの蔵書に 1 件まで
+        checkout_repo
+            .create(CreateCheckout {
+                book_id,
+                checked_out_by: user_id,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        let review = repo.create(create(book_id, 4)).await?;
+        assert_eq!(review.reviewer.id, user_id);
+        assert_eq!(review.rating, 4);
+        let res = repo.create(create(book_id, 5)).await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        // 書いたユーザー以外は変更・削除できない
+        let update = |review_id, requested_user| UpdateReview {
+            book_id,
+            review_id,
+            rating: 2,
+            comment: "思ったより難しかった".into(),
+            requested_user,
+        };
+        let res = repo.update(update(review.id, UserId::new())).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo.update(update(ReviewId::new(), user_id)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        let updated = repo.update(update(review.id, user_id)).await?;
+        assert_eq!(updated.rating, 2);
+        assert_eq!(updated.comment, "思ったより難しかった");
+
+        // 蔵書には評価の平均と件数が付き、評価の高い順に並べられる
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.average_rating, Some(2.0));
+        assert_eq!(book.review_count, 1);
+        let other = book_repo.find_by_id(other_book_id).await?.unwrap();
+        assert_eq!(other.average_rating, None);
+        assert_eq!(other.review_count, 0);
+        let books = book_repo
+            .find_all(BookListOptions {
+                limit: 10,
+                sort: Some(BookSortKey::Rating),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(books.items[0].id, book_id);
+
+        let page = repo
+            .find_by_book_id(
+                book_id,
+                ReviewListOptions {
+                    limit: 10,
+                    offset: 0,
+                },
+            )
+            .await?;
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].id, review.id);
+
+        let delete = |requested_user| DeleteReview {
+            book_id,
+            review_id: review.id,
+            requested_user,
+        };
+        let res = repo.delete(delete(UserId::new())).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.delete(delete(user_id)).await?;
+        let res = repo.delete(delete(user_id)).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        Ok(())
+    }
+}
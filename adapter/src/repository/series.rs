@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::SeriesId,
+        series::{Series, SeriesVolume},
+    },
+    repository::series::SeriesRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{
+    model::series::{SeriesRow, SeriesVolumeRow},
+    ConnectionPool,
+};
+
+#[derive(new)]
+pub struct SeriesRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl SeriesRepository for SeriesRepositoryImpl {
+    async fn find_by_id(&self, series_id: SeriesId) -> AppResult<Option<Series>> {
+        let Some(row) = sqlx::query_as!(
+            SeriesRow,
+            r#"
+                SELECT series_id, title
+                FROM series
+                WHERE series_id = $1
+            "#,
+            series_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        else {
+            return Ok(None);
+        };
+
+        // 同じ巻数の蔵書が複数ある場合（版違いなど）は登録順に並べる
+        let volumes = sqlx::query_as!(
+            SeriesVolumeRow,
+            r#"
+                SELECT
+                    b.series_volume AS "volume!",
+                    b.book_id,
+                    b.title,
+                    b.author,
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc WHERE bc.book_id = b.book_id
+                    ) AS "total_copies!",
+                    (
+                        SELECT COUNT(*) FROM book_copies AS bc
+                        WHERE bc.book_id = b.book_id
+                        AND bc.withdrawn_at IS NULL
+                        AND NOT EXISTS (SELECT 1 FROM checkouts AS c WHERE c.copy_id = bc.copy_id)
+                    ) AS "available_copies!"
+                FROM books AS b
+                WHERE b.series_id = $1
+                AND   b.deleted_at IS NULL
+                ORDER BY b.series_volume ASC, b.created_at ASC, b.book_id ASC
+            "#,
+            series_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(SeriesVolume::from)
+        .collect();
+
+        Ok(Some(row.into_series(volumes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::Utc;
+    use kernel::{
+        model::{
+            author::{AuthorCredit, AuthorRole},
+            book::event::{DeleteBook, UpdateBook},
+            checkout::event::CreateCheckout,
+            id::{BookId, UserId},
+            role::Role,
+            series::SeriesMembership,
+            user::Actor,
+        },
+        repository::{book::BookRepository, checkout::CheckoutRepository},
+    };
+
+    use crate::repository::{book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl};
+
+    use super::*;
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_series(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = SeriesRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let checkout_repo = CheckoutRepositoryImpl::new(ConnectionPool::new(pool.clone()), 14, 2);
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let first = BookId::from_str("f397b83a-dd2a-4a01-9e77-db1eea7de5b6")?;
+        let second = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let third = BookId::from_str("17afb850-c786-49c5-a303-a3a443a2212c")?;
+
+        let books = &book_repo;
+        let set_series = |book_id, series| async move {
+            let book = books.find_by_id(book_id).await?.unwrap();
+            books
+                .update(UpdateBook {
+                    book_id,
+                    title: book.title,
+                    authors: vec![AuthorCredit {
+                        name: book.author,
+                        role: AuthorRole::Author,
+                    }],
+                    isbn: book.isbn.parse()?,
+                    description: book.description,
+                    publisher: None,
+                    published_year: None,
+                    tags: None,
+                    series,
+                    requested_by: Actor {
+                        id: owner,
+                        role: Role::User,
+                    },
+                    expected_updated_at: None,
+                })
+                .await
+        };
+        let volume = |volume| {
+            Some(Some(SeriesMembership {
+                title: "Rust シリーズ".into(),
+                volume,
+            }))
+        };
+
+        // 同じタイトルを指定した蔵書は同じシリーズにまとまる
+        set_series(second, volume(2)).await?;
+        set_series(first, volume(1)).await?;
+        set_series(third, volume(3)).await?;
+        let book = book_repo.find_by_id(first).await?.unwrap();
+        let series = book.series.unwrap();
+        assert_eq!(series.title, "Rust シリーズ");
+        assert_eq!(series.volume, 1);
+        assert_eq!(
+            book_repo
+                .find_by_id(second)
+                .await?
+                .unwrap()
+                .series
+                .unwrap()
+                .id,
+            series.id
+        );
+
+        // 巻数の順に並び、貸出状況が分かる
+        checkout_repo
+            .create(CreateCheckout {
+                book_id: second,
+                checked_out_by: owner,
+                checked_out_at: Utc::now(),
+            })
+            .await?;
+        let found = repo.find_by_id(series.id).await?.unwrap();
+        assert_eq!(
+            found
+                .volumes
+                .iter()
+                .map(|v| (v.volume, v.book_id, v.available_copies))
+                .collect::<Vec<_>>(),
+            vec![(1, first, 1), (2, second, 0), (3, third, 1)]
+        );
+
+        // シリーズを指定しない更新ではシリーズは変わらず、null を指定するとシリーズから外れる
+        set_series(first, None).await?;
+        assert!(book_repo.find_by_id(first).await?.unwrap().series.is_some());
+        set_series(first, Some(None)).await?;
+        assert!(book_repo.find_by_id(first).await?.unwrap().series.is_none());
+
+        // 削除された蔵書は巻の一覧に含めない
+        book_repo
+            .delete(DeleteBook {
+                book_id: third,
+                requested_by: Actor {
+                    id: owner,
+                    role: Role::User,
+                },
+                expected_updated_at: None,
+            })
+            .await?;
+        let found = repo.find_by_id(series.id).await?.unwrap();
+        assert_eq!(found.volumes.len(), 1);
+        assert_eq!(found.volumes[0].book_id, second);
+
+        assert!(repo.find_by_id(SeriesId::new()).await?.is_none());
+
+        Ok(())
+    }
+}
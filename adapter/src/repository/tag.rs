@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::TagId,
+        tag::{
+            event::{CreateTag, DeleteTag, UpdateTag},
+            Tag,
+        },
+    },
+    repository::tag::TagRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{model::tag::TagRow, ConnectionPool};
+
+#[derive(new)]
+pub struct TagRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl TagRepository for TagRepositoryImpl {
+    async fn find_all(&self) -> AppResult<Vec<Tag>> {
+        let tags = sqlx::query_as!(
+            TagRow,
+            r#"
+                SELECT tag_id, name
+                FROM tags
+                ORDER BY name ASC
+            "#
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(Tag::from)
+        .collect();
+
+        Ok(tags)
+    }
+
+    async fn create(&self, event: CreateTag) -> AppResult<Tag> {
+        let tag_id = TagId::new();
+        sqlx::query!(
+            r#"
+                INSERT INTO tags (tag_id, name)
+                VALUES ($1, $2)
+            "#,
+            tag_id as _,
+            event.name
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_name_conflict)?;
+
+        Ok(Tag {
+            id: tag_id,
+            name: event.name,
+        })
+    }
+
+    async fn update(&self, event: UpdateTag) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                UPDATE tags
+                SET name = $2
+                WHERE tag_id = $1
+            "#,
+            event.tag_id as _,
+            event.name
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(map_name_conflict)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound("specified tag not found".into()));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, event: DeleteTag) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM tags
+                WHERE tag_id = $1
+            "#,
+            event.tag_id as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound("specified tag not found".into()));
+        }
+        Ok(())
+    }
+}
+
+// タグ名の一意制約に違反した場合は、登録済みのタグと重複している旨のエラーにする
+fn map_name_conflict(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("tags_name_key") => AppError::ConflictError("specified tag already exists".into()),
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_tag_crud(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = TagRepositoryImpl::new(ConnectionPool::new(pool));
+
+        let rust = repo
+            .create(CreateTag {
+                name: "Rust".into(),
+            })
+            .await?;
+        repo.create(CreateTag {
+            name: "Database".into(),
+        })
+        .await?;
+
+        // 同じ名前のタグは登録できない
+        let res = repo
+            .create(CreateTag {
+                name: "Rust".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        repo.update(UpdateTag {
+            tag_id: rust.id,
+            name: "プログラミング言語".into(),
+        })
+        .await?;
+        let res = repo
+            .update(UpdateTag {
+                tag_id: rust.id,
+                name: "Database".into(),
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ConflictError(_))));
+
+        // 名前の昇順に並ぶ
+        let tags = repo.find_all().await?;
+        assert_eq!(
+            tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["Database", "プログラミング言語"]
+        );
+
+        repo.delete(DeleteTag { tag_id: rust.id }).await?;
+        let res = repo.delete(DeleteTag { tag_id: rust.id }).await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+        assert_eq!(repo.find_all().await?.len(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use derive_new::new;
+use kernel::{
+    model::{
+        id::{BookId, UserId},
+        transfer::{
+            event::{AcceptTransfer, CancelTransfer, ProposeTransfer, ReassignOwner},
+            BookTransfer,
+        },
+    },
+    repository::transfer::BookTransferRepository,
+};
+use shared::error::{AppError, AppResult};
+
+use crate::database::{model::transfer::BookTransferRow, ConnectionPool};
+
+#[derive(new)]
+pub struct BookTransferRepositoryImpl {
+    db: ConnectionPool,
+}
+
+#[async_trait]
+impl BookTransferRepository for BookTransferRepositoryImpl {
+    async fn find_by_book_id(&self, book_id: BookId) -> AppResult<Option<BookTransfer>> {
+        let row = sqlx::query_as!(
+            BookTransferRow,
+            r#"
+                SELECT
+                    t.book_id,
+                    b.title,
+                    t.from_user_id,
+                    fu.name AS from_user_name,
+                    t.to_user_id,
+                    tu.name AS to_user_name,
+                    t.created_at
+                FROM book_transfers AS t
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN users AS fu ON fu.user_id = t.from_user_id
+                INNER JOIN users AS tu ON tu.user_id = t.to_user_id
+                WHERE t.book_id = $1
+                AND   b.deleted_at IS NULL
+            "#,
+            book_id as _
+        )
+        .fetch_optional(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        Ok(row.map(BookTransfer::from))
+    }
+
+    async fn find_incoming(&self, user_id: UserId) -> AppResult<Vec<BookTransfer>> {
+        let transfers = sqlx::query_as!(
+            BookTransferRow,
+            r#"
+                SELECT
+                    t.book_id,
+                    b.title,
+                    t.from_user_id,
+                    fu.name AS from_user_name,
+                    t.to_user_id,
+                    tu.name AS to_user_name,
+                    t.created_at
+                FROM book_transfers AS t
+                INNER JOIN books AS b USING(book_id)
+                INNER JOIN users AS fu ON fu.user_id = t.from_user_id
+                INNER JOIN users AS tu ON tu.user_id = t.to_user_id
+                WHERE t.to_user_id = $1
+                AND   b.deleted_at IS NULL
+                ORDER BY t.created_at ASC
+            "#,
+            user_id as _
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .into_iter()
+        .map(BookTransfer::from)
+        .collect();
+
+        Ok(transfers)
+    }
+
+    async fn propose(&self, event: ProposeTransfer) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let owner = lock_owner(&mut tx, event.book_id).await?;
+        if owner != event.requested_user {
+            return Err(AppError::ForbiddenOperation);
+        }
+        if event.to_user == owner {
+            return Err(AppError::UnprocessableEntiry(
+                "cannot transfer a book to its owner".into(),
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+                INSERT INTO book_transfers (book_id, from_user_id, to_user_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (book_id) DO UPDATE
+                SET from_user_id = EXCLUDED.from_user_id,
+                    to_user_id = EXCLUDED.to_user_id,
+                    created_at = CURRENT_TIMESTAMP(3)
+            "#,
+            event.book_id as _,
+            owner as _,
+            event.to_user as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_user_not_found)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn accept(&self, event: AcceptTransfer) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        let owner = lock_owner(&mut tx, event.book_id).await?;
+        // 所有者が変わった後に残っていた申し出は承認できない
+        let recipient = sqlx::query_scalar!(
+            r#"
+                SELECT to_user_id AS "to_user_id: UserId" FROM book_transfers
+                WHERE book_id = $1
+                AND   from_user_id = $2
+            "#,
+            event.book_id as _,
+            owner as _
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?
+        .ok_or_else(|| AppError::EntityNotFound("specified transfer not found".into()))?;
+        if recipient != event.requested_user {
+            return Err(AppError::ForbiddenOperation);
+        }
+
+        sqlx::query!(
+            "DELETE FROM book_transfers WHERE book_id = $1",
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+        change_owner(&mut tx, event.book_id, recipient).await?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+
+    async fn cancel(&self, event: CancelTransfer) -> AppResult<()> {
+        let res = sqlx::query!(
+            r#"
+                DELETE FROM book_transfers
+                WHERE book_id = $1
+                AND   (from_user_id = $2 OR to_user_id = $2)
+            "#,
+            event.book_id as _,
+            event.requested_user as _
+        )
+        .execute(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        if res.rows_affected() < 1 {
+            return Err(AppError::EntityNotFound(
+                "specified transfer not found".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn reassign(&self, event: ReassignOwner) -> AppResult<()> {
+        let mut tx = self.db.begin().await?;
+
+        lock_owner(&mut tx, event.book_id).await?;
+        change_owner(&mut tx, event.book_id, event.to_user).await?;
+        sqlx::query!(
+            "DELETE FROM book_transfers WHERE book_id = $1",
+            event.book_id as _
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        tx.commit().await.map_err(AppError::TransactionError)?;
+
+        Ok(())
+    }
+}
+
+// 譲渡の処理が終わるまで蔵書の行をロックし、現在の所有者を返す
+async fn lock_owner(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+) -> AppResult<UserId> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT user_id AS "user_id: UserId" FROM books
+            WHERE book_id = $1
+            AND   deleted_at IS NULL
+            FOR UPDATE
+        "#,
+        book_id as _
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(AppError::SpecificOperationError)?
+    .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))
+}
+
+async fn change_owner(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    book_id: BookId,
+    user_id: UserId,
+) -> AppResult<()> {
+    sqlx::query!(
+        "UPDATE books SET user_id = $2 WHERE book_id = $1",
+        book_id as _,
+        user_id as _
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(map_user_not_found)?;
+
+    Ok(())
+}
+
+fn map_user_not_found(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|e| e.constraint()) {
+        Some("book_transfers_to_user_id_fkey" | "books_user_id_fkey") => {
+            AppError::UnprocessableEntiry("specified user not found".into())
+        }
+        _ => AppError::SpecificOperationError(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use kernel::{
+        model::user::event::{CreateUser, DeleteUser},
+        repository::{book::BookRepository, user::UserRepository},
+    };
+
+    use super::*;
+    use crate::repository::{book::BookRepositoryImpl, user::UserRepositoryImpl};
+
+    #[sqlx::test(fixtures("common", "book"))]
+    async fn test_book_transfer(pool: sqlx::PgPool) -> anyhow::Result<()> {
+        let repo = BookTransferRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_repo = BookRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let user_repo = UserRepositoryImpl::new(ConnectionPool::new(pool.clone()));
+        let book_id = BookId::from_str("9890736e-a4e4-461a-a77d-eac3517ef11b")?;
+        let owner = UserId::from_str("5b4c96ac-316a-4bee-8e69-cac5eb84ff4c")?;
+        let other = user_repo
+            .create(CreateUser {
+                name: "Other User".into(),
+                email: "other@example.com".into(),
+                password: "test_password".into(),
+            })
+            .await?
+            .id;
+
+        let propose = |to_user, requested_user| ProposeTransfer {
+            book_id,
+            to_user,
+            requested_user,
+        };
+
+        // 所有者以外は申し出られず、所有者自身や存在しないユーザーには譲渡できない
+        let res = repo.propose(propose(owner, other)).await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        let res = repo.propose(propose(owner, owner)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        let res = repo.propose(propose(UserId::new(), owner)).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+
+        repo.propose(propose(other, owner)).await?;
+        let transfer = repo.find_by_book_id(book_id).await?.unwrap();
+        assert_eq!(transfer.from.id, owner);
+        assert_eq!(transfer.to.id, other);
+        assert_eq!(repo.find_incoming(other).await?.len(), 1);
+        assert!(repo.find_incoming(owner).await?.is_empty());
+
+        // 譲渡先のユーザーだけが承認でき、承認すると所有者が変わる
+        let res = repo
+            .accept(AcceptTransfer {
+                book_id,
+                requested_user: owner,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::ForbiddenOperation)));
+        repo.accept(AcceptTransfer {
+            book_id,
+            requested_user: other,
+        })
+        .await?;
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, other);
+        assert!(repo.find_by_book_id(book_id).await?.is_none());
+
+        // 譲渡先のユーザーは辞退できる
+        repo.propose(propose(owner, other)).await?;
+        repo.cancel(CancelTransfer {
+            book_id,
+            requested_user: owner,
+        })
+        .await?;
+        let res = repo
+            .cancel(CancelTransfer {
+                book_id,
+                requested_user: owner,
+            })
+            .await;
+        assert!(matches!(res, Err(AppError::EntityNotFound(_))));
+
+        // 管理者による付け替えでは、承認待ちの申し出も取り消される
+        repo.propose(propose(owner, other)).await?;
+        repo.reassign(ReassignOwner {
+            book_id,
+            to_user: owner,
+        })
+        .await?;
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, owner);
+        assert!(repo.find_by_book_id(book_id).await?.is_none());
+
+        // 蔵書を所有しているユーザーは削除できず、付け替えた後なら削除できる
+        let res = user_repo.delete(DeleteUser { user_id: owner }).await;
+        assert!(matches!(res, Err(AppError::UnprocessableEntiry(_))));
+        assert!(book_repo.find_by_id(book_id).await?.is_some());
+        let owned = sqlx::query_scalar!(
+            r#"SELECT book_id AS "book_id: BookId" FROM books WHERE user_id = $1"#,
+            owner as _
+        )
+        .fetch_all(&pool)
+        .await?;
+        for book_id in owned {
+            repo.reassign(ReassignOwner {
+                book_id,
+                to_user: other,
+            })
+            .await?;
+        }
+        user_repo.delete(DeleteUser { user_id: owner }).await?;
+        let book = book_repo.find_by_id(book_id).await?.unwrap();
+        assert_eq!(book.owner.id, other);
+
+        Ok(())
+    }
+}
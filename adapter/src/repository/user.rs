@@ -3,6 +3,7 @@ use derive_new::new;
 use kernel::{
     model::{
         id::UserId,
+        list::{CursorOptions, CursorPaginatedList},
         role::Role,
         user::{
             event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole},
@@ -13,7 +14,10 @@ use kernel::{
 };
 use shared::error::{AppError, AppResult};
 
-use crate::database::{model::user::UserRow, ConnectionPool};
+use crate::database::{
+    model::{list::TimestampCursor, user::UserRow},
+    ConnectionPool,
+};
 
 #[derive(new)]
 pub struct UserRepositoryImpl {
@@ -73,6 +77,73 @@ impl UserRepository for UserRepositoryImpl {
         Ok(users)
     }
 
+    async fn find_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<User>> {
+        const ORDER_BY: &str = "created_at:desc,user_id:desc";
+        let (after, before) = TimestampCursor::split(options.cursor.as_ref(), ORDER_BY)?;
+        let (after_at, after_id) = after.map(|c| (c.at, c.id)).unzip();
+        let (before_at, before_id) = before.map(|c| (c.at, c.id)).unzip();
+
+        // find_all と同じく登録日時の新しい順に並べる
+        // 前のページを取得する場合は逆順に取得し、CursorPaginatedList::from_rows で並べ直す
+        let rows = sqlx::query_as!(
+            UserRow,
+            r#"
+                SELECT
+                    u.user_id,
+                    u.name,
+                    u.email,
+                    r.name as role_name,
+                    u.created_at,
+                    u.updated_at
+                FROM users AS u
+                INNER JOIN roles AS r USING(role_id)
+                WHERE ($1::timestamptz IS NULL OR (u.created_at, u.user_id) < ($1, $2::uuid))
+                AND   ($3::timestamptz IS NULL OR (u.created_at, u.user_id) > ($3, $4::uuid))
+                ORDER BY
+                    CASE WHEN $3::timestamptz IS NOT NULL THEN u.created_at END ASC,
+                    CASE WHEN $3::timestamptz IS NOT NULL THEN u.user_id END ASC,
+                    u.created_at DESC,
+                    u.user_id DESC
+                LIMIT $5
+            "#,
+            after_at,
+            after_id,
+            before_at,
+            before_id,
+            options.limit.saturating_add(1),
+        )
+        .fetch_all(self.db.inner_ref())
+        .await
+        .map_err(AppError::SpecificOperationError)?;
+
+        let page = CursorPaginatedList::from_rows(rows, &options, |row| {
+            TimestampCursor {
+                at: row.created_at,
+                id: row.user_id.raw(),
+            }
+            .into_cursor(ORDER_BY)
+        });
+        let CursorPaginatedList {
+            limit,
+            items,
+            next_cursor,
+            prev_cursor,
+        } = page;
+
+        Ok(CursorPaginatedList {
+            limit,
+            items: items
+                .into_iter()
+                .filter_map(|row| User::try_from(row).ok())
+                .collect(),
+            next_cursor,
+            prev_cursor,
+        })
+    }
+
     async fn create(&self, event: CreateUser) -> AppResult<User> {
         let user_id = UserId::new();
         let hashed_password = hash_password(&event.password)?;
@@ -170,7 +241,13 @@ impl UserRepository for UserRepositoryImpl {
         )
         .execute(self.db.inner_ref())
         .await
-        .map_err(AppError::SpecificOperationError)?;
+        .map_err(|e| match e.as_database_error().and_then(|e| e.constraint()) {
+            // 所有している蔵書がある場合は、先に所有者を付け替えてもらう
+            Some("books_user_id_fkey") => AppError::UnprocessableEntiry(
+                "所有している蔵書があるため削除できません。蔵書の所有者を付け替えてから削除してください。".into(),
+            ),
+            _ => AppError::SpecificOperationError(e),
+        })?;
         if res.rows_affected() < 1 {
             return Err(AppError::EntityNotFound("specified user not found".into()));
         }
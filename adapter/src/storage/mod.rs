@@ -0,0 +1,67 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use shared::{config::StorageConfig, error::AppResult};
+use uuid::Uuid;
+
+// ローカルのファイルシステムにファイルを保存するクライアント
+// キーは root からの相対パスとして扱う
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(config: &StorageConfig) -> Self {
+        Self {
+            root: config.root.clone(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    pub async fn put(&self, key: &str, data: &[u8]) -> AppResult<()> {
+        let path = self.path(key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        // 書き込み途中のファイルを読み出さないよう、一時ファイルに書いてから置き換える
+        // 同じキーへの書き込みが同時に行われても互いの一時ファイルを上書きしないよう、名前は毎回変える
+        let tmp = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        if let Err(e) = tokio::fs::write(&tmp, data).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        if let Err(e) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> AppResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path(key)).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // 存在しないファイルを削除しようとした場合もエラーにしない
+    pub async fn delete(&self, key: &str) -> AppResult<()> {
+        match tokio::fs::remove_file(self.path(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // prefix 以下のファイルをすべて削除する。存在しない場合もエラーにしない
+    pub async fn delete_all(&self, prefix: &str) -> AppResult<()> {
+        match tokio::fs::remove_dir_all(self.path(prefix)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -3,7 +3,12 @@ use axum_extra::{
     headers::{authorization::Bearer, Authorization},
     TypedHeader,
 };
-use kernel::model::{auth::AccessToken, id::UserId, role::Role, user::User};
+use kernel::model::{
+    auth::AccessToken,
+    id::UserId,
+    role::Role,
+    user::{Actor, User},
+};
 use registry::AppRegistry;
 use shared::error::AppError;
 
@@ -21,6 +26,14 @@ impl AuthorizedUser {
     pub fn is_admin(&self) -> bool {
         self.user.role == Role::Admin
     }
+
+    // 蔵書の変更などの権限の判定に使う
+    pub fn actor(&self) -> Actor {
+        Actor {
+            id: self.user.id,
+            role: self.user.role,
+        }
+    }
 }
 
 #[async_trait]
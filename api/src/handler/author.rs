@@ -0,0 +1,96 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use garde::Validate;
+use kernel::model::id::AuthorId;
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::{
+        author::{AuthorBookListQuery, AuthorSuggestQuery, AuthorsResponse},
+        book::PaginatedBookResponse,
+    },
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/authors",
+        responses(
+            (status = 200, description = "著者名の候補の取得に成功した場合。", body = AuthorsResponse),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+        ),
+        params(
+            ("keyword" = String, Query, description = "著者名の一部。前方一致する著者から順に返す"),
+            ("limit" = Option<i64>, Query, description = "返す候補の数の上限。1 から 50 まで。省略時は 10"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn suggest_authors(
+    user: AuthorizedUser,
+    Query(query): Query<AuthorSuggestQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<AuthorsResponse>> {
+    query.validate(&())?;
+
+    registry
+        .author_repository()
+        .suggest(&query.keyword, query.limit)
+        .await
+        .map(AuthorsResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/authors/{author_id}/books",
+        responses(
+            (status = 200, description = "著者の蔵書一覧の取得に成功した場合。", body = PaginatedBookResponse),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "指定された著者が存在しない場合。"),
+        ),
+        params(
+            ("author_id" = AuthorId, Path, description = "著者 ID"),
+            ("limit" = Option<i64>, Query, description = "一度に取得する蔵書数の上限値の指定。100 まで"),
+            ("offset" = Option<i64>, Query, description = "取得対象とする蔵書一覧の開始位置"),
+            ("role" = Option<String>, Query, description = "著者の役割で絞り込む。`author`、`editor`、`translator` のいずれか"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_author_books(
+    user: AuthorizedUser,
+    Path(author_id): Path<AuthorId>,
+    Query(query): Query<AuthorBookListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedBookResponse>> {
+    query.validate(&())?;
+
+    registry
+        .author_repository()
+        .find_by_id(author_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified author not found".into()))?;
+
+    registry
+        .book_repository()
+        .find_all(query.into_options(author_id))
+        .await
+        .map(PaginatedBookResponse::from)
+        .map(Json)
+}
@@ -1,19 +1,40 @@
 use axum::{
+    body::{Body, Bytes},
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use garde::Validate;
-use kernel::model::{book::event::DeleteBook, id::BookId};
+use kernel::model::{
+    book::{
+        duplicate::DuplicateReason,
+        event::{
+            CreateBook, DeleteBook, DeleteBookCopy, ImportBooks, ImportMode, PurgeBook,
+            RestoreBook, UpdateBook, UpdateBookCopyWithdrawal,
+        },
+        BookImportResult, BookImportStatus,
+    },
+    id::{BookId, CopyId},
+    isbn::Isbn,
+};
 use registry::AppRegistry;
 use shared::error::{AppError, AppResult};
+use tokio_stream::StreamExt;
 
 use crate::{
     extractor::AuthorizedUser,
     model::book::{
-        BookListQuery, BookResponse, CreateBookRequest, PaginatedBookResponse, UpdateBookRequest,
-        UpdateBookRequestWithIds,
+        BookCopiesResponse, BookListQuery, BookLookupQuery, BookMetadataResponse, BookResponse,
+        CreateBookCopyRequest, CreateBookCopyRequestWithIds, CreateBookRequest,
+        CursorPaginatedBookResponse, DeletedBookListQuery, DuplicateBooksResponse,
+        PaginatedBookResponse, UpdateBookCopyConditionRequest,
+        UpdateBookCopyConditionRequestWithIds, UpdateBookCopyLocationRequest,
+        UpdateBookCopyLocationRequestWithIds, UpdateBookRequest, UpdateBookRequestWithIds,
     },
+    model::etag::{book_etag, matches_if_none_match, parse_if_match},
+    model::export::BookExportQuery,
+    model::import::{parse_import_rows, BookImportFormat, BookImportQuery, BookImportResponse},
 };
 
 // アクセストークンによるユーザ検証をおこなうため user を引数に追加
@@ -28,6 +49,7 @@ use crate::{
             (status = 201, description = "蔵書の登録に成功した場合。"),
             (status = 400, description = "リクエストのパラメータに不備があった場合。"),
             (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 409, description = "重複の候補がある場合。重複の候補の一覧を返す。`force` を `true` にすると、タイトルが似ていて共通する著者がいる蔵書があっても登録できる。ただし同じ ISBN の蔵書がある場合は `force` を指定しても登録できない（ISBN は蔵書ごとに一意のため）。同じ本をもう 1 冊持つ場合は、既存の蔵書に冊子を追加する。", body = DuplicateBooksResponse),
             (status = 422, description = "リクエストした蔵書の登録に失敗した場合。")
         )
     )
@@ -41,16 +63,188 @@ use crate::{
 pub async fn register_book(
     user: AuthorizedUser,
     State(registry): State<AppRegistry>,
-    Json(req): Json<CreateBookRequest>,
-) -> AppResult<StatusCode> {
+    Json(mut req): Json<CreateBookRequest>,
+) -> AppResult<Response> {
+    // 書誌情報を引けなかった場合も登録は続け、足りない項目はバリデーションで弾く
+    if req.autofill {
+        if let Ok(isbn) = req.isbn.parse::<Isbn>() {
+            match registry.book_metadata_provider().lookup(&isbn).await {
+                Ok(Some(metadata)) => req.fill_missing(metadata),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error.message = %e, "failed to look up book metadata"),
+            }
+        }
+    }
+
     // これは thiserror つかってる AppError のどれになるんだろう
     req.validate(&())?;
 
+    // 同じ ISBN の蔵書は force を指定しても登録できない（同じ本を複数冊持つ場合は冊子を追加する）
+    let force = req.force;
+    let event: CreateBook = req.try_into()?;
+    let candidates = registry.book_repository().find_duplicates(&event).await?;
+    let has_same_isbn = candidates.iter().any(|c| c.reason == DuplicateReason::Isbn);
+    if has_same_isbn || (!force && !candidates.is_empty()) {
+        return Ok((
+            StatusCode::CONFLICT,
+            Json(DuplicateBooksResponse::from(candidates)),
+        )
+            .into_response());
+    }
+
     registry
         .book_repository()
-        .create(req.into(), user.id())
+        .create(event, user.id())
         .await
-        .map(|_| StatusCode::CREATED)
+        .map(|_| StatusCode::CREATED.into_response())
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/lookup",
+        responses(
+            (status = 200, description = "ISBN に該当する書誌情報が見つかった場合。", body = BookMetadataResponse),
+            (status = 400, description = "ISBN の形式が正しくない場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "ISBN に該当する書誌情報がなかった場合。"),
+            (status = 502, description = "書誌情報の提供元への問い合わせに失敗した場合。"),
+        ),
+        params(
+            ("isbn" = String, Query, description = "ISBN-10 または ISBN-13。ハイフンの有無は問わない"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn lookup_book_metadata(
+    _user: AuthorizedUser,
+    Query(query): Query<BookLookupQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookMetadataResponse>> {
+    let isbn = query.isbn.parse::<Isbn>()?;
+    registry
+        .book_metadata_provider()
+        .lookup(&isbn)
+        .await?
+        .map(BookMetadataResponse::from)
+        .map(Json)
+        .ok_or_else(|| AppError::EntityNotFound("book metadata not found".into()))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/import",
+        request_body(content = String, content_type = "text/csv", description = "title・author・isbn・description の列を持つ CSV（1 行目はヘッダー）、または同じ項目を持つ JSON を 1 行ずつ並べた NDJSON（`application/x-ndjson`）"),
+        responses(
+            (status = 200, description = "取り込みを行った場合。行ごとの結果を返す。", body = BookImportResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 415, description = "CSV・NDJSON 以外の形式が指定された場合。"),
+            (status = 422, description = "`allOrNothing` で登録できない行があったため、何も登録しなかった場合。行ごとの結果を返す。", body = BookImportResponse),
+        ),
+        params(
+            ("mode" = Option<String>, Query, description = "`allOrNothing`（1 行でも登録できなければ何も登録しない）または `bestEffort`（登録できる行だけ登録する）。省略時は `allOrNothing`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, headers, body),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn import_books(
+    user: AuthorizedUser,
+    Query(query): Query<BookImportQuery>,
+    State(registry): State<AppRegistry>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Response> {
+    let format = BookImportFormat::from_content_type(
+        headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default(),
+    )?;
+    let mode = ImportMode::from(query.mode);
+
+    let (rows, mut results) = parse_import_rows(format, &body);
+
+    // すべて登録するモードで検証に通らない行があれば、DB には問い合わせずに終える
+    if mode == ImportMode::AllOrNothing && !results.is_empty() {
+        results.extend(rows.into_iter().map(|row| BookImportResult {
+            line: row.line,
+            status: BookImportStatus::Skipped,
+        }));
+    } else if !rows.is_empty() {
+        let imported = registry
+            .book_repository()
+            .import(ImportBooks {
+                rows,
+                mode,
+                requested_user: user.id(),
+            })
+            .await?;
+        results.extend(imported);
+    }
+
+    let res = BookImportResponse::from(results);
+    let status = if mode == ImportMode::AllOrNothing && res.failed > 0 {
+        StatusCode::UNPROCESSABLE_ENTITY
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(res)).into_response())
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/export",
+        responses(
+            (status = 200, description = "すべての蔵書を所有者・貸出状態とともに出力する。", content_type = ["text/csv", "application/x-ndjson"]),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+        ),
+        params(
+            ("format" = Option<String>, Query, description = "`csv`（Excel 向けの BOM 付き CSV）または `ndjson`（蔵書一覧と同じ形式の JSON を 1 行ずつ並べたもの）。省略時は `csv`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn export_books(
+    _user: AuthorizedUser,
+    Query(query): Query<BookExportQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    let format = query.format;
+    let preamble = format.preamble()?;
+
+    // 蔵書をすべてメモリに載せないよう、DB から読み出した順に書き出す
+    let books = registry
+        .book_repository()
+        .export_all()
+        .map(move |book| book.and_then(|book| format.encode(book)));
+    let body = Body::from_stream(tokio_stream::once(Ok(preamble)).chain(books));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
 }
 
 // ここなんで user つかってる？
@@ -67,8 +261,20 @@ pub async fn register_book(
             (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
         ),
         params(
-            ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定"),
+            ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定。100 まで"),
             ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置"),
+            ("keyword" = Option<String>, Query, description = "タイトル・著者名・ISBN・説明文に対する検索キーワード。空白区切りで複数指定でき、関連度の高い順に返す"),
+            ("owner" = Option<Uuid>, Query, description = "蔵書の所有者のユーザーIDで絞り込む"),
+            ("isbn" = Option<String>, Query, description = "ISBN で絞り込む。ISBN-10・ISBN-13 のどちらでも指定でき、ハイフンの有無は問わない"),
+            ("author" = Option<String>, Query, description = "著者名で絞り込む（部分一致）"),
+            ("availability" = Option<String>, Query, description = "貸出状態で絞り込む。`available`（貸出可能な冊子がある）または `checkedOut`（すべての冊子が貸出中）"),
+            ("tags" = Option<String>, Query, description = "タグIDで絞り込む。カンマ区切りで複数指定した場合はすべてのタグが付けられた蔵書を返す"),
+            ("location" = Option<Uuid>, Query, description = "場所IDで絞り込む。指定した場所、もしくはその配下に冊子が置かれている蔵書を返す"),
+            ("sort" = Option<String>, Query, description = "並び替えのキー。`title`、`author`、`createdAt`、`updatedAt`、`rating`（レビューの評価の平均）のいずれか。省略時は検索キーワードとの関連度、登録日時の新しい順"),
+            ("order" = Option<String>, Query, description = "並び順。`asc` または `desc`。省略時は `title`・`author` は昇順、それ以外は降順"),
+            ("paging" = Option<String>, Query, description = "`cursor` を指定するとカーソルによるページネーションを行い、`CursorPaginatedBookResponse` を返す。この場合 `offset` は使わず、総件数も返さない"),
+            ("after" = Option<String>, Query, description = "カーソルによるページネーションで、前回のレスポンスの `nextCursor` を指定すると次のページを返す。指定した場合は `paging=cursor` とみなす"),
+            ("before" = Option<String>, Query, description = "カーソルによるページネーションで、前回のレスポンスの `prevCursor` を指定すると前のページを返す。指定した場合は `paging=cursor` とみなす"),
         )
     )
 )]
@@ -81,17 +287,27 @@ pub async fn register_book(
 #[axum::debug_handler]
 pub async fn show_book_list(
     user: AuthorizedUser,
-    Query(query): Query<BookListQuery>,
+    Query(mut query): Query<BookListQuery>,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<PaginatedBookResponse>> {
+) -> AppResult<Response> {
     query.validate(&())?;
 
+    if query.is_cursor_paging() {
+        let cursor = query.take_page_cursor()?;
+        return registry
+            .book_repository()
+            .find_all_by_cursor(query.into(), cursor)
+            .await
+            .map(CursorPaginatedBookResponse::from)
+            .map(|res| Json(res).into_response());
+    }
+
     registry
         .book_repository()
         .find_all(query.into())
         .await
         .map(PaginatedBookResponse::from)
-        .map(Json)
+        .map(|res| Json(res).into_response())
 }
 
 #[cfg_attr(
@@ -99,20 +315,21 @@ pub async fn show_book_list(
     // 2
     utoipa::path(
         get,
-        path="/api/v1/books",
+        path="/api/v1/books/{book_id}",
         responses(
-            (status = 200, description = "蔵書一覧の取得に成功した場合。", body = PaginatedBookResponse),
-            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 200, description = "蔵書の取得に成功した場合。", body = BookResponse),
+            (status = 304, description = "If-None-Match で指定された ETag と蔵書の ETag が一致した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正な場合。"),
             (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
         ),
         params(
-            ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定"),
-            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置"),
+            ("book_id" = Uuid, Path, description = "蔵書ID")
         )
     )
 )]
 #[tracing::instrument(
-    skip(_user, registry),
+    skip(_user, registry, headers),
     fields(
         user_id = %_user.user.id.to_string()
     )
@@ -121,17 +338,24 @@ pub async fn show_book_list(
 pub async fn show_book(
     _user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
     State(registry): State<AppRegistry>,
-) -> AppResult<Json<BookResponse>> {
+) -> AppResult<Response> {
     tracing::info!("ここにログを追加した");
-    registry
+    let book = registry
         .book_repository()
         .find_by_id(book_id)
-        .await
-        .and_then(|bc| match bc {
-            Some(bc) => Ok(Json(bc.into())),
-            None => Err(AppError::EntityNotFound("not found".into())),
-        })
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("not found".into()))?;
+
+    // 更新や削除の際に If-Match で指定できるよう、ETag を返す
+    let updated_at = book.updated_at;
+    let book = BookResponse::from(book);
+    let etag = book_etag(updated_at, &book);
+    if matches_if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+    Ok(([(header::ETAG, etag)], Json(book)).into_response())
 }
 
 #[cfg_attr(
@@ -141,7 +365,10 @@ pub async fn show_book(
         responses(
             (status = 200, description = "蔵書の更新に成功した場合。"),
             (status = 400, description = "リクエストのパラメータに不備があった場合。"),
-            (status = 404, description = "変更対象の書籍が見つからなかった場合。")
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "変更対象の書籍が見つからなかった場合。"),
+            (status = 409, description = "同じ ISBN の蔵書がすでに登録されていた場合。"),
+            (status = 412, description = "If-Match で指定された ETag と蔵書の ETag が一致しなかった場合。")
         ),
         params(
             ("book_id" = Uuid, Path, description = "蔵書ID")
@@ -149,7 +376,7 @@ pub async fn show_book(
     )
 )]
 #[tracing::instrument(
-    skip(user, registry),
+    skip(user, registry, headers),
     fields(
         user_id = %user.user.id.to_string()
     )
@@ -157,15 +384,18 @@ pub async fn show_book(
 pub async fn update_book(
     user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
     State(registry): State<AppRegistry>,
     Json(req): Json<UpdateBookRequest>,
 ) -> AppResult<StatusCode> {
     req.validate(&())?;
 
-    let update_book = UpdateBookRequestWithIds::new(book_id, user.id(), req);
+    let mut update_book: UpdateBook =
+        UpdateBookRequestWithIds::new(book_id, user.actor(), req).try_into()?;
+    update_book.expected_updated_at = parse_if_match(&headers);
     registry
         .book_repository()
-        .update(update_book.into())
+        .update(update_book)
         .await
         .map(|_| StatusCode::OK)
 }
@@ -176,7 +406,10 @@ pub async fn update_book(
         responses(
             (status = 204, description = "書籍の削除に成功した場合。"),
             (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
             (status = 404, description = "削除対象の書籍が存在しなかった場合。"),
+            (status = 412, description = "If-Match で指定された ETag と蔵書の ETag が一致しなかった場合。"),
+            (status = 422, description = "削除対象の書籍が貸出中だった場合。"),
         ),
         params(
             ("book_id" = Uuid, Path, description = "蔵書ID")
@@ -184,7 +417,7 @@ pub async fn update_book(
     )
 )]
 #[tracing::instrument(
-    skip(user, registry),
+    skip(user, registry, headers),
     fields(
         user_id = %user.user.id.to_string()
     )
@@ -192,15 +425,399 @@ pub async fn update_book(
 pub async fn delete_book(
     user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    headers: HeaderMap,
     State(registry): State<AppRegistry>,
 ) -> AppResult<StatusCode> {
     let delete_book = DeleteBook {
         book_id,
-        requested_user: user.id(),
+        requested_by: user.actor(),
+        expected_updated_at: parse_if_match(&headers),
     };
+    // 論理削除のため、表紙画像は完全に削除するまで残しておく
     registry
         .book_repository()
         .delete(delete_book)
         .await
         .map(|_| StatusCode::OK)
 }
+
+/// 削除済みの蔵書一覧を取得する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/deleted",
+        responses(
+            (status = 200, description = "削除済みの蔵書一覧の取得に成功した場合。", body = PaginatedBookResponse),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+        ),
+        params(
+            ("limit" = i64, Query, description = "一度に取得する蔵書数の上限値の指定。100 まで"),
+            ("offset" = i64, Query, description = "取得対象とする蔵書一覧の開始位置"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_deleted_book_list(
+    user: AuthorizedUser,
+    Query(query): Query<DeletedBookListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedBookResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    query.validate(&())?;
+
+    registry
+        .book_repository()
+        .find_deleted(query.limit, query.offset)
+        .await
+        .map(PaginatedBookResponse::from)
+        .map(Json)
+}
+
+/// 削除済みの蔵書を元に戻す（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/restore",
+        responses(
+            (status = 200, description = "蔵書の復元に成功した場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "復元対象の削除済みの蔵書が存在しなかった場合。"),
+            (status = 409, description = "同じ ISBN の蔵書がすでに登録されていた場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn restore_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .book_repository()
+        .restore(RestoreBook { book_id })
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 削除済みの蔵書を完全に削除する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/purge",
+        responses(
+            (status = 204, description = "蔵書の完全な削除に成功した場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "削除対象の削除済みの蔵書が存在しなかった場合。"),
+            (status = 422, description = "蔵書に貸し出し中の冊子が残っている場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn purge_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .book_repository()
+        .purge(PurgeBook { book_id })
+        .await?;
+    // 完全に削除した蔵書の表紙画像も片付ける
+    registry
+        .cover_image_repository()
+        .purge(book_id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/copies",
+        responses(
+            (status = 200, description = "冊子の一覧の取得に成功した場合。", body = BookCopiesResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_copies(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookCopiesResponse>> {
+    registry
+        .book_repository()
+        .find_copies(book_id)
+        .await
+        .map(BookCopiesResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/copies",
+        request_body = CreateBookCopyRequest,
+        responses(
+            (status = 201, description = "冊子の追加に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "対象の蔵書が存在しない場合。"),
+            (status = 409, description = "同じバーコードの冊子がすでに登録されていた場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn add_book_copy(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateBookCopyRequest>,
+) -> AppResult<StatusCode> {
+    req.validate(&())?;
+
+    let create_book_copy = CreateBookCopyRequestWithIds::new(book_id, user.actor(), req);
+    registry
+        .book_repository()
+        .add_copy(create_book_copy.into())
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/copies/{copy_id}/location",
+        request_body = UpdateBookCopyLocationRequest,
+        responses(
+            (status = 200, description = "冊子の場所の変更に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "対象の冊子が存在しない場合。"),
+            (status = 422, description = "指定の場所が登録されていない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("copy_id" = Uuid, Path, description = "冊子ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn update_book_copy_location(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateBookCopyLocationRequest>,
+) -> AppResult<StatusCode> {
+    let update_location =
+        UpdateBookCopyLocationRequestWithIds::new(book_id, copy_id, user.actor(), req);
+    registry
+        .book_repository()
+        .update_copy_location(update_location.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/copies/{copy_id}/condition",
+        request_body = UpdateBookCopyConditionRequest,
+        responses(
+            (status = 200, description = "冊子の状態の変更に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "対象の冊子が存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("copy_id" = Uuid, Path, description = "冊子ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn update_book_copy_condition(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateBookCopyConditionRequest>,
+) -> AppResult<StatusCode> {
+    let update_condition =
+        UpdateBookCopyConditionRequestWithIds::new(book_id, copy_id, user.actor(), req);
+    registry
+        .book_repository()
+        .update_copy_condition(update_condition.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+// 修理が終わるまで冊子を貸出の対象から外す
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/copies/{copy_id}/withdrawal",
+        responses(
+            (status = 200, description = "冊子を貸出の対象から外した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "対象の冊子が存在しない場合。"),
+            (status = 422, description = "対象の冊子が貸し出し中の場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("copy_id" = Uuid, Path, description = "冊子ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn withdraw_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let withdraw = UpdateBookCopyWithdrawal {
+        book_id,
+        copy_id,
+        withdrawn: true,
+        requested_by: user.actor(),
+    };
+    registry
+        .book_repository()
+        .update_copy_withdrawal(withdraw)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/copies/{copy_id}/withdrawal",
+        responses(
+            (status = 204, description = "冊子を貸出の対象に戻した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "対象の冊子が存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("copy_id" = Uuid, Path, description = "冊子ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn reinstate_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let reinstate = UpdateBookCopyWithdrawal {
+        book_id,
+        copy_id,
+        withdrawn: false,
+        requested_by: user.actor(),
+    };
+    registry
+        .book_repository()
+        .update_copy_withdrawal(reinstate)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/copies/{copy_id}",
+        responses(
+            (status = 204, description = "冊子の削除に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "削除対象の冊子が存在しない場合。"),
+            (status = 422, description = "削除対象の冊子が貸し出し中の場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("copy_id" = Uuid, Path, description = "冊子ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn delete_book_copy(
+    user: AuthorizedUser,
+    Path((book_id, copy_id)): Path<(BookId, CopyId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_book_copy = DeleteBookCopy {
+        book_id,
+        copy_id,
+        requested_by: user.actor(),
+    };
+    registry
+        .book_repository()
+        .delete_copy(delete_book_copy)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
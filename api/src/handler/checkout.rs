@@ -1,16 +1,25 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
+use garde::Validate;
 use kernel::model::{
-    checkout::event::{CreateCheckout, UpdateReturned},
+    checkout::event::{CreateCheckout, CreateHold, CreateRenewal, DeleteHold, UpdateReturned},
     id::{BookId, CheckoutId},
 };
 use registry::AppRegistry;
-use shared::error::AppResult;
+use shared::error::{AppError, AppResult};
 
-use crate::{extractor::AuthorizedUser, model::checkout::CheckoutsResponse};
+use crate::{
+    extractor::AuthorizedUser,
+    model::{
+        checkout::{
+            CheckoutListQuery, CheckoutRenewalResponse, CheckoutsResponse, ReturnBookQuery,
+        },
+        list::CursorQuery,
+    },
+};
 
 #[cfg_attr(
     debug_assertions,
@@ -46,6 +55,112 @@ pub async fn checkout_book(
         .map(|_| StatusCode::CREATED)
 }
 
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/checkouts/{checkout_id}/renewals",
+        responses(
+            (status = 200, description = "貸出の延長に成功した場合。延長後の返却期限を返す。", body = CheckoutRenewalResponse),
+            (status = 400, description = "リクエストのパラメータが不正な場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "借りたユーザー以外が延長しようとした場合や、延長できる回数の上限に達している場合、ほかのユーザーが蔵書を予約している場合。"),
+            (status = 500, description = "延長の登録に失敗した場合。")
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("checkout_id" = Uuid, Path, description = "貸出ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn renew_checkout(
+    user: AuthorizedUser,
+    Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<CheckoutRenewalResponse>> {
+    let create_renewal = CreateRenewal::new(checkout_id, book_id, user.id(), chrono::Utc::now());
+
+    registry
+        .checkout_repository()
+        .renew(create_renewal)
+        .await
+        .map(CheckoutRenewalResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/holds",
+        responses(
+            (status = 201, description = "予約の登録に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正な場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 422, description = "すでにこの蔵書を予約している場合。"),
+            (status = 500, description = "予約の登録に失敗した場合。")
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn hold_book(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let create_hold = CreateHold::new(book_id, user.id(), chrono::Utc::now());
+
+    registry
+        .checkout_repository()
+        .create_hold(create_hold)
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/holds",
+        responses(
+            (status = 204, description = "予約の取り消しに成功した場合。"),
+            (status = 400, description = "リクエストのパラメータが不正な場合。"),
+            (status = 404, description = "この蔵書を予約していない場合。"),
+            (status = 500, description = "予約の取り消しに失敗した場合。")
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn cancel_hold(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_hold = DeleteHold::new(book_id, user.id());
+
+    registry
+        .checkout_repository()
+        .delete_hold(delete_hold)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
 #[cfg_attr(
     debug_assertions,
     utoipa::path(put, path="/api/v1/books/{book_id}/checkouts/{checkout_id}/returned",
@@ -57,7 +172,8 @@ pub async fn checkout_book(
         ),
         params(
             ("book_id" = Uuid, Path, description = "蔵書ID"),
-            ("checkout_id" = Uuid, Path, description = "貸出ID")
+            ("checkout_id" = Uuid, Path, description = "貸出ID"),
+            ("locationId" = Option<Uuid>, Query, description = "返却した冊子を置いた場所の ID。指定した場合は冊子の場所をここに変更する")
         )
     )
 )]
@@ -70,9 +186,16 @@ pub async fn checkout_book(
 pub async fn return_book(
     user: AuthorizedUser,
     Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
+    Query(query): Query<ReturnBookQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<StatusCode> {
-    let update_returned = UpdateReturned::new(checkout_id, book_id, user.id(), chrono::Utc::now());
+    let update_returned = UpdateReturned::new(
+        checkout_id,
+        book_id,
+        user.id(),
+        chrono::Utc::now(),
+        query.location_id,
+    );
 
     registry
         .checkout_repository()
@@ -86,25 +209,55 @@ pub async fn return_book(
     utoipa::path(get, path="/api/v1/books/checkouts",
         responses(
             (status = 200, description = "蔵書の貸し出し履歴の一覧取得に成功した場合。", body = CheckoutsResponse),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーが `overdue` を指定した場合。"),
+        ),
+        params(
+            ("overdue" = Option<bool>, Query, description = "`true` の場合は返却期限を過ぎた貸出のみを返す。管理者のみ指定できる"),
+            ("limit" = Option<i64>, Query, description = "カーソルによるページネーションで一度に取得する件数。100 まで。`limit`・`after`・`before` のいずれも指定しない場合は全件を返す"),
+            ("after" = Option<String>, Query, description = "前回のレスポンスの `nextCursor` を指定すると次のページを返す"),
+            ("before" = Option<String>, Query, description = "前回のレスポンスの `prevCursor` を指定すると前のページを返す"),
         )
     )
 )]
 #[tracing::instrument(
-    skip(_user, registry),
+    skip(user, registry),
     fields(
-        user_id = %_user.user.id.to_string()
+        user_id = %user.user.id.to_string()
     )
 )]
 pub async fn show_checked_out_list(
-    _user: AuthorizedUser,
+    user: AuthorizedUser,
+    Query(filter): Query<CheckoutListQuery>,
+    Query(query): Query<CursorQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
-    registry
-        .checkout_repository()
-        .find_unreturned_all()
-        .await
-        .map(CheckoutsResponse::from)
-        .map(Json)
+    query.validate(&())?;
+    if filter.overdue && !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    let repository = registry.checkout_repository();
+    let now = chrono::Utc::now();
+    match (query.into_options()?, filter.overdue) {
+        (Some(options), true) => repository
+            .find_overdue_all_by_cursor(now, options)
+            .await
+            .map(CheckoutsResponse::from),
+        (Some(options), false) => repository
+            .find_unreturned_all_by_cursor(options)
+            .await
+            .map(CheckoutsResponse::from),
+        (None, true) => repository
+            .find_overdue_all(now)
+            .await
+            .map(CheckoutsResponse::from),
+        (None, false) => repository
+            .find_unreturned_all()
+            .await
+            .map(CheckoutsResponse::from),
+    }
+    .map(Json)
 }
 
 #[cfg_attr(
@@ -112,9 +265,13 @@ pub async fn show_checked_out_list(
     utoipa::path(get, path="/api/v1/books/{book_id}/checkout-history",
         responses(
             (status = 200, description = "蔵書の貸し出し履歴の一覧取得に成功した場合。", body = CheckoutsResponse),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
         ),
         params(
-            ("book_id" = Uuid, Path, description = "蔵書ID")
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "カーソルによるページネーションで一度に取得する件数。100 まで。`limit`・`after`・`before` のいずれも指定しない場合は全件を返す"),
+            ("after" = Option<String>, Query, description = "前回のレスポンスの `nextCursor` を指定すると次のページを返す"),
+            ("before" = Option<String>, Query, description = "前回のレスポンスの `prevCursor` を指定すると前のページを返す"),
         )
     )
 )]
@@ -127,12 +284,22 @@ pub async fn show_checked_out_list(
 pub async fn checkout_history(
     _user: AuthorizedUser,
     Path(book_id): Path<BookId>,
+    Query(query): Query<CursorQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
-    registry
-        .checkout_repository()
-        .find_history_by_book_id(book_id)
-        .await
-        .map(CheckoutsResponse::from)
-        .map(Json)
+    query.validate(&())?;
+
+    match query.into_options()? {
+        Some(options) => registry
+            .checkout_repository()
+            .find_history_by_book_id_by_cursor(book_id, options)
+            .await
+            .map(CheckoutsResponse::from),
+        None => registry
+            .checkout_repository()
+            .find_history_by_book_id(book_id)
+            .await
+            .map(CheckoutsResponse::from),
+    }
+    .map(Json)
 }
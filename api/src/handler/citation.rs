@@ -0,0 +1,125 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+use garde::Validate;
+use kernel::model::{book::Book, id::BookId};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::citation::{CitationFormat, CitationListQuery, CitationQuery},
+};
+
+/// 蔵書を引用の形式で書き出す
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/citation",
+        responses(
+            (status = 200, description = "蔵書を引用の形式で書き出す。", content_type = ["application/x-bibtex", "application/x-research-info-systems", "application/vnd.citationstyles.csl+json"]),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 406, description = "Accept ヘッダーで対応していない形式のみが指定された場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("format" = Option<String>, Query, description = "`bibtex`・`ris`・`cslJson` のいずれか。Accept ヘッダーより優先し、どちらもない場合は `bibtex`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_citation(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<CitationQuery>,
+    headers: HeaderMap,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    let format = negotiate(query.format, &headers)?;
+
+    let book = registry
+        .book_repository()
+        .find_by_id(book_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("specified book not found".into()))?;
+
+    citation_response(format, &[book])
+}
+
+/// 複数の蔵書をまとめて引用の形式で書き出す
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/citations",
+        responses(
+            (status = 200, description = "指定した蔵書を指定した順に引用の形式で書き出す。", content_type = ["application/x-bibtex", "application/x-research-info-systems", "application/vnd.citationstyles.csl+json"]),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "指定した蔵書のいずれかが存在しない場合。"),
+            (status = 406, description = "Accept ヘッダーで対応していない形式のみが指定された場合。"),
+        ),
+        params(
+            ("ids" = String, Query, description = "カンマ区切りの蔵書ID。1 〜 100 件で指定する"),
+            ("format" = Option<String>, Query, description = "`bibtex`・`ris`・`cslJson` のいずれか。Accept ヘッダーより優先し、どちらもない場合は `bibtex`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_citations(
+    _user: AuthorizedUser,
+    Query(query): Query<CitationListQuery>,
+    headers: HeaderMap,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    query.validate(&())?;
+    let format = negotiate(query.format, &headers)?;
+
+    let books = registry
+        .book_repository()
+        .find_all_by_ids(&query.ids)
+        .await?;
+    if query
+        .ids
+        .iter()
+        .any(|id| !books.iter().any(|book| book.id == *id))
+    {
+        return Err(AppError::EntityNotFound("specified book not found".into()));
+    }
+
+    citation_response(format, &books)
+}
+
+fn negotiate(format: Option<CitationFormat>, headers: &HeaderMap) -> AppResult<CitationFormat> {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    CitationFormat::negotiate(format, accept)
+}
+
+// Accept ヘッダーによって内容が変わるため、Vary ヘッダーを付ける
+fn citation_response(format: CitationFormat, books: &[Book]) -> AppResult<Response> {
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", format.file_name()),
+            ),
+            (header::VARY, header::ACCEPT.to_string()),
+        ],
+        format.render(books)?,
+    )
+        .into_response())
+}
@@ -0,0 +1,144 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use kernel::model::{
+    cover::{
+        event::{DeleteCover, UploadCover},
+        CoverImageFormat,
+    },
+    id::BookId,
+};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{extractor::AuthorizedUser, model::cover::CoverImageQuery};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/cover",
+        responses(
+            (status = 200, description = "表紙画像の取得に成功した場合。", content_type = "image/*"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 404, description = "蔵書が存在しないか、表紙画像が登録されていない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("size" = Option<String>, Query, description = "`original`（元画像）または `thumbnail`（サムネイル）。省略時は `original`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_cover(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<CoverImageQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    let cover = registry
+        .cover_image_repository()
+        .find(book_id, query.size.into())
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("cover image not found".into()))?;
+
+    Ok((
+        [(header::CONTENT_TYPE, cover.format.content_type())],
+        cover.data,
+    )
+        .into_response())
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/cover",
+        request_body(content = Vec<u8>, content_type = "image/png", description = "PNG・JPEG・WebP 形式の画像。Content-Type ヘッダーで形式を指定する"),
+        responses(
+            (status = 200, description = "表紙画像の登録に成功した場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 413, description = "画像のサイズが上限を超えていた場合。"),
+            (status = 415, description = "対応していない形式の画像だった場合。"),
+            (status = 422, description = "画像を読み込めなかった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, headers, body),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn upload_book_cover(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<StatusCode> {
+    let format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(CoverImageFormat::from_content_type)
+        .ok_or_else(|| {
+            AppError::UnsupportedMediaType(
+                "cover image must be image/png, image/jpeg or image/webp".into(),
+            )
+        })?;
+
+    let upload_cover = UploadCover {
+        book_id,
+        format,
+        data: body.to_vec(),
+        requested_by: user.actor(),
+    };
+    registry
+        .cover_image_repository()
+        .store(upload_cover)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/cover",
+        responses(
+            (status = 204, description = "表紙画像の削除に成功した場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しないか、表紙画像が登録されていない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn delete_book_cover(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_cover = DeleteCover {
+        book_id,
+        requested_by: user.actor(),
+    };
+    registry
+        .cover_image_repository()
+        .delete(delete_cover)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,280 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use garde::Validate;
+use kernel::model::{
+    cover::CoverImageFormat,
+    damage::{event::UploadDamagePhoto, DamageReport},
+    id::{BookId, CheckoutId, DamageReportId},
+};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::damage::{
+        CreateDamageReportRequest, CreateDamageReportRequestWithIds, DamageReportListQuery,
+        DamageReportResponse, DamageReportsResponse, ResolveDamageReportRequest,
+        ResolveDamageReportRequestWithIds,
+    },
+};
+
+// 報告は管理者と、報告したユーザー本人だけが参照できる
+async fn find_visible_report(
+    user: &AuthorizedUser,
+    registry: &AppRegistry,
+    damage_report_id: DamageReportId,
+) -> AppResult<DamageReport> {
+    let report = registry
+        .damage_report_repository()
+        .find_by_id(damage_report_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("damage report not found".into()))?;
+    if !user.is_admin() && report.reported_by.id != user.id() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    Ok(report)
+}
+
+/// 借りた冊子の破損を報告する
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/checkouts/{checkout_id}/damage-reports",
+        request_body = CreateDamageReportRequest,
+        responses(
+            (status = 200, description = "破損の報告に成功した場合。", body = DamageReportResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "貸出を受けたユーザー以外が報告しようとした場合。"),
+            (status = 404, description = "貸出が存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("checkout_id" = Uuid, Path, description = "貸出ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn report_damage(
+    user: AuthorizedUser,
+    Path((book_id, checkout_id)): Path<(BookId, CheckoutId)>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateDamageReportRequest>,
+) -> AppResult<Json<DamageReportResponse>> {
+    req.validate(&())?;
+
+    let create_report = CreateDamageReportRequestWithIds::new(book_id, checkout_id, user.id(), req);
+    let report = registry
+        .damage_report_repository()
+        .create(create_report.into())
+        .await?;
+
+    Ok(Json(report.into()))
+}
+
+/// 破損の報告の一覧を取得する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/damage-reports",
+        responses(
+            (status = 200, description = "破損の報告の一覧の取得に成功した場合。", body = DamageReportsResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+        ),
+        params(
+            ("status" = Option<String>, Query, description = "`open`（未対応）または `resolved`（対応済み）。省略時は `open`"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_damage_report_list(
+    user: AuthorizedUser,
+    Query(query): Query<DamageReportListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<DamageReportsResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .damage_report_repository()
+        .find_all(query.status.into())
+        .await
+        .map(DamageReportsResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/damage-reports/{damage_report_id}",
+        responses(
+            (status = 200, description = "破損の報告の取得に成功した場合。", body = DamageReportResponse),
+            (status = 403, description = "管理者でも報告したユーザーでもない場合。"),
+            (status = 404, description = "破損の報告が存在しない場合。"),
+        ),
+        params(
+            ("damage_report_id" = Uuid, Path, description = "破損の報告のID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_damage_report(
+    user: AuthorizedUser,
+    Path(damage_report_id): Path<DamageReportId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<DamageReportResponse>> {
+    find_visible_report(&user, &registry, damage_report_id)
+        .await
+        .map(DamageReportResponse::from)
+        .map(Json)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/damage-reports/{damage_report_id}/photo",
+        request_body(content = Vec<u8>, content_type = "image/jpeg", description = "PNG・JPEG・WebP 形式の画像。Content-Type ヘッダーで形式を指定する"),
+        responses(
+            (status = 200, description = "写真の添付に成功した場合。"),
+            (status = 403, description = "報告したユーザー以外が添付しようとした場合。"),
+            (status = 404, description = "破損の報告が存在しない場合。"),
+            (status = 413, description = "画像のサイズが上限を超えていた場合。"),
+            (status = 415, description = "対応していない形式の画像だった場合。"),
+            (status = 422, description = "報告が対応済みの場合。"),
+        ),
+        params(
+            ("damage_report_id" = Uuid, Path, description = "破損の報告のID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, headers, body),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn upload_damage_photo(
+    user: AuthorizedUser,
+    Path(damage_report_id): Path<DamageReportId>,
+    State(registry): State<AppRegistry>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<StatusCode> {
+    let format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(CoverImageFormat::from_content_type)
+        .ok_or_else(|| {
+            AppError::UnsupportedMediaType(
+                "damage photo must be image/png, image/jpeg or image/webp".into(),
+            )
+        })?;
+
+    let upload_photo = UploadDamagePhoto {
+        damage_report_id,
+        format,
+        data: body.to_vec(),
+        requested_user: user.id(),
+    };
+    registry
+        .damage_report_repository()
+        .store_photo(upload_photo)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/damage-reports/{damage_report_id}/photo",
+        responses(
+            (status = 200, description = "写真の取得に成功した場合。", content_type = "image/*"),
+            (status = 403, description = "管理者でも報告したユーザーでもない場合。"),
+            (status = 404, description = "破損の報告が存在しないか、写真が添付されていない場合。"),
+        ),
+        params(
+            ("damage_report_id" = Uuid, Path, description = "破損の報告のID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_damage_photo(
+    user: AuthorizedUser,
+    Path(damage_report_id): Path<DamageReportId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Response> {
+    find_visible_report(&user, &registry, damage_report_id).await?;
+
+    let photo = registry
+        .damage_report_repository()
+        .find_photo(damage_report_id)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound("damage photo not found".into()))?;
+
+    Ok((
+        [(header::CONTENT_TYPE, photo.format.content_type())],
+        photo.data,
+    )
+        .into_response())
+}
+
+/// 破損の報告を対応済みにする（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/damage-reports/{damage_report_id}/resolve",
+        request_body = ResolveDamageReportRequest,
+        responses(
+            (status = 200, description = "報告を対応済みにした場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "破損の報告が存在しない場合。"),
+            (status = 422, description = "報告がすでに対応済みの場合。"),
+        ),
+        params(
+            ("damage_report_id" = Uuid, Path, description = "破損の報告のID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn resolve_damage_report(
+    user: AuthorizedUser,
+    Path(damage_report_id): Path<DamageReportId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ResolveDamageReportRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    let resolve = ResolveDamageReportRequestWithIds::new(damage_report_id, user.id(), req);
+    registry
+        .damage_report_repository()
+        .resolve(resolve.into())
+        .await
+        .map(|_| StatusCode::OK)
+}
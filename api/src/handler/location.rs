@@ -0,0 +1,154 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use garde::Validate;
+use kernel::model::{id::LocationId, location::event::DeleteLocation};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::location::{
+        CreateLocationRequest, LocationResponse, LocationsResponse, UpdateLocationRequest,
+        UpdateLocationRequestWithId,
+    },
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/locations",
+        responses(
+            (status = 200, description = "場所の一覧の取得に成功した場合。", body = LocationsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+        )
+    )
+)]
+pub async fn list_locations(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<LocationsResponse>> {
+    registry
+        .location_repository()
+        .find_all()
+        .await
+        .map(LocationsResponse::from)
+        .map(Json)
+}
+
+/// 場所を追加する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/locations",
+        request_body = CreateLocationRequest,
+        responses(
+            (status = 200, description = "場所の追加に成功した場合。", body = LocationResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 409, description = "同じ親の下に同じ名前の場所がすでに登録されていた場合。"),
+            (status = 422, description = "親の場所が存在しないか、階層の順序に合わない場合。"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn register_location(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateLocationRequest>,
+) -> AppResult<Json<LocationResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    req.validate(&())?;
+
+    let location = registry.location_repository().create(req.into()).await?;
+
+    Ok(Json(location.into()))
+}
+
+/// 場所の名前を変更する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/locations/{location_id}",
+        request_body = UpdateLocationRequest,
+        responses(
+            (status = 200, description = "場所の更新に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "更新対象の場所が存在しなかった場合。"),
+            (status = 409, description = "同じ親の下に同じ名前の場所がすでに登録されていた場合。"),
+        ),
+        params(
+            ("location_id" = Uuid, Path, description = "場所ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn update_location(
+    user: AuthorizedUser,
+    Path(location_id): Path<LocationId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateLocationRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    req.validate(&())?;
+
+    registry
+        .location_repository()
+        .update(UpdateLocationRequestWithId::new(location_id, req).into())
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+/// 場所を削除する（Admin only）
+/// 配下に場所がある場合は削除できない。置かれていた冊子の場所は未設定になる
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/locations/{location_id}",
+        responses(
+            (status = 200, description = "場所の削除に成功した場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "削除対象の場所が存在しなかった場合。"),
+            (status = 422, description = "配下に場所が登録されている場合。"),
+        ),
+        params(
+            ("location_id" = Uuid, Path, description = "場所ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn delete_location(
+    user: AuthorizedUser,
+    Path(location_id): Path<LocationId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .location_repository()
+        .delete(DeleteLocation { location_id })
+        .await?;
+
+    Ok(StatusCode::OK)
+}
@@ -1,5 +1,16 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod citation;
+pub mod cover;
+pub mod damage;
 pub mod health;
+pub mod location;
+pub mod recommendation;
+pub mod review;
+pub mod revision;
+pub mod series;
+pub mod tag;
+pub mod transfer;
 pub mod user;
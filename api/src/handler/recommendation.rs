@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use garde::Validate;
+use kernel::model::id::BookId;
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::recommendation::{RecommendationQuery, RecommendedBooksResponse},
+};
+
+/// 同じユーザーに借りられている蔵書を取得する
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/related",
+        responses(
+            (status = 200, description = "一緒に借りられている蔵書の取得に成功した場合。", body = RecommendedBooksResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "取得する件数。1 〜 50 で指定し、省略時は 10"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_related_books(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<RecommendationQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<RecommendedBooksResponse>> {
+    query.validate(&())?;
+
+    registry
+        .recommendation_repository()
+        .find_related(book_id, query.limit)
+        .await
+        .map(RecommendedBooksResponse::from)
+        .map(Json)
+}
+
+/// 借りたことのある蔵書をもとに、まだ借りていない蔵書をおすすめする
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/users/me/recommendations",
+        responses(
+            (status = 200, description = "おすすめの蔵書の取得に成功した場合。", body = RecommendedBooksResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+        ),
+        params(
+            ("limit" = Option<i64>, Query, description = "取得する件数。1 〜 50 で指定し、省略時は 10"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_recommendations(
+    user: AuthorizedUser,
+    Query(query): Query<RecommendationQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<RecommendedBooksResponse>> {
+    query.validate(&())?;
+
+    registry
+        .recommendation_repository()
+        .find_for_user(user.id(), query.limit)
+        .await
+        .map(RecommendedBooksResponse::from)
+        .map(Json)
+}
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use garde::Validate;
+use kernel::model::{
+    id::{BookId, ReviewId},
+    review::event::DeleteReview,
+};
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::review::{
+        CreateReviewRequestWithIds, PaginatedReviewResponse, ReviewListQuery, ReviewRequest,
+        ReviewResponse, UpdateReviewRequestWithIds,
+    },
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/reviews",
+        responses(
+            (status = 200, description = "レビューの一覧の取得に成功した場合。", body = PaginatedReviewResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("limit" = Option<i64>, Query, description = "取得する件数。100 まで。省略時は 20"),
+            ("offset" = Option<i64>, Query, description = "取得を始める位置。省略時は 0"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_reviews(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    Query(query): Query<ReviewListQuery>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<PaginatedReviewResponse>> {
+    query.validate(&())?;
+
+    registry
+        .review_repository()
+        .find_by_book_id(book_id, query.into())
+        .await
+        .map(PaginatedReviewResponse::from)
+        .map(Json)
+}
+
+/// 借りたことのある蔵書のレビューを書く
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/reviews",
+        request_body = ReviewRequest,
+        responses(
+            (status = 200, description = "レビューの登録に成功した場合。", body = ReviewResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "蔵書を借りたことがない場合。"),
+            (status = 404, description = "蔵書が存在しない場合。"),
+            (status = 409, description = "蔵書のレビューをすでに書いていた場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn register_review(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ReviewRequest>,
+) -> AppResult<Json<ReviewResponse>> {
+    req.validate(&())?;
+
+    let create_review = CreateReviewRequestWithIds::new(book_id, user.id(), req);
+    let review = registry
+        .review_repository()
+        .create(create_review.into())
+        .await?;
+
+    Ok(Json(review.into()))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/reviews/{review_id}",
+        request_body = ReviewRequest,
+        responses(
+            (status = 200, description = "レビューの更新に成功した場合。", body = ReviewResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "自分以外が書いたレビューを更新しようとした場合。"),
+            (status = 404, description = "レビューが存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("review_id" = Uuid, Path, description = "レビューID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn update_review(
+    user: AuthorizedUser,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ReviewRequest>,
+) -> AppResult<Json<ReviewResponse>> {
+    req.validate(&())?;
+
+    let update_review = UpdateReviewRequestWithIds::new(book_id, review_id, user.id(), req);
+    let review = registry
+        .review_repository()
+        .update(update_review.into())
+        .await?;
+
+    Ok(Json(review.into()))
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/reviews/{review_id}",
+        responses(
+            (status = 204, description = "レビューの削除に成功した場合。"),
+            (status = 403, description = "自分以外が書いたレビューを削除しようとした場合。"),
+            (status = 404, description = "レビューが存在しない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("review_id" = Uuid, Path, description = "レビューID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn delete_review(
+    user: AuthorizedUser,
+    Path((book_id, review_id)): Path<(BookId, ReviewId)>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let delete_review = DeleteReview {
+        book_id,
+        review_id,
+        requested_user: user.id(),
+    };
+    registry
+        .review_repository()
+        .delete(delete_review)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use kernel::model::{book::event::RevertBook, id::BookId};
+use registry::AppRegistry;
+use shared::error::AppResult;
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::{etag::parse_if_match, revision::BookRevisionsResponse},
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/revisions",
+        responses(
+            (status = 200, description = "蔵書の版の一覧の取得に成功した場合。", body = BookRevisionsResponse),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 404, description = "蔵書が存在しなかった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_revisions(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookRevisionsResponse>> {
+    registry
+        .book_repository()
+        .find_revisions(book_id)
+        .await
+        .map(BookRevisionsResponse::from)
+        .map(Json)
+}
+
+/// 蔵書の書誌情報を指定した版の内容に戻す（所有者、またはすべての蔵書を管理できるロール）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/revisions/{revision}/revert",
+        responses(
+            (status = 200, description = "指定した版の内容に戻せた場合。"),
+            (status = 400, description = "リクエストのパラメータが不正だった場合。"),
+            (status = 403, description = "蔵書の所有者でも管理者でもないユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書または指定した版が存在しなかった場合。"),
+            (status = 409, description = "戻す版の ISBN の蔵書がすでに登録されていた場合。"),
+            (status = 412, description = "If-Match で指定された ETag と蔵書の ETag が一致しなかった場合。"),
+            (status = 422, description = "戻す版の ISBN が正しい ISBN ではなかった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID"),
+            ("revision" = i32, Path, description = "戻す版の番号"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, headers),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn revert_book(
+    user: AuthorizedUser,
+    Path((book_id, revision)): Path<(BookId, i32)>,
+    headers: HeaderMap,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let revert_book = RevertBook {
+        book_id,
+        revision,
+        requested_by: user.actor(),
+        expected_updated_at: parse_if_match(&headers),
+    };
+    registry
+        .book_repository()
+        .revert(revert_book)
+        .await
+        .map(|_| StatusCode::OK)
+}
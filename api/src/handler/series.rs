@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use kernel::model::id::SeriesId;
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{extractor::AuthorizedUser, model::series::SeriesResponse};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/series/{series_id}",
+        responses(
+            (status = 200, description = "シリーズの取得に成功した場合。各巻を巻数の順に、貸出状況とともに返す。", body = SeriesResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+            (status = 404, description = "指定されたシリーズが存在しない場合。"),
+        ),
+        params(
+            ("series_id" = SeriesId, Path, description = "シリーズ ID"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_series(
+    user: AuthorizedUser,
+    Path(series_id): Path<SeriesId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<SeriesResponse>> {
+    registry
+        .series_repository()
+        .find_by_id(series_id)
+        .await?
+        .map(SeriesResponse::from)
+        .map(Json)
+        .ok_or_else(|| AppError::EntityNotFound("specified series not found".into()))
+}
@@ -0,0 +1,151 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use garde::Validate;
+use kernel::model::{id::TagId, tag::event::DeleteTag};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::tag::{
+        CreateTagRequest, TagResponse, TagsResponse, UpdateTagRequest, UpdateTagRequestWithId,
+    },
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/tags",
+        responses(
+            (status = 200, description = "タグの一覧の取得に成功した場合。", body = TagsResponse),
+            (status = 401, description = "認証されていないユーザーがアクセスした場合。"),
+        )
+    )
+)]
+pub async fn list_tags(
+    _user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<TagsResponse>> {
+    registry
+        .tag_repository()
+        .find_all()
+        .await
+        .map(TagsResponse::from)
+        .map(Json)
+}
+
+/// タグを追加する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/tags",
+        request_body = CreateTagRequest,
+        responses(
+            (status = 200, description = "タグの追加に成功した場合。", body = TagResponse),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 409, description = "同じ名前のタグがすでに登録されていた場合。"),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn register_tag(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<CreateTagRequest>,
+) -> AppResult<Json<TagResponse>> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    req.validate(&())?;
+
+    let tag = registry.tag_repository().create(req.into()).await?;
+
+    Ok(Json(tag.into()))
+}
+
+/// タグの名前を変更する（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/tags/{tag_id}",
+        request_body = UpdateTagRequest,
+        responses(
+            (status = 200, description = "タグの更新に成功した場合。"),
+            (status = 400, description = "リクエストのパラメータに不備があった場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "更新対象のタグが存在しなかった場合。"),
+            (status = 409, description = "同じ名前のタグがすでに登録されていた場合。"),
+        ),
+        params(
+            ("tag_id" = Uuid, Path, description = "タグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry, req),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn update_tag(
+    user: AuthorizedUser,
+    Path(tag_id): Path<TagId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<UpdateTagRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+    req.validate(&())?;
+
+    registry
+        .tag_repository()
+        .update(UpdateTagRequestWithId::new(tag_id, req).into())
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+/// タグを削除する（Admin only）
+/// 蔵書に付けられていたタグも外れる
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/tags/{tag_id}",
+        responses(
+            (status = 200, description = "タグの削除に成功した場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "削除対象のタグが存在しなかった場合。"),
+        ),
+        params(
+            ("tag_id" = Uuid, Path, description = "タグID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string(),
+    )
+)]
+pub async fn delete_tag(
+    user: AuthorizedUser,
+    Path(tag_id): Path<TagId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    registry
+        .tag_repository()
+        .delete(DeleteTag { tag_id })
+        .await?;
+
+    Ok(StatusCode::OK)
+}
@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use kernel::model::{
+    id::BookId,
+    transfer::event::{AcceptTransfer, CancelTransfer, ProposeTransfer, ReassignOwner},
+};
+use registry::AppRegistry;
+use shared::error::{AppError, AppResult};
+
+use crate::{
+    extractor::AuthorizedUser,
+    model::transfer::{
+        BookTransferResponse, BookTransfersResponse, ProposeTransferRequest, ReassignOwnerRequest,
+    },
+};
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/books/{book_id}/transfer",
+        responses(
+            (status = 200, description = "承認待ちの譲渡の取得に成功した場合。", body = BookTransferResponse),
+            (status = 404, description = "蔵書が存在しないか、承認待ちの譲渡がない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(_user, registry),
+    fields(
+        user_id = %_user.user.id.to_string()
+    )
+)]
+pub async fn show_book_transfer(
+    _user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookTransferResponse>> {
+    registry
+        .book_transfer_repository()
+        .find_by_book_id(book_id)
+        .await?
+        .map(BookTransferResponse::from)
+        .map(Json)
+        .ok_or_else(|| AppError::EntityNotFound("specified transfer not found".into()))
+}
+
+/// 蔵書の所有権の譲渡を申し出る（所有者のみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/transfer",
+        request_body = ProposeTransferRequest,
+        responses(
+            (status = 201, description = "譲渡の申し出に成功した場合。"),
+            (status = 403, description = "蔵書の所有者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しなかった場合。"),
+            (status = 422, description = "譲渡先のユーザーが存在しないか、所有者自身だった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn propose_book_transfer(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ProposeTransferRequest>,
+) -> AppResult<StatusCode> {
+    let propose_transfer = ProposeTransfer {
+        book_id,
+        to_user: req.to_user_id,
+        requested_user: user.id(),
+    };
+    registry
+        .book_transfer_repository()
+        .propose(propose_transfer)
+        .await
+        .map(|_| StatusCode::CREATED)
+}
+
+/// 蔵書の所有権の譲渡を承認し、所有者になる（譲渡先のユーザーのみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(post, path="/api/v1/books/{book_id}/transfer/accept",
+        responses(
+            (status = 200, description = "譲渡の承認に成功した場合。"),
+            (status = 403, description = "譲渡先以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しないか、承認待ちの譲渡がない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn accept_book_transfer(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let accept_transfer = AcceptTransfer {
+        book_id,
+        requested_user: user.id(),
+    };
+    registry
+        .book_transfer_repository()
+        .accept(accept_transfer)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// 蔵書の所有権の譲渡を取り下げる、または辞退する（所有者または譲渡先のユーザーのみ）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(delete, path="/api/v1/books/{book_id}/transfer",
+        responses(
+            (status = 204, description = "譲渡の取り下げ・辞退に成功した場合。"),
+            (status = 404, description = "自身が関わる承認待ちの譲渡がない場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn cancel_book_transfer(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+) -> AppResult<StatusCode> {
+    let cancel_transfer = CancelTransfer {
+        book_id,
+        requested_user: user.id(),
+    };
+    registry
+        .book_transfer_repository()
+        .cancel(cancel_transfer)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+/// 蔵書の所有者を付け替える（Admin only）
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(put, path="/api/v1/books/{book_id}/owner",
+        request_body = ReassignOwnerRequest,
+        responses(
+            (status = 200, description = "所有者の付け替えに成功した場合。"),
+            (status = 403, description = "管理者以外のユーザーがアクセスした場合。"),
+            (status = 404, description = "蔵書が存在しなかった場合。"),
+            (status = 422, description = "指定したユーザーが存在しなかった場合。"),
+        ),
+        params(
+            ("book_id" = Uuid, Path, description = "蔵書ID")
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn reassign_book_owner(
+    user: AuthorizedUser,
+    Path(book_id): Path<BookId>,
+    State(registry): State<AppRegistry>,
+    Json(req): Json<ReassignOwnerRequest>,
+) -> AppResult<StatusCode> {
+    if !user.is_admin() {
+        return Err(AppError::ForbiddenOperation);
+    }
+
+    let reassign_owner = ReassignOwner {
+        book_id,
+        to_user: req.user_id,
+    };
+    registry
+        .book_transfer_repository()
+        .reassign(reassign_owner)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+#[cfg_attr(
+    debug_assertions,
+    utoipa::path(get, path="/api/v1/users/me/transfers",
+        responses(
+            (status = 200, description = "自身が譲渡先になっている承認待ちの譲渡の一覧の取得に成功した場合。", body = BookTransfersResponse),
+        )
+    )
+)]
+#[tracing::instrument(
+    skip(user, registry),
+    fields(
+        user_id = %user.user.id.to_string()
+    )
+)]
+pub async fn show_incoming_transfers(
+    user: AuthorizedUser,
+    State(registry): State<AppRegistry>,
+) -> AppResult<Json<BookTransfersResponse>> {
+    registry
+        .book_transfer_repository()
+        .find_incoming(user.id())
+        .await
+        .map(BookTransfersResponse::from)
+        .map(Json)
+}
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -12,6 +12,7 @@ use crate::{
     extractor::AuthorizedUser,
     model::{
         checkout::CheckoutsResponse,
+        list::CursorQuery,
         user::{
             CreateUserRequest, UpdateUserPasswordRequest, UpdateUserPasswordWithUserId,
             UpdateUserRoleRequest, UpdateUserRoleRequestWithUserid, UserResponse, UsersResponse,
@@ -49,26 +50,40 @@ pub async fn register_user(
     utoipa::path(get, path="/api/v1/users",
         responses(
             (status = 200, description = "ユーザーの一覧を取得できた場合。"),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
             (status = 500, description = "サーバーサイドエラーが発生した場合。")
+        ),
+        params(
+            ("limit" = Option<i64>, Query, description = "カーソルによるページネーションで一度に取得する件数。100 まで。`limit`・`after`・`before` のいずれも指定しない場合は全件を返す"),
+            ("after" = Option<String>, Query, description = "前回のレスポンスの `nextCursor` を指定すると次のページを返す"),
+            ("before" = Option<String>, Query, description = "前回のレスポンスの `prevCursor` を指定すると前のページを返す"),
         )
     )
 )]
 pub async fn list_users(
     _user: AuthorizedUser,
+    Query(query): Query<CursorQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<UsersResponse>> {
-    let items = registry
-        .user_repository()
-        .find_all()
-        .await?
-        .into_iter()
-        .map(UserResponse::from)
-        .collect();
-
-    Ok(Json(UsersResponse { items }))
+    query.validate(&())?;
+
+    match query.into_options()? {
+        Some(options) => registry
+            .user_repository()
+            .find_all_by_cursor(options)
+            .await
+            .map(UsersResponse::from),
+        None => registry
+            .user_repository()
+            .find_all()
+            .await
+            .map(UsersResponse::from),
+    }
+    .map(Json)
 }
 
 /// ユーザを削除する（Admin only）
+/// 蔵書（削除済みのものを含む）を所有しているユーザは削除できず、422 を返す
 #[tracing::instrument(
     skip(user, registry),
     fields(
@@ -166,7 +181,13 @@ pub async fn change_password(
     utoipa::path(get, path="/api/v1/users/me/checkouts",
         responses(
             (status = 200, description = "貸し出し中の書籍を取得できた場合。"),
+            (status = 400, description = "指定されたクエリの値に不備があった場合。"),
             (status = 500, description = "サーバーサイドエラーが発生した場合。")
+        ),
+        params(
+            ("limit" = Option<i64>, Query, description = "カーソルによるページネーションで一度に取得する件数。100 まで。`limit`・`after`・`before` のいずれも指定しない場合は全件を返す"),
+            ("after" = Option<String>, Query, description = "前回のレスポンスの `nextCursor` を指定すると次のページを返す"),
+            ("before" = Option<String>, Query, description = "前回のレスポンスの `prevCursor` を指定すると前のページを返す"),
         )
     )
 )]
@@ -178,12 +199,22 @@ pub async fn change_password(
 )]
 pub async fn get_checkouts(
     user: AuthorizedUser,
+    Query(query): Query<CursorQuery>,
     State(registry): State<AppRegistry>,
 ) -> AppResult<Json<CheckoutsResponse>> {
-    registry
-        .checkout_repository()
-        .find_unreturned_by_user_id(user.id())
-        .await
-        .map(CheckoutsResponse::from)
-        .map(Json)
+    query.validate(&())?;
+
+    match query.into_options()? {
+        Some(options) => registry
+            .checkout_repository()
+            .find_unreturned_by_user_id_by_cursor(user.id(), options)
+            .await
+            .map(CheckoutsResponse::from),
+        None => registry
+            .checkout_repository()
+            .find_unreturned_by_user_id(user.id())
+            .await
+            .map(CheckoutsResponse::from),
+    }
+    .map(Json)
 }
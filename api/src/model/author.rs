@@ -0,0 +1,160 @@
+use garde::Validate;
+use kernel::model::{
+    author::{Author, AuthorCredit, BookAuthor},
+    book::BookListOptions,
+    id::AuthorId,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::{book::default_limit, list::MAX_LIMIT};
+
+// 蔵書に対する著者の役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum AuthorRole {
+    #[default]
+    Author,
+    Editor,
+    Translator,
+}
+
+impl From<AuthorRole> for kernel::model::author::AuthorRole {
+    fn from(value: AuthorRole) -> Self {
+        match value {
+            AuthorRole::Author => Self::Author,
+            AuthorRole::Editor => Self::Editor,
+            AuthorRole::Translator => Self::Translator,
+        }
+    }
+}
+
+impl From<kernel::model::author::AuthorRole> for AuthorRole {
+    fn from(value: kernel::model::author::AuthorRole) -> Self {
+        match value {
+            kernel::model::author::AuthorRole::Author => Self::Author,
+            kernel::model::author::AuthorRole::Editor => Self::Editor,
+            kernel::model::author::AuthorRole::Translator => Self::Translator,
+        }
+    }
+}
+
+// 蔵書の登録・更新時に指定する著者
+// 名前で指定し、まだ登録されていない名前の場合は著者として登録する
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorCreditRequest {
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+    // 省略した場合は著者として扱う
+    #[garde(skip)]
+    #[serde(default)]
+    pub role: AuthorRole,
+}
+
+impl From<AuthorCreditRequest> for AuthorCredit {
+    fn from(value: AuthorCreditRequest) -> Self {
+        let AuthorCreditRequest { name, role } = value;
+        Self {
+            name: name.trim().to_string(),
+            role: role.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookAuthorResponse {
+    pub id: AuthorId,
+    pub name: String,
+    pub role: AuthorRole,
+}
+
+impl From<BookAuthor> for BookAuthorResponse {
+    fn from(value: BookAuthor) -> Self {
+        let BookAuthor { id, name, role } = value;
+        Self {
+            id,
+            name,
+            role: role.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorResponse {
+    pub id: AuthorId,
+    pub name: String,
+}
+
+impl From<Author> for AuthorResponse {
+    fn from(value: Author) -> Self {
+        let Author { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorsResponse {
+    pub items: Vec<AuthorResponse>,
+}
+
+impl From<Vec<Author>> for AuthorsResponse {
+    fn from(value: Vec<Author>) -> Self {
+        Self {
+            items: value.into_iter().map(AuthorResponse::from).collect(),
+        }
+    }
+}
+
+// 著者名の入力補完のクエリ
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuthorSuggestQuery {
+    #[garde(length(min = 1, max = 255))]
+    pub keyword: String,
+    #[garde(range(min = 1, max = 50))]
+    #[serde(default = "default_suggest_limit")]
+    pub limit: i64,
+}
+
+const fn default_suggest_limit() -> i64 {
+    10
+}
+
+// 著者の蔵書一覧のクエリ。role を指定した場合はその役割で関わる蔵書に絞り込む
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuthorBookListQuery {
+    #[garde(range(min = 0, max = MAX_LIMIT))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+    #[garde(skip)]
+    pub role: Option<AuthorRole>,
+}
+
+impl AuthorBookListQuery {
+    pub fn into_options(self, author_id: AuthorId) -> BookListOptions {
+        let AuthorBookListQuery {
+            limit,
+            offset,
+            role,
+        } = self;
+        BookListOptions {
+            limit,
+            offset,
+            author_id: Some(author_id),
+            author_role: role.map(Into::into),
+            ..Default::default()
+        }
+    }
+}
@@ -2,47 +2,132 @@ use chrono::{DateTime, Utc};
 use derive_new::new;
 use garde::Validate;
 use kernel::model::{
+    author::AuthorCredit,
     book::{
-        event::{CreateBook, UpdateBook},
-        Book, BookListOptions, Checkout,
+        duplicate::{DuplicateCandidate, DuplicateReason},
+        event::{
+            CreateBook, CreateBookCopy, UpdateBook, UpdateBookCopyCondition, UpdateBookCopyLocation,
+        },
+        metadata::BookMetadata,
+        Book, BookCopy, BookListOptions, Checkout, MAX_PUBLISHED_YEAR, MIN_PUBLISHED_YEAR,
     },
-    id::{BookId, CheckoutId, UserId},
-    list::PaginatedList,
+    id::{BookId, CheckoutId, CopyId, LocationId, TagId, UserId},
+    isbn::Isbn,
+    list::{CursorPaginatedList, PageCursor, PaginatedList},
+    user::Actor,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use shared::error::{AppError, AppResult};
 #[cfg(debug_assertions)]
 use utoipa::ToSchema;
 
-use super::user::{BookOwner, CheckoutUser};
+use super::{
+    author::{AuthorCreditRequest, BookAuthorResponse},
+    cover::{cover_thumbnail_url, cover_url},
+    list::{parse_page_cursor, MAX_LIMIT},
+    location::LocationResponse,
+    series::{BookSeriesResponse, SeriesMembershipRequest},
+    tag::TagResponse,
+    user::{BookOwner, CheckoutUser},
+};
 
 #[derive(Debug, Deserialize, Validate)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBookRequest {
+    // autofill を指定した場合は省略でき、ISBN から引いた書誌情報で埋める
     #[garde(length(min = 1))]
+    #[serde(default)]
     pub title: String,
-    #[garde(length(min = 1))]
-    pub author: String,
-    #[garde(length(min = 1))]
+    // 指定した順に並べる。1 人以上指定する
+    #[garde(length(min = 1), dive)]
+    #[serde(default)]
+    pub authors: Vec<AuthorCreditRequest>,
+    // ISBN-10・ISBN-13 のどちらでも受け付け、ISBN-13 に正規化して登録する
+    #[garde(custom(validate_isbn))]
     pub isbn: String,
     #[garde(skip)]
+    #[serde(default)]
     pub description: String,
+    // 出版社と出版年。不明な場合は省略する
+    #[garde(length(min = 1, max = 255))]
+    pub publisher: Option<String>,
+    #[garde(range(min = MIN_PUBLISHED_YEAR, max = MAX_PUBLISHED_YEAR))]
+    pub published_year: Option<i32>,
+    // 蔵書に付けるタグの ID
+    #[garde(skip)]
+    #[serde(default)]
+    pub tags: Vec<TagId>,
+    // 蔵書が属するシリーズと巻数。シリーズに属さない場合は省略する
+    #[garde(dive)]
+    pub series: Option<SeriesMembershipRequest>,
+    // true の場合、空の項目を ISBN から引いた書誌情報で埋める
+    #[garde(skip)]
+    #[serde(default)]
+    pub autofill: bool,
+    // true の場合、タイトルが似ていて共通する著者がいる蔵書があっても登録する
+    // ISBN が同じ蔵書がある場合は登録できない
+    #[garde(skip)]
+    #[serde(default)]
+    pub force: bool,
 }
 
-impl From<CreateBookRequest> for CreateBook {
-    fn from(value: CreateBookRequest) -> Self {
+impl CreateBookRequest {
+    // 入力されていない項目だけを書誌情報で埋める
+    pub fn fill_missing(&mut self, metadata: BookMetadata) {
+        let fill = |field: &mut String, value: Option<String>| {
+            if let (true, Some(value)) = (field.trim().is_empty(), value) {
+                *field = value;
+            }
+        };
+        fill(&mut self.title, metadata.title);
+        fill(&mut self.description, metadata.description);
+        if self.authors.is_empty() {
+            self.authors = metadata
+                .authors
+                .into_iter()
+                .map(|name| AuthorCreditRequest {
+                    name,
+                    role: Default::default(),
+                })
+                .collect();
+        }
+    }
+}
+
+// ISBN の形式が正しいかどうかを、どの項目のエラーかが分かるように検証する
+fn validate_isbn(value: &str, _: &()) -> garde::Result {
+    value
+        .parse::<Isbn>()
+        .map(|_| ())
+        .map_err(|e| garde::Error::new(e.to_string()))
+}
+
+impl TryFrom<CreateBookRequest> for CreateBook {
+    type Error = AppError;
+    fn try_from(value: CreateBookRequest) -> Result<Self, Self::Error> {
         let CreateBookRequest {
             title,
-            author,
+            authors,
             isbn,
             description,
+            publisher,
+            published_year,
+            tags,
+            series,
+            autofill: _,
+            force: _,
         } = value;
-        Self {
+        Ok(Self {
             title,
-            author,
-            isbn,
+            authors: authors.into_iter().map(AuthorCredit::from).collect(),
+            isbn: isbn.parse()?,
             description,
-        }
+            publisher,
+            published_year,
+            tags,
+            series: series.map(Into::into),
+        })
     }
 }
 
@@ -52,39 +137,69 @@ impl From<CreateBookRequest> for CreateBook {
 pub struct UpdateBookRequest {
     #[garde(length(min = 1))]
     pub title: String,
-    #[garde(length(min = 1))]
-    pub author: String,
-    #[garde(length(min = 1))]
+    // 指定した順に並べる。1 人以上指定する
+    #[garde(length(min = 1), dive)]
+    pub authors: Vec<AuthorCreditRequest>,
+    // ISBN-10・ISBN-13 のどちらでも受け付け、ISBN-13 に正規化して登録する
+    #[garde(custom(validate_isbn))]
     pub isbn: String,
     #[garde(skip)]
     pub description: String,
+    // 指定した場合は出版社・出版年をこの内容にし、null の場合は未設定に戻す。省略した場合は変更しない
+    #[garde(inner(length(min = 1, max = 255)))]
+    #[serde(default, deserialize_with = "double_option")]
+    #[cfg_attr(debug_assertions, schema(value_type = Option<String>, nullable))]
+    pub publisher: Option<Option<String>>,
+    #[garde(inner(range(min = MIN_PUBLISHED_YEAR, max = MAX_PUBLISHED_YEAR)))]
+    #[serde(default, deserialize_with = "double_option")]
+    #[cfg_attr(debug_assertions, schema(value_type = Option<i32>, nullable))]
+    pub published_year: Option<Option<i32>>,
+    // 指定した場合は蔵書に付けるタグをこの内容に置き換える。省略した場合は変更しない
+    #[garde(skip)]
+    pub tags: Option<Vec<TagId>>,
+    // 指定した場合は蔵書が属するシリーズと巻数をこの内容にし、null の場合はシリーズから外す
+    // 省略した場合は変更しない
+    #[garde(dive)]
+    #[serde(default, deserialize_with = "double_option")]
+    #[cfg_attr(debug_assertions, schema(value_type = Option<SeriesMembershipRequest>, nullable))]
+    pub series: Option<Option<SeriesMembershipRequest>>,
 }
 
 // パスパラメータからの BookId,
 // リクエスト時に AuthorizedUser から取り出す UserId,
 // UpdateBookRequest の 3 つの値のセットを UpdateBook 型に変換するための一時的な型
 #[derive(new)]
-pub struct UpdateBookRequestWithIds(BookId, UserId, UpdateBookRequest);
-impl From<UpdateBookRequestWithIds> for UpdateBook {
-    fn from(value: UpdateBookRequestWithIds) -> Self {
+pub struct UpdateBookRequestWithIds(BookId, Actor, UpdateBookRequest);
+impl TryFrom<UpdateBookRequestWithIds> for UpdateBook {
+    type Error = AppError;
+    fn try_from(value: UpdateBookRequestWithIds) -> Result<Self, Self::Error> {
         let UpdateBookRequestWithIds(
             book_id,
-            user_id,
+            actor,
             UpdateBookRequest {
                 title,
-                author,
+                authors,
                 isbn,
                 description,
+                publisher,
+                published_year,
+                tags,
+                series,
             },
         ) = value;
-        UpdateBook {
+        Ok(UpdateBook {
             book_id,
             title,
-            author,
-            isbn,
+            authors: authors.into_iter().map(AuthorCredit::from).collect(),
+            isbn: isbn.parse()?,
             description,
-            requested_user: user_id,
-        }
+            publisher,
+            published_year,
+            tags,
+            series: series.map(|s| s.map(Into::into)),
+            requested_by: actor,
+            expected_updated_at: None,
+        })
     }
 }
 
@@ -92,23 +207,188 @@ impl From<UpdateBookRequestWithIds> for UpdateBook {
 // handler 側のメソッドで、クエリのデータを取得できる
 #[derive(Debug, Deserialize, Validate)]
 pub struct BookListQuery {
-    #[garde(range(min = 0))]
+    #[garde(range(min = 0, max = MAX_LIMIT))]
     #[serde(default = "default_limit")]
     pub limit: i64,
     #[garde(range(min = 0))]
     #[serde(default)] // default は 0
     pub offset: i64,
+    #[garde(length(max = 255))]
+    pub keyword: Option<String>,
+    #[garde(skip)]
+    pub owner: Option<UserId>,
+    // ハイフンの有無や ISBN-10・ISBN-13 の違いによらず、同じ ISBN の蔵書に絞り込む
+    #[garde(skip)]
+    pub isbn: Option<Isbn>,
+    #[garde(length(min = 1, max = 255))]
+    pub author: Option<String>,
+    #[garde(skip)]
+    pub availability: Option<BookAvailability>,
+    // カンマ区切りで複数指定した場合はすべてのタグを付けられた蔵書に絞り込む
+    #[garde(skip)]
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub tags: Vec<TagId>,
+    // 指定した場所、もしくはその配下に冊子が置かれている蔵書に絞り込む
+    #[garde(skip)]
+    pub location: Option<LocationId>,
+    #[garde(skip)]
+    pub sort: Option<BookSortKey>,
+    #[garde(skip)]
+    pub order: Option<SortOrder>,
+    // paging=cursor もしくは after・before を指定した場合はカーソルによるページネーションを行う
+    // その場合 offset は使わず、総件数も返さない
+    #[garde(skip)]
+    pub paging: Option<Paging>,
+    #[garde(skip)]
+    pub after: Option<String>,
+    #[garde(skip)]
+    pub before: Option<String>,
+}
+
+impl BookListQuery {
+    pub fn is_cursor_paging(&self) -> bool {
+        matches!(self.paging, Some(Paging::Cursor)) || self.after.is_some() || self.before.is_some()
+    }
+
+    pub fn take_page_cursor(&mut self) -> AppResult<Option<PageCursor>> {
+        parse_page_cursor(self.after.take(), self.before.take())
+    }
+}
+
+// 削除済みの蔵書一覧のクエリ。絞り込みや並び替えは行わない
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeletedBookListQuery {
+    #[garde(range(min = 0, max = MAX_LIMIT))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Paging {
+    Offset,
+    Cursor,
+}
+
+// 貸出状態による絞り込み条件
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookAvailability {
+    Available,
+    CheckedOut,
+}
+
+impl From<BookAvailability> for kernel::model::book::BookAvailability {
+    fn from(value: BookAvailability) -> Self {
+        match value {
+            BookAvailability::Available => Self::Available,
+            BookAvailability::CheckedOut => Self::CheckedOut,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookSortKey {
+    Title,
+    Author,
+    CreatedAt,
+    UpdatedAt,
+    Rating,
+}
+
+impl From<BookSortKey> for kernel::model::book::BookSortKey {
+    fn from(value: BookSortKey) -> Self {
+        match value {
+            BookSortKey::Title => Self::Title,
+            BookSortKey::Author => Self::Author,
+            BookSortKey::CreatedAt => Self::CreatedAt,
+            BookSortKey::UpdatedAt => Self::UpdatedAt,
+            BookSortKey::Rating => Self::Rating,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl From<SortOrder> for kernel::model::list::SortOrder {
+    fn from(value: SortOrder) -> Self {
+        match value {
+            SortOrder::Asc => Self::Asc,
+            SortOrder::Desc => Self::Desc,
+        }
+    }
+}
+
+// カンマ区切りの文字列を、要素ごとに FromStr で変換した Vec として受け取る
+pub fn comma_separated<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let s = String::deserialize(deserializer)?;
+    s.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.parse().map_err(serde::de::Error::custom))
+        .collect()
+}
+
+// 省略された項目は None、null が指定された項目は Some(None) として受け取る
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 const DEFAULT_LIMIT: i64 = 20;
-const fn default_limit() -> i64 {
+pub(super) const fn default_limit() -> i64 {
     DEFAULT_LIMIT
 }
 
 impl From<BookListQuery> for BookListOptions {
     fn from(value: BookListQuery) -> Self {
-        let BookListQuery { limit, offset } = value;
-        Self { limit, offset }
+        let BookListQuery {
+            limit,
+            offset,
+            keyword,
+            owner,
+            isbn,
+            author,
+            availability,
+            tags,
+            location,
+            sort,
+            order,
+            ..
+        } = value;
+        Self {
+            limit,
+            offset,
+            // 空文字列や空白のみのキーワードは指定なしとして扱う
+            keyword: keyword.filter(|k| !k.trim().is_empty()),
+            owner,
+            isbn,
+            author,
+            author_id: None,
+            author_role: None,
+            availability: availability.map(Into::into),
+            tags,
+            location,
+            sort: sort.map(Into::into),
+            order: order.map(Into::into),
+        }
     }
 }
 
@@ -118,11 +398,31 @@ impl From<BookListQuery> for BookListOptions {
 pub struct BookResponse {
     pub id: BookId,
     pub title: String,
+    // 著者の一覧を 1 つにまとめた表示用の文字列
     pub author: String,
+    pub authors: Vec<BookAuthorResponse>,
     pub isbn: String,
     pub description: String,
+    // 出版社と出版年。登録されていない場合は null
+    pub publisher: Option<String>,
+    pub published_year: Option<i32>,
     pub owner: BookOwner,
-    pub checkout: Option<BookCheckoutResponse>,
+    // 冊子の総数と、そのうち貸出可能な冊子の数
+    pub total_copies: i64,
+    pub available_copies: i64,
+    // 貸出中の冊子の貸出情報
+    pub checkouts: Vec<BookCheckoutResponse>,
+    pub tags: Vec<TagResponse>,
+    // シリーズに属さない場合は null
+    pub series: Option<BookSeriesResponse>,
+    // 表紙画像とサムネイルの URL。表紙画像が登録されていない場合は null
+    pub cover_url: Option<String>,
+    pub cover_thumbnail_url: Option<String>,
+    // レビューの評価の平均。レビューがない場合は null
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    // 論理削除された日時。削除されていない場合は null
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Book> for BookResponse {
@@ -131,19 +431,47 @@ impl From<Book> for BookResponse {
             id,
             title,
             author,
+            authors,
             isbn,
             description,
+            publisher,
+            published_year,
             owner,
-            checkout,
+            total_copies,
+            available_copies,
+            checkouts,
+            tags,
+            series,
+            has_cover,
+            average_rating,
+            review_count,
+            // 最終更新日時は ETag ヘッダーとして返す
+            updated_at: _,
+            deleted_at,
         } = value;
         Self {
             id,
             title,
             author,
+            authors: authors.into_iter().map(BookAuthorResponse::from).collect(),
             isbn,
             description,
+            publisher,
+            published_year,
             owner: owner.into(),
-            checkout: checkout.map(BookCheckoutResponse::from),
+            total_copies,
+            available_copies,
+            checkouts: checkouts
+                .into_iter()
+                .map(BookCheckoutResponse::from)
+                .collect(),
+            tags: tags.into_iter().map(TagResponse::from).collect(),
+            series: series.map(BookSeriesResponse::from),
+            cover_url: has_cover.then(|| cover_url(id)),
+            cover_thumbnail_url: has_cover.then(|| cover_thumbnail_url(id)),
+            average_rating,
+            review_count,
+            deleted_at,
         }
     }
 }
@@ -178,26 +506,332 @@ impl From<PaginatedList<Book>> for PaginatedBookResponse {
     }
 }
 
+// カーソルによるページネーションの場合のレスポンス
+// 次のページ・前のページがない場合、nextCursor・prevCursor は null になる
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginatedBookResponse {
+    pub limit: i64,
+    pub items: Vec<BookResponse>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+impl From<CursorPaginatedList<Book>> for CursorPaginatedBookResponse {
+    fn from(value: CursorPaginatedList<Book>) -> Self {
+        let CursorPaginatedList {
+            limit,
+            items,
+            next_cursor,
+            prev_cursor,
+        } = value;
+        Self {
+            limit,
+            items: items.into_iter().map(BookResponse::from).collect(),
+            next_cursor: next_cursor.map(|c| c.to_string()),
+            prev_cursor: prev_cursor.map(|c| c.to_string()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BookCheckoutResponse {
     pub id: CheckoutId,
+    pub copy_id: CopyId,
+    pub barcode: String,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    // 返却期限を過ぎている場合に true
+    pub overdue: bool,
 }
 
 impl From<Checkout> for BookCheckoutResponse {
     fn from(value: Checkout) -> Self {
+        let overdue = value.is_overdue(Utc::now());
         let Checkout {
             checkout_id,
+            copy_id,
+            barcode,
             checked_out_by,
             checked_out_at,
+            due_at,
         } = value;
         Self {
             id: checkout_id,
+            copy_id,
+            barcode,
             checked_out_by: checked_out_by.into(),
             checked_out_at,
+            due_at,
+            overdue,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBookCopyRequest {
+    // 指定しない場合は冊子 ID から生成する
+    #[garde(length(min = 1, max = 255))]
+    pub barcode: Option<String>,
+}
+
+#[derive(new)]
+pub struct CreateBookCopyRequestWithIds(BookId, Actor, CreateBookCopyRequest);
+impl From<CreateBookCopyRequestWithIds> for CreateBookCopy {
+    fn from(value: CreateBookCopyRequestWithIds) -> Self {
+        let CreateBookCopyRequestWithIds(book_id, actor, CreateBookCopyRequest { barcode }) = value;
+        CreateBookCopy {
+            book_id,
+            barcode,
+            requested_by: actor,
+        }
+    }
+}
+
+// 冊子を置く場所の変更。null を指定した場合は未設定に戻す
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBookCopyLocationRequest {
+    pub location_id: Option<LocationId>,
+}
+
+#[derive(new)]
+pub struct UpdateBookCopyLocationRequestWithIds(
+    BookId,
+    CopyId,
+    Actor,
+    UpdateBookCopyLocationRequest,
+);
+impl From<UpdateBookCopyLocationRequestWithIds> for UpdateBookCopyLocation {
+    fn from(value: UpdateBookCopyLocationRequestWithIds) -> Self {
+        let UpdateBookCopyLocationRequestWithIds(
+            book_id,
+            copy_id,
+            actor,
+            UpdateBookCopyLocationRequest { location_id },
+        ) = value;
+        UpdateBookCopyLocation {
+            book_id,
+            copy_id,
+            location_id,
+            requested_by: actor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum CopyCondition {
+    New,
+    Good,
+    Fair,
+    Poor,
+}
+
+impl From<CopyCondition> for kernel::model::book::CopyCondition {
+    fn from(value: CopyCondition) -> Self {
+        match value {
+            CopyCondition::New => Self::New,
+            CopyCondition::Good => Self::Good,
+            CopyCondition::Fair => Self::Fair,
+            CopyCondition::Poor => Self::Poor,
+        }
+    }
+}
+
+impl From<kernel::model::book::CopyCondition> for CopyCondition {
+    fn from(value: kernel::model::book::CopyCondition) -> Self {
+        match value {
+            kernel::model::book::CopyCondition::New => Self::New,
+            kernel::model::book::CopyCondition::Good => Self::Good,
+            kernel::model::book::CopyCondition::Fair => Self::Fair,
+            kernel::model::book::CopyCondition::Poor => Self::Poor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBookCopyConditionRequest {
+    pub condition: CopyCondition,
+}
+
+#[derive(new)]
+pub struct UpdateBookCopyConditionRequestWithIds(
+    BookId,
+    CopyId,
+    Actor,
+    UpdateBookCopyConditionRequest,
+);
+impl From<UpdateBookCopyConditionRequestWithIds> for UpdateBookCopyCondition {
+    fn from(value: UpdateBookCopyConditionRequestWithIds) -> Self {
+        let UpdateBookCopyConditionRequestWithIds(
+            book_id,
+            copy_id,
+            actor,
+            UpdateBookCopyConditionRequest { condition },
+        ) = value;
+        UpdateBookCopyCondition {
+            book_id,
+            copy_id,
+            condition: condition.into(),
+            requested_by: actor,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookCopyResponse {
+    pub id: CopyId,
+    pub barcode: String,
+    // 場所が設定されていない場合は null
+    pub location: Option<LocationResponse>,
+    pub condition: CopyCondition,
+    // 貸出の対象から外されていない場合は null
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    // 貸し出し中でない場合は null
+    pub checkout: Option<BookCheckoutResponse>,
+}
+
+impl From<BookCopy> for BookCopyResponse {
+    fn from(value: BookCopy) -> Self {
+        let BookCopy {
+            id,
+            barcode,
+            location,
+            condition,
+            withdrawn_at,
+            checkout,
+        } = value;
+        Self {
+            id,
+            barcode,
+            location: location.map(LocationResponse::from),
+            condition: condition.into(),
+            withdrawn_at,
+            checkout: checkout.map(BookCheckoutResponse::from),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookCopiesResponse {
+    pub items: Vec<BookCopyResponse>,
+}
+
+impl From<Vec<BookCopy>> for BookCopiesResponse {
+    fn from(value: Vec<BookCopy>) -> Self {
+        Self {
+            items: value.into_iter().map(BookCopyResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookLookupQuery {
+    pub isbn: String,
+}
+
+// 蔵書登録の入力欄を埋めるための書誌情報
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookMetadataResponse {
+    pub isbn: String,
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl From<BookMetadata> for BookMetadataResponse {
+    fn from(value: BookMetadata) -> Self {
+        let BookMetadata {
+            isbn,
+            title,
+            authors,
+            description,
+        } = value;
+        Self {
+            isbn: isbn.into_inner(),
+            title,
+            authors,
+            description,
+        }
+    }
+}
+
+// 蔵書の登録時に見つかった、重複している可能性のある登録済みの蔵書の一覧
+#[derive(Debug, Serialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateBooksResponse {
+    pub candidates: Vec<DuplicateBookResponse>,
+}
+
+impl From<Vec<DuplicateCandidate>> for DuplicateBooksResponse {
+    fn from(value: Vec<DuplicateCandidate>) -> Self {
+        Self {
+            candidates: value.into_iter().map(DuplicateBookResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateBookResponse {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub reason: DuplicateBookReason,
+}
+
+impl From<DuplicateCandidate> for DuplicateBookResponse {
+    fn from(value: DuplicateCandidate) -> Self {
+        let DuplicateCandidate {
+            book_id,
+            title,
+            author,
+            isbn,
+            reason,
+        } = value;
+        Self {
+            book_id,
+            title,
+            author,
+            isbn,
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateBookReason {
+    Isbn,
+    TitleAndAuthor,
+}
+
+impl From<DuplicateReason> for DuplicateBookReason {
+    fn from(value: DuplicateReason) -> Self {
+        match value {
+            DuplicateReason::Isbn => Self::Isbn,
+            DuplicateReason::TitleAndAuthor => Self::TitleAndAuthor,
         }
     }
 }
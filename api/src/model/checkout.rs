@@ -1,23 +1,61 @@
 use chrono::{DateTime, Utc};
 use kernel::model::{
-    checkout::{Checkout, CheckoutBook},
-    id::{BookId, CheckoutId, UserId},
+    checkout::{Checkout, CheckoutBook, CheckoutRenewal},
+    id::{BookId, CheckoutId, CopyId, LocationId, UserId},
+    list::CursorPaginatedList,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 #[cfg(debug_assertions)]
 use utoipa::ToSchema;
 
+// 貸出中の一覧を取得する際の絞り込み条件
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutListQuery {
+    // true の場合は返却期限を過ぎた貸出のみを返す。管理者のみ指定できる
+    #[serde(default)]
+    pub overdue: bool,
+}
+
+// 返却時に、返却した冊子を置いた場所を指定する
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnBookQuery {
+    pub location_id: Option<LocationId>,
+}
+
 #[derive(Serialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutsResponse {
     pub items: Vec<CheckoutResponse>,
+    // カーソルによるページネーションを行った場合のみ値が入る
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
 }
 
 impl From<Vec<Checkout>> for CheckoutsResponse {
     fn from(value: Vec<Checkout>) -> Self {
         Self {
             items: value.into_iter().map(CheckoutResponse::from).collect(),
+            next_cursor: None,
+            prev_cursor: None,
+        }
+    }
+}
+
+impl From<CursorPaginatedList<Checkout>> for CheckoutsResponse {
+    fn from(value: CursorPaginatedList<Checkout>) -> Self {
+        let CursorPaginatedList {
+            items,
+            next_cursor,
+            prev_cursor,
+            ..
+        } = value;
+        Self {
+            items: items.into_iter().map(CheckoutResponse::from).collect(),
+            next_cursor: next_cursor.map(|c| c.to_string()),
+            prev_cursor: prev_cursor.map(|c| c.to_string()),
         }
     }
 }
@@ -29,25 +67,63 @@ pub struct CheckoutResponse {
     pub id: CheckoutId,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+    // 返却期限を過ぎても返却されていない場合に true
+    pub overdue: bool,
     pub returned_at: Option<DateTime<Utc>>,
     pub book: CheckoutBookResponse,
+    // 延長した日時の古い順に並ぶ
+    pub renewals: Vec<CheckoutRenewalResponse>,
 }
 
 impl From<Checkout> for CheckoutResponse {
     fn from(value: Checkout) -> Self {
+        let overdue = value.is_overdue(Utc::now());
         let Checkout {
             id,
             checked_out_by,
             checked_out_at,
+            due_at,
             returned_at,
             book,
+            renewals,
         } = value;
         Self {
             id,
             checked_out_by,
             checked_out_at,
+            due_at,
+            overdue,
             returned_at,
             book: book.into(),
+            renewals: renewals
+                .into_iter()
+                .map(CheckoutRenewalResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutRenewalResponse {
+    pub renewed_at: DateTime<Utc>,
+    pub previous_due_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+}
+
+impl From<CheckoutRenewal> for CheckoutRenewalResponse {
+    fn from(value: CheckoutRenewal) -> Self {
+        let CheckoutRenewal {
+            renewed_at,
+            previous_due_at,
+            due_at,
+        } = value;
+        Self {
+            renewed_at,
+            previous_due_at,
+            due_at,
         }
     }
 }
@@ -57,6 +133,9 @@ impl From<Checkout> for CheckoutResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CheckoutBookResponse {
     pub id: BookId,
+    // 貸し出した冊子。返却後に冊子が削除された場合などは null
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
     pub title: String,
     pub author: String,
     pub isbn: String,
@@ -66,12 +145,16 @@ impl From<CheckoutBook> for CheckoutBookResponse {
     fn from(value: CheckoutBook) -> Self {
         let CheckoutBook {
             book_id,
+            copy_id,
+            barcode,
             title,
             author,
             isbn,
         } = value;
         Self {
             id: book_id,
+            copy_id,
+            barcode,
             title,
             author,
             isbn,
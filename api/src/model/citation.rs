@@ -0,0 +1,277 @@
+use garde::Validate;
+use kernel::model::{author::AuthorRole, book::Book, id::BookId};
+use serde::{Deserialize, Serialize};
+use shared::error::{AppError, AppResult};
+
+use super::book::comma_separated;
+
+// 一度に書き出せる蔵書の数の上限
+pub const MAX_CITATION_BOOKS: usize = 100;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CitationQuery {
+    // 指定した場合は Accept ヘッダーより優先する
+    pub format: Option<CitationFormat>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CitationListQuery {
+    // カンマ区切りの蔵書 ID。指定した順に書き出す
+    #[garde(length(min = 1, max = MAX_CITATION_BOOKS))]
+    #[serde(deserialize_with = "comma_separated")]
+    pub ids: Vec<BookId>,
+    #[garde(skip)]
+    pub format: Option<CitationFormat>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CitationFormat {
+    Bibtex,
+    Ris,
+    CslJson,
+}
+
+impl CitationFormat {
+    // format クエリの指定を優先し、なければ Accept ヘッダーで判定する
+    // 品質値は考慮せず、対応している形式のうち最初に挙げられたものを選ぶ
+    // Accept ヘッダーがない場合や */* の場合は BibTeX とする
+    pub fn negotiate(format: Option<Self>, accept: Option<&str>) -> AppResult<Self> {
+        if let Some(format) = format {
+            return Ok(format);
+        }
+        let Some(accept) = accept else {
+            return Ok(Self::Bibtex);
+        };
+        accept
+            .split(',')
+            .find_map(|range| {
+                let mime = range.split(';').next().unwrap_or_default().trim();
+                match mime.to_ascii_lowercase().as_str() {
+                    "application/x-bibtex" | "text/x-bibtex" | "*/*" => Some(Self::Bibtex),
+                    "application/x-research-info-systems" => Some(Self::Ris),
+                    "application/vnd.citationstyles.csl+json" | "application/json" => {
+                        Some(Self::CslJson)
+                    }
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| {
+                AppError::NotAcceptable("citation format must be BibTeX, RIS or CSL-JSON".into())
+            })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Bibtex => "application/x-bibtex; charset=utf-8",
+            Self::Ris => "application/x-research-info-systems; charset=utf-8",
+            Self::CslJson => "application/vnd.citationstyles.csl+json",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Bibtex => "citation.bib",
+            Self::Ris => "citation.ris",
+            Self::CslJson => "citation.json",
+        }
+    }
+
+    // 蔵書を 1 件ずつ引用の形式に変換し、1 つの文書にまとめる
+    // 出版社・出版年などの登録されていない項目は出力しない
+    pub fn render(&self, books: &[Book]) -> AppResult<String> {
+        match self {
+            Self::Bibtex => Ok(books
+                .iter()
+                .map(bibtex_entry)
+                .collect::<Vec<_>>()
+                .join("\n")),
+            Self::Ris => Ok(books
+                .iter()
+                .map(ris_record)
+                .collect::<Vec<_>>()
+                .join("\r\n")),
+            Self::CslJson => {
+                let items = books.iter().map(CslItem::from).collect::<Vec<_>>();
+                serde_json::to_string_pretty(&items)
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+            }
+        }
+    }
+}
+
+// 役割ごとの著者名。著者の一覧がない蔵書では、表示用の著者名を著者として扱う
+fn names(book: &Book, role: AuthorRole) -> Vec<&str> {
+    if book.authors.is_empty() {
+        return match role {
+            AuthorRole::Author if !book.author.is_empty() => vec![book.author.as_str()],
+            _ => vec![],
+        };
+    }
+    book.authors
+        .iter()
+        .filter(|a| a.role == role)
+        .map(|a| a.name.as_str())
+        .collect()
+}
+
+// 引用キーには蔵書 ID を使う
+fn bibtex_entry(book: &Book) -> String {
+    let names = |role| names(book, role).join(" and ");
+    let fields = [
+        ("title", book.title.clone()),
+        ("author", names(AuthorRole::Author)),
+        ("editor", names(AuthorRole::Editor)),
+        ("translator", names(AuthorRole::Translator)),
+        ("publisher", book.publisher.clone().unwrap_or_default()),
+        (
+            "year",
+            book.published_year
+                .map(|y| y.to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "series",
+            book.series
+                .as_ref()
+                .map(|s| s.title.clone())
+                .unwrap_or_default(),
+        ),
+        (
+            "volume",
+            book.series
+                .as_ref()
+                .map(|s| s.volume.to_string())
+                .unwrap_or_default(),
+        ),
+        ("isbn", book.isbn.clone()),
+    ];
+    let mut entry = format!("@book{{{},\n", book.id);
+    for (name, value) in fields.iter().filter(|(_, value)| !value.is_empty()) {
+        entry.push_str(&format!("  {name} = {{{}}},\n", escape_bibtex(value)));
+    }
+    entry.push_str("}\n");
+    entry
+}
+
+// BibTeX で特別な意味を持つ文字をエスケープする
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// RIS は 1 行に 1 項目を並べるため、値の中の改行は空白に置き換える
+fn ris_record(book: &Book) -> String {
+    let mut lines = vec![("TY", "BOOK".to_string()), ("TI", book.title.clone())];
+    for (tag, role) in [
+        ("AU", AuthorRole::Author),
+        ("ED", AuthorRole::Editor),
+        ("A4", AuthorRole::Translator),
+    ] {
+        lines.extend(names(book, role).into_iter().map(|n| (tag, n.to_string())));
+    }
+    if let Some(publisher) = &book.publisher {
+        lines.push(("PB", publisher.clone()));
+    }
+    if let Some(year) = book.published_year {
+        lines.push(("PY", year.to_string()));
+    }
+    if let Some(series) = &book.series {
+        lines.push(("T2", series.title.clone()));
+        lines.push(("VL", series.volume.to_string()));
+    }
+    if !book.isbn.is_empty() {
+        lines.push(("SN", book.isbn.clone()));
+    }
+    if !book.description.is_empty() {
+        lines.push(("AB", book.description.clone()));
+    }
+
+    let mut record = String::new();
+    for (tag, value) in lines {
+        let value = value.replace(['\r', '\n'], " ");
+        record.push_str(&format!("{tag}  - {value}\r\n"));
+    }
+    record.push_str("ER  - \r\n");
+    record
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct CslItem {
+    id: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    author: Vec<CslName>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    editor: Vec<CslName>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    translator: Vec<CslName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issued: Option<CslDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    collection_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<i32>,
+    #[serde(rename = "ISBN", skip_serializing_if = "String::is_empty")]
+    isbn: String,
+    #[serde(rename = "abstract", skip_serializing_if = "String::is_empty")]
+    description: String,
+}
+
+// 姓と名に分けられないため、名前はそのまま literal として出力する
+#[derive(Debug, Serialize)]
+struct CslName {
+    literal: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CslDate {
+    #[serde(rename = "date-parts")]
+    date_parts: Vec<Vec<i32>>,
+}
+
+impl From<&Book> for CslItem {
+    fn from(book: &Book) -> Self {
+        let names = |role| {
+            names(book, role)
+                .into_iter()
+                .map(|name| CslName {
+                    literal: name.to_string(),
+                })
+                .collect()
+        };
+        Self {
+            id: book.id.to_string(),
+            kind: "book",
+            title: book.title.clone(),
+            author: names(AuthorRole::Author),
+            editor: names(AuthorRole::Editor),
+            translator: names(AuthorRole::Translator),
+            publisher: book.publisher.clone(),
+            issued: book.published_year.map(|year| CslDate {
+                date_parts: vec![vec![year]],
+            }),
+            collection_title: book.series.as_ref().map(|s| s.title.clone()),
+            volume: book.series.as_ref().map(|s| s.volume),
+            isbn: book.isbn.clone(),
+            description: book.description.clone(),
+        }
+    }
+}
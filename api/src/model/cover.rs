@@ -0,0 +1,35 @@
+use kernel::model::cover::CoverImageSize;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CoverImageQuery {
+    #[serde(default)]
+    pub size: CoverImageSizeQuery,
+}
+
+// 取得する表紙画像の大きさ。省略時は元画像を返す
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CoverImageSizeQuery {
+    #[default]
+    Original,
+    Thumbnail,
+}
+
+impl From<CoverImageSizeQuery> for CoverImageSize {
+    fn from(value: CoverImageSizeQuery) -> Self {
+        match value {
+            CoverImageSizeQuery::Original => Self::Original,
+            CoverImageSizeQuery::Thumbnail => Self::Thumbnail,
+        }
+    }
+}
+
+// 表紙画像を取得するための URL
+pub fn cover_url(book_id: kernel::model::id::BookId) -> String {
+    format!("/api/v1/books/{}/cover", book_id.raw())
+}
+
+pub fn cover_thumbnail_url(book_id: kernel::model::id::BookId) -> String {
+    format!("{}?size=thumbnail", cover_url(book_id))
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use kernel::model::{
+    damage::{
+        event::{CreateDamageReport, ResolveDamageReport},
+        DamageReport, DamageResolution,
+    },
+    id::{BookId, CheckoutId, CopyId, DamageReportId, UserId},
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::user::DamageReporter;
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDamageReportRequest {
+    // 破損の箇所や程度
+    #[garde(length(min = 1))]
+    pub description: String,
+}
+
+#[derive(new)]
+pub struct CreateDamageReportRequestWithIds(BookId, CheckoutId, UserId, CreateDamageReportRequest);
+impl From<CreateDamageReportRequestWithIds> for CreateDamageReport {
+    fn from(value: CreateDamageReportRequestWithIds) -> Self {
+        let CreateDamageReportRequestWithIds(
+            book_id,
+            checkout_id,
+            user_id,
+            CreateDamageReportRequest { description },
+        ) = value;
+        CreateDamageReport {
+            book_id,
+            checkout_id,
+            description: description.trim().to_string(),
+            reported_by: user_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveDamageReportRequest {
+    // 修理した、弁償を受けたなどの対応内容
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(new)]
+pub struct ResolveDamageReportRequestWithIds(DamageReportId, UserId, ResolveDamageReportRequest);
+impl From<ResolveDamageReportRequestWithIds> for ResolveDamageReport {
+    fn from(value: ResolveDamageReportRequestWithIds) -> Self {
+        let ResolveDamageReportRequestWithIds(
+            damage_report_id,
+            user_id,
+            ResolveDamageReportRequest { note },
+        ) = value;
+        ResolveDamageReport {
+            damage_report_id,
+            note: note.trim().to_string(),
+            resolved_by: user_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum DamageReportStatus {
+    #[default]
+    Open,
+    Resolved,
+}
+
+impl From<DamageReportStatus> for kernel::model::damage::DamageReportStatus {
+    fn from(value: DamageReportStatus) -> Self {
+        match value {
+            DamageReportStatus::Open => Self::Open,
+            DamageReportStatus::Resolved => Self::Resolved,
+        }
+    }
+}
+
+impl From<kernel::model::damage::DamageReportStatus> for DamageReportStatus {
+    fn from(value: kernel::model::damage::DamageReportStatus) -> Self {
+        match value {
+            kernel::model::damage::DamageReportStatus::Open => Self::Open,
+            kernel::model::damage::DamageReportStatus::Resolved => Self::Resolved,
+        }
+    }
+}
+
+// 省略した場合は未対応の報告を返す
+#[derive(Debug, Deserialize)]
+pub struct DamageReportListQuery {
+    #[serde(default)]
+    pub status: DamageReportStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DamageReportResponse {
+    pub id: DamageReportId,
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
+    pub reported_by: DamageReporter,
+    pub description: String,
+    // 写真が添付されている場合は /damage-reports/{id}/photo で取得できる
+    pub has_photo: bool,
+    pub status: DamageReportStatus,
+    pub reported_at: DateTime<Utc>,
+    // 以下は対応済みでない場合は null
+    pub resolved_by: Option<UserId>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<DamageReport> for DamageReportResponse {
+    fn from(value: DamageReport) -> Self {
+        let status = value.status().into();
+        let DamageReport {
+            id,
+            checkout_id,
+            book_id,
+            book_title,
+            copy_id,
+            barcode,
+            reported_by,
+            description,
+            photo_format,
+            reported_at,
+            resolution,
+        } = value;
+        let (resolved_by, resolution_note, resolved_at) = match resolution {
+            Some(DamageResolution {
+                resolved_by,
+                note,
+                resolved_at,
+            }) => (resolved_by, Some(note), Some(resolved_at)),
+            None => (None, None, None),
+        };
+        Self {
+            id,
+            checkout_id,
+            book_id,
+            book_title,
+            copy_id,
+            barcode,
+            reported_by: reported_by.into(),
+            description,
+            has_photo: photo_format.is_some(),
+            status,
+            reported_at,
+            resolved_by,
+            resolution_note,
+            resolved_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DamageReportsResponse {
+    pub items: Vec<DamageReportResponse>,
+}
+
+impl From<Vec<DamageReport>> for DamageReportsResponse {
+    fn from(value: Vec<DamageReport>) -> Self {
+        Self {
+            items: value.into_iter().map(DamageReportResponse::from).collect(),
+        }
+    }
+}
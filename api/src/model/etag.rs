@@ -0,0 +1,57 @@
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// 蔵書の ETag。最終更新日時をミリ秒単位で表した値と、レスポンスの本文のハッシュ値を `-` でつなぐ
+// 貸出や返却、レビューの投稿では蔵書の最終更新日時は変わらないが本文は変わるため、
+// 本文のハッシュ値も含めて If-None-Match で古い内容を返さないようにする
+pub fn book_etag(updated_at: DateTime<Utc>, body: &impl Serialize) -> String {
+    let digest = Sha256::digest(serde_json::to_vec(body).unwrap_or_default());
+    let hash = digest[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("\"{}-{hash}\"", updated_at.timestamp_millis())
+}
+
+// If-Match で指定された ETag から、更新の前提とする蔵書の最終更新日時を取り出す
+// 更新の競合は蔵書そのものの変更だけで判定するため、本文のハッシュ値は比較しない
+// ヘッダーがない場合や `*` の場合は確認不要として None を返す
+// 弱い ETag や解釈できない値は、どの蔵書とも一致しないものとして読み飛ばす
+pub fn parse_if_match(headers: &HeaderMap) -> Option<Vec<DateTime<Utc>>> {
+    let tags = entity_tags(headers, header::IF_MATCH);
+    if tags.is_empty() || tags.contains(&"*") {
+        return None;
+    }
+    Some(
+        tags.into_iter()
+            .filter_map(|tag| {
+                let tag = tag.strip_prefix('"')?.strip_suffix('"')?;
+                let (updated_at, _) = tag.split_once('-').unwrap_or((tag, ""));
+                updated_at.parse().ok()
+            })
+            .filter_map(DateTime::from_timestamp_millis)
+            .collect(),
+    )
+}
+
+// If-None-Match で指定された ETag のいずれかが etag と一致するか
+// If-None-Match は弱い比較を行うため、W/ の有無は区別しない
+pub fn matches_if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    entity_tags(headers, header::IF_NONE_MATCH)
+        .into_iter()
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+// ヘッダーにカンマ区切りで並んだ ETag を取り出す
+fn entity_tags(headers: &HeaderMap, name: header::HeaderName) -> Vec<&str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
@@ -0,0 +1,119 @@
+use axum::body::Bytes;
+use kernel::model::book::Book;
+use serde::Deserialize;
+use shared::error::{AppError, AppResult};
+
+use super::book::BookResponse;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BookExportQuery {
+    #[serde(default)]
+    pub format: BookExportFormat,
+}
+
+// 省略時は Excel でそのまま開ける BOM 付きの CSV とする
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl BookExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Csv => "books.csv",
+            Self::Ndjson => "books.ndjson",
+        }
+    }
+
+    // 1 件目の蔵書より前に出力する内容
+    pub fn preamble(&self) -> AppResult<Bytes> {
+        match self {
+            Self::Csv => {
+                let mut buf = b"\xEF\xBB\xBF".to_vec();
+                buf.extend(csv_line(&CSV_HEADERS)?);
+                Ok(buf.into())
+            }
+            Self::Ndjson => Ok(Bytes::new()),
+        }
+    }
+
+    // 蔵書 1 件分を 1 行に変換する
+    pub fn encode(&self, book: Book) -> AppResult<Bytes> {
+        match self {
+            Self::Csv => csv_line(&csv_record(book)).map(Bytes::from),
+            Self::Ndjson => {
+                let mut buf = serde_json::to_vec(&BookResponse::from(book))
+                    .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+                buf.push(b'\n');
+                Ok(buf.into())
+            }
+        }
+    }
+}
+
+const CSV_HEADERS: [&str; 13] = [
+    "id",
+    "title",
+    "author",
+    "isbn",
+    "description",
+    "owner_id",
+    "owner_name",
+    "total_copies",
+    "available_copies",
+    "tags",
+    "checked_out_barcodes",
+    "checked_out_by",
+    "checked_out_at",
+];
+
+// 複数の値を持つ項目は "; " で区切って 1 列にまとめる
+// 貸出情報の 3 列は、同じ位置の値が同じ貸出に対応する
+fn csv_record(book: Book) -> [String; 13] {
+    let join = |values: Vec<String>| values.join("; ");
+    [
+        book.id.to_string(),
+        book.title,
+        book.author,
+        book.isbn,
+        book.description,
+        book.owner.id.to_string(),
+        book.owner.name,
+        book.total_copies.to_string(),
+        book.available_copies.to_string(),
+        join(book.tags.into_iter().map(|t| t.name).collect()),
+        join(book.checkouts.iter().map(|c| c.barcode.clone()).collect()),
+        join(
+            book.checkouts
+                .iter()
+                .map(|c| c.checked_out_by.name.clone())
+                .collect(),
+        ),
+        join(
+            book.checkouts
+                .iter()
+                .map(|c| c.checked_out_at.to_rfc3339())
+                .collect(),
+        ),
+    ]
+}
+
+fn csv_line<T: AsRef<[u8]>>(record: &[T]) -> AppResult<Vec<u8>> {
+    let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+    writer
+        .write_record(record)
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))
+}
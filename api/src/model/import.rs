@@ -0,0 +1,248 @@
+use garde::Validate;
+use kernel::model::{
+    book::{
+        event::{CreateBook, ImportBookRow, ImportMode},
+        BookImportResult, BookImportStatus,
+    },
+    id::BookId,
+};
+use serde::{Deserialize, Serialize};
+use shared::error::{AppError, AppResult};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::{author::AuthorCreditRequest, book::CreateBookRequest};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BookImportQuery {
+    #[serde(default)]
+    pub mode: BookImportModeQuery,
+}
+
+// 省略時は 1 行でも登録できなければ何も登録しない
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookImportModeQuery {
+    #[default]
+    AllOrNothing,
+    BestEffort,
+}
+
+impl From<BookImportModeQuery> for ImportMode {
+    fn from(value: BookImportModeQuery) -> Self {
+        match value {
+            BookImportModeQuery::AllOrNothing => Self::AllOrNothing,
+            BookImportModeQuery::BestEffort => Self::BestEffort,
+        }
+    }
+}
+
+// 取り込むファイルの形式。Content-Type ヘッダーで判定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookImportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl BookImportFormat {
+    pub fn from_content_type(content_type: &str) -> AppResult<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "text/csv" => Ok(Self::Csv),
+            "application/x-ndjson" | "application/jsonl" => Ok(Self::Ndjson),
+            _ => Err(AppError::UnsupportedMediaType(
+                "import file must be text/csv or application/x-ndjson".into(),
+            )),
+        }
+    }
+}
+
+// 取り込むファイルの 1 行分。CSV ではヘッダー行の列名で項目を対応づける
+#[derive(Debug, Deserialize)]
+struct BookImportRecord {
+    title: String,
+    author: String,
+    isbn: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl From<BookImportRecord> for CreateBookRequest {
+    fn from(value: BookImportRecord) -> Self {
+        let BookImportRecord {
+            title,
+            author,
+            isbn,
+            description,
+        } = value;
+        Self {
+            title,
+            // 著者名の列は 1 人の著者として登録する
+            authors: (!author.trim().is_empty())
+                .then(|| AuthorCreditRequest {
+                    name: author,
+                    role: Default::default(),
+                })
+                .into_iter()
+                .collect(),
+            isbn,
+            description,
+            publisher: None,
+            published_year: None,
+            tags: vec![],
+            series: None,
+            autofill: false,
+            // 取り込みでは重複の候補を確認しない。同じ ISBN の行は登録に失敗した行として扱う
+            force: false,
+        }
+    }
+}
+
+// ファイルを行ごとに読み取り、CreateBookRequest と同じ規則で検証する
+// 検証を通った行は登録対象として、通らなかった行はその理由とともに返す
+pub fn parse_import_rows(
+    format: BookImportFormat,
+    body: &[u8],
+) -> (Vec<ImportBookRow>, Vec<BookImportResult>) {
+    let records = match format {
+        BookImportFormat::Csv => read_csv(body),
+        BookImportFormat::Ndjson => read_ndjson(body),
+    };
+
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    for (line, record) in records {
+        match record.and_then(into_create_book) {
+            Ok(book) => rows.push(ImportBookRow { line, book }),
+            Err(errors) => failures.push(BookImportResult {
+                line,
+                status: BookImportStatus::Failed(errors),
+            }),
+        }
+    }
+    (rows, failures)
+}
+
+fn into_create_book(record: BookImportRecord) -> Result<CreateBook, Vec<String>> {
+    let req = CreateBookRequest::from(record);
+    req.validate(&()).map_err(|report| {
+        report
+            .iter()
+            .map(|(path, error)| format!("{path}: {}", error.message()))
+            .collect::<Vec<_>>()
+    })?;
+    req.try_into().map_err(|e: AppError| vec![e.to_string()])
+}
+
+type ParsedRecord = (u64, Result<BookImportRecord, Vec<String>>);
+
+fn read_csv(body: &[u8]) -> Vec<ParsedRecord> {
+    // Excel などが付ける BOM は読み飛ばす
+    let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(body);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(body);
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => return vec![(1, Err(vec![e.to_string()]))],
+    };
+
+    reader
+        .records()
+        .map(|record| match record {
+            Ok(record) => {
+                let line = record.position().map(|p| p.line()).unwrap_or_default();
+                let parsed = record
+                    .deserialize::<BookImportRecord>(Some(&headers))
+                    .map_err(|e| vec![e.to_string()]);
+                (line, parsed)
+            }
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                (line, Err(vec![e.to_string()]))
+            }
+        })
+        .collect()
+}
+
+fn read_ndjson(body: &[u8]) -> Vec<ParsedRecord> {
+    body.split(|b| *b == b'\n')
+        .enumerate()
+        .map(|(i, line)| (i as u64 + 1, line.trim_ascii()))
+        // 空行は無視する
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line_no, line)| {
+            let parsed =
+                serde_json::from_slice::<BookImportRecord>(line).map_err(|e| vec![e.to_string()]);
+            (line_no, parsed)
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportResponse {
+    pub imported: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    // 行番号の順に並べた各行の結果
+    pub rows: Vec<BookImportRowResponse>,
+}
+
+impl From<Vec<BookImportResult>> for BookImportResponse {
+    fn from(mut value: Vec<BookImportResult>) -> Self {
+        value.sort_by_key(|r| r.line);
+        let rows = value
+            .into_iter()
+            .map(BookImportRowResponse::from)
+            .collect::<Vec<_>>();
+        let count =
+            |status: BookImportRowStatus| rows.iter().filter(|r| r.status == status).count();
+        Self {
+            imported: count(BookImportRowStatus::Imported),
+            failed: count(BookImportRowStatus::Failed),
+            skipped: count(BookImportRowStatus::Skipped),
+            rows,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BookImportRowStatus {
+    Imported,
+    Failed,
+    // 他の行が登録できなかったため登録しなかった行
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookImportRowResponse {
+    pub line: u64,
+    pub status: BookImportRowStatus,
+    pub book_id: Option<BookId>,
+    pub errors: Vec<String>,
+}
+
+impl From<BookImportResult> for BookImportRowResponse {
+    fn from(value: BookImportResult) -> Self {
+        let BookImportResult { line, status } = value;
+        let (status, book_id, errors) = match status {
+            BookImportStatus::Imported(book_id) => {
+                (BookImportRowStatus::Imported, Some(book_id), vec![])
+            }
+            BookImportStatus::Failed(errors) => (BookImportRowStatus::Failed, None, errors),
+            BookImportStatus::Skipped => (BookImportRowStatus::Skipped, None, vec![]),
+        };
+        Self {
+            line,
+            status,
+            book_id,
+            errors,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use garde::Validate;
+use kernel::model::list::{Cursor, CursorOptions, PageCursor};
+use serde::Deserialize;
+use shared::error::{AppError, AppResult};
+
+// カーソルによるページネーションのためのクエリ
+// after・before には前回のレスポンスの nextCursor・prevCursor を指定する
+#[derive(Debug, Deserialize, Validate)]
+pub struct CursorQuery {
+    #[garde(range(min = 0, max = MAX_LIMIT))]
+    pub limit: Option<i64>,
+    #[garde(skip)]
+    pub after: Option<String>,
+    #[garde(skip)]
+    pub before: Option<String>,
+}
+
+const DEFAULT_LIMIT: i64 = 20;
+// 一度に取得できる件数の上限
+pub const MAX_LIMIT: i64 = 100;
+
+impl CursorQuery {
+    // いずれのパラメータも指定されていない場合は None を返し、ページネーションを行わない
+    pub fn into_options(self) -> AppResult<Option<CursorOptions>> {
+        let CursorQuery {
+            limit,
+            after,
+            before,
+        } = self;
+        if limit.is_none() && after.is_none() && before.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(CursorOptions {
+            limit: limit.unwrap_or(DEFAULT_LIMIT),
+            cursor: parse_page_cursor(after, before)?,
+        }))
+    }
+}
+
+pub fn parse_page_cursor(
+    after: Option<String>,
+    before: Option<String>,
+) -> AppResult<Option<PageCursor>> {
+    match (after, before) {
+        (None, None) => Ok(None),
+        (Some(after), None) => Ok(Some(PageCursor::After(after.parse::<Cursor>()?))),
+        (None, Some(before)) => Ok(Some(PageCursor::Before(before.parse::<Cursor>()?))),
+        (Some(_), Some(_)) => Err(AppError::InvalidCursorError(
+            "after と before は同時に指定できません。".into(),
+        )),
+    }
+}
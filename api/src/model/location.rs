@@ -0,0 +1,135 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::model::{
+    id::LocationId,
+    location::{
+        event::{CreateLocation, UpdateLocation},
+        Location,
+    },
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum LocationKind {
+    Building,
+    Room,
+    Shelf,
+}
+
+impl From<LocationKind> for kernel::model::location::LocationKind {
+    fn from(value: LocationKind) -> Self {
+        match value {
+            LocationKind::Building => Self::Building,
+            LocationKind::Room => Self::Room,
+            LocationKind::Shelf => Self::Shelf,
+        }
+    }
+}
+
+impl From<kernel::model::location::LocationKind> for LocationKind {
+    fn from(value: kernel::model::location::LocationKind) -> Self {
+        match value {
+            kernel::model::location::LocationKind::Building => Self::Building,
+            kernel::model::location::LocationKind::Room => Self::Room,
+            kernel::model::location::LocationKind::Shelf => Self::Shelf,
+        }
+    }
+}
+
+// 部屋は建物を、書架は部屋を parentId に指定して作成する
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLocationRequest {
+    #[garde(skip)]
+    pub parent_id: Option<LocationId>,
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+    #[garde(skip)]
+    pub kind: LocationKind,
+}
+
+impl From<CreateLocationRequest> for CreateLocation {
+    fn from(value: CreateLocationRequest) -> Self {
+        let CreateLocationRequest {
+            parent_id,
+            name,
+            kind,
+        } = value;
+        Self {
+            parent_id,
+            name: name.trim().to_string(),
+            kind: kind.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLocationRequest {
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(new)]
+pub struct UpdateLocationRequestWithId(LocationId, UpdateLocationRequest);
+impl From<UpdateLocationRequestWithId> for UpdateLocation {
+    fn from(value: UpdateLocationRequestWithId) -> Self {
+        let UpdateLocationRequestWithId(location_id, UpdateLocationRequest { name }) = value;
+        Self {
+            location_id,
+            name: name.trim().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LocationResponse {
+    pub id: LocationId,
+    pub parent_id: Option<LocationId>,
+    pub name: String,
+    pub kind: LocationKind,
+    // 最上位の建物からこの場所までの名前
+    pub path: Vec<String>,
+}
+
+impl From<Location> for LocationResponse {
+    fn from(value: Location) -> Self {
+        let Location {
+            id,
+            parent_id,
+            name,
+            kind,
+            path,
+        } = value;
+        Self {
+            id,
+            parent_id,
+            name,
+            kind: kind.into(),
+            path,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LocationsResponse {
+    pub items: Vec<LocationResponse>,
+}
+
+impl From<Vec<Location>> for LocationsResponse {
+    fn from(value: Vec<Location>) -> Self {
+        Self {
+            items: value.into_iter().map(LocationResponse::from).collect(),
+        }
+    }
+}
@@ -1,4 +1,19 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod citation;
+pub mod cover;
+pub mod damage;
+pub mod etag;
+pub mod export;
+pub mod import;
+pub mod list;
+pub mod location;
+pub mod recommendation;
+pub mod review;
+pub mod revision;
+pub mod series;
+pub mod tag;
+pub mod transfer;
 pub mod user;
@@ -0,0 +1,68 @@
+use garde::Validate;
+use kernel::model::{id::BookId, recommendation::RecommendedBook};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::cover::cover_thumbnail_url;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RecommendationQuery {
+    #[garde(range(min = 1, max = 50))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+const DEFAULT_LIMIT: i64 = 10;
+const fn default_limit() -> i64 {
+    DEFAULT_LIMIT
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedBookResponse {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    // 表紙画像が登録されていない場合は null
+    pub cover_thumbnail_url: Option<String>,
+    pub score: i64,
+}
+
+impl From<RecommendedBook> for RecommendedBookResponse {
+    fn from(value: RecommendedBook) -> Self {
+        let RecommendedBook {
+            book_id,
+            title,
+            author,
+            has_cover,
+            score,
+        } = value;
+        Self {
+            book_id,
+            title,
+            author,
+            cover_thumbnail_url: has_cover.then(|| cover_thumbnail_url(book_id)),
+            score,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedBooksResponse {
+    pub items: Vec<RecommendedBookResponse>,
+}
+
+impl From<Vec<RecommendedBook>> for RecommendedBooksResponse {
+    fn from(value: Vec<RecommendedBook>) -> Self {
+        Self {
+            items: value
+                .into_iter()
+                .map(RecommendedBookResponse::from)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use derive_new::new;
+use garde::Validate;
+use kernel::model::{
+    id::{BookId, ReviewId, UserId},
+    list::PaginatedList,
+    review::{
+        event::{CreateReview, UpdateReview},
+        BookReview, ReviewListOptions, MAX_RATING, MIN_RATING,
+    },
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::{book::default_limit, list::MAX_LIMIT, user::Reviewer};
+
+// 評価は 1 〜 5 で指定する。コメントは省略できる
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewRequest {
+    #[garde(range(min = MIN_RATING, max = MAX_RATING))]
+    pub rating: i16,
+    #[garde(skip)]
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(new)]
+pub struct CreateReviewRequestWithIds(BookId, UserId, ReviewRequest);
+impl From<CreateReviewRequestWithIds> for CreateReview {
+    fn from(value: CreateReviewRequestWithIds) -> Self {
+        let CreateReviewRequestWithIds(book_id, user_id, ReviewRequest { rating, comment }) = value;
+        CreateReview {
+            book_id,
+            rating,
+            comment: comment.trim().to_string(),
+            requested_user: user_id,
+        }
+    }
+}
+
+#[derive(new)]
+pub struct UpdateReviewRequestWithIds(BookId, ReviewId, UserId, ReviewRequest);
+impl From<UpdateReviewRequestWithIds> for UpdateReview {
+    fn from(value: UpdateReviewRequestWithIds) -> Self {
+        let UpdateReviewRequestWithIds(
+            book_id,
+            review_id,
+            user_id,
+            ReviewRequest { rating, comment },
+        ) = value;
+        UpdateReview {
+            book_id,
+            review_id,
+            rating,
+            comment: comment.trim().to_string(),
+            requested_user: user_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReviewListQuery {
+    #[garde(range(min = 0, max = MAX_LIMIT))]
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[garde(range(min = 0))]
+    #[serde(default)]
+    pub offset: i64,
+}
+
+impl From<ReviewListQuery> for ReviewListOptions {
+    fn from(value: ReviewListQuery) -> Self {
+        let ReviewListQuery { limit, offset } = value;
+        Self { limit, offset }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewResponse {
+    pub id: ReviewId,
+    pub book_id: BookId,
+    pub reviewer: Reviewer,
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<BookReview> for ReviewResponse {
+    fn from(value: BookReview) -> Self {
+        let BookReview {
+            id,
+            book_id,
+            reviewer,
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        } = value;
+        Self {
+            id,
+            book_id,
+            reviewer: reviewer.into(),
+            rating,
+            comment,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedReviewResponse {
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub items: Vec<ReviewResponse>,
+}
+
+impl From<PaginatedList<BookReview>> for PaginatedReviewResponse {
+    fn from(value: PaginatedList<BookReview>) -> Self {
+        let PaginatedList {
+            total,
+            limit,
+            offset,
+            items,
+        } = value;
+        Self {
+            total,
+            limit,
+            offset,
+            items: items.into_iter().map(ReviewResponse::from).collect(),
+        }
+    }
+}
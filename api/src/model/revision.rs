@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use kernel::model::book::revision::{BookField, BookFieldChange, BookRevision};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::user::BookEditor;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionsResponse {
+    pub items: Vec<BookRevisionResponse>,
+}
+
+impl From<Vec<BookRevision>> for BookRevisionsResponse {
+    fn from(value: Vec<BookRevision>) -> Self {
+        Self {
+            items: value.into_iter().map(BookRevisionResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookRevisionResponse {
+    pub revision: i32,
+    // 変更したユーザーが削除されている場合は null
+    pub edited_by: Option<BookEditor>,
+    pub edited_at: DateTime<Utc>,
+    pub changes: Vec<BookFieldChangeResponse>,
+}
+
+impl From<BookRevision> for BookRevisionResponse {
+    fn from(value: BookRevision) -> Self {
+        let BookRevision {
+            revision,
+            edited_by,
+            edited_at,
+            changes,
+        } = value;
+        Self {
+            revision,
+            edited_by: edited_by.map(BookEditor::from),
+            edited_at,
+            changes: changes
+                .into_iter()
+                .map(BookFieldChangeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+// 直前の版からの項目ごとの変更点。最初の版では before が null になる
+// 出版社と出版年は値がない場合があるため、before・after ともに null になりうる
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookFieldChangeResponse {
+    pub field: BookFieldName,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl From<BookFieldChange> for BookFieldChangeResponse {
+    fn from(value: BookFieldChange) -> Self {
+        let BookFieldChange {
+            field,
+            before,
+            after,
+        } = value;
+        Self {
+            field: field.into(),
+            before,
+            after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BookFieldName {
+    Title,
+    Author,
+    Isbn,
+    Description,
+    Publisher,
+    PublishedYear,
+}
+
+impl From<BookField> for BookFieldName {
+    fn from(value: BookField) -> Self {
+        match value {
+            BookField::Title => Self::Title,
+            BookField::Author => Self::Author,
+            BookField::Isbn => Self::Isbn,
+            BookField::Description => Self::Description,
+            BookField::Publisher => Self::Publisher,
+            BookField::PublishedYear => Self::PublishedYear,
+        }
+    }
+}
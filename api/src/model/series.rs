@@ -0,0 +1,104 @@
+use garde::Validate;
+use kernel::model::{
+    id::{BookId, SeriesId},
+    series::{BookSeries, Series, SeriesMembership, SeriesVolume},
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+// 蔵書の登録・更新時に指定するシリーズと巻数
+// シリーズはタイトルで指定し、まだ登録されていないタイトルの場合はシリーズとして登録する
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesMembershipRequest {
+    #[garde(length(min = 1, max = 255))]
+    pub title: String,
+    #[garde(range(min = 1))]
+    pub volume: i32,
+}
+
+impl From<SeriesMembershipRequest> for SeriesMembership {
+    fn from(value: SeriesMembershipRequest) -> Self {
+        let SeriesMembershipRequest { title, volume } = value;
+        Self {
+            title: title.trim().to_string(),
+            volume,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookSeriesResponse {
+    pub id: SeriesId,
+    pub title: String,
+    pub volume: i32,
+}
+
+impl From<BookSeries> for BookSeriesResponse {
+    fn from(value: BookSeries) -> Self {
+        let BookSeries { id, title, volume } = value;
+        Self { id, title, volume }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesResponse {
+    pub id: SeriesId,
+    pub title: String,
+    // 巻数の順に並べた各巻の蔵書
+    pub volumes: Vec<SeriesVolumeResponse>,
+}
+
+impl From<Series> for SeriesResponse {
+    fn from(value: Series) -> Self {
+        let Series { id, title, volumes } = value;
+        Self {
+            id,
+            title,
+            volumes: volumes
+                .into_iter()
+                .map(SeriesVolumeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesVolumeResponse {
+    pub volume: i32,
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    // 冊子の総数と、そのうち貸出可能な冊子の数
+    pub total_copies: i64,
+    pub available_copies: i64,
+}
+
+impl From<SeriesVolume> for SeriesVolumeResponse {
+    fn from(value: SeriesVolume) -> Self {
+        let SeriesVolume {
+            volume,
+            book_id,
+            title,
+            author,
+            total_copies,
+            available_copies,
+        } = value;
+        Self {
+            volume,
+            book_id,
+            title,
+            author,
+            total_copies,
+            available_copies,
+        }
+    }
+}
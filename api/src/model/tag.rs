@@ -0,0 +1,74 @@
+use derive_new::new;
+use garde::Validate;
+use kernel::model::{
+    id::TagId,
+    tag::{
+        event::{CreateTag, UpdateTag},
+        Tag,
+    },
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagRequest {
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+impl From<CreateTagRequest> for CreateTag {
+    fn from(value: CreateTagRequest) -> Self {
+        let CreateTagRequest { name } = value;
+        Self { name }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagRequest {
+    #[garde(length(min = 1, max = 255))]
+    pub name: String,
+}
+
+#[derive(new)]
+pub struct UpdateTagRequestWithId(TagId, UpdateTagRequest);
+impl From<UpdateTagRequestWithId> for UpdateTag {
+    fn from(value: UpdateTagRequestWithId) -> Self {
+        let UpdateTagRequestWithId(tag_id, UpdateTagRequest { name }) = value;
+        Self { tag_id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TagResponse {
+    pub id: TagId,
+    pub name: String,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(value: Tag) -> Self {
+        let Tag { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TagsResponse {
+    pub items: Vec<TagResponse>,
+}
+
+impl From<Vec<Tag>> for TagsResponse {
+    fn from(value: Vec<Tag>) -> Self {
+        Self {
+            items: value.into_iter().map(TagResponse::from).collect(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use kernel::model::{
+    id::{BookId, UserId},
+    transfer::BookTransfer,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(debug_assertions)]
+use utoipa::ToSchema;
+
+use super::user::{BookOwner, TransferRecipient};
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ProposeTransferRequest {
+    pub to_user_id: UserId,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ReassignOwnerRequest {
+    pub user_id: UserId,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookTransfersResponse {
+    pub items: Vec<BookTransferResponse>,
+}
+
+impl From<Vec<BookTransfer>> for BookTransfersResponse {
+    fn from(value: Vec<BookTransfer>) -> Self {
+        Self {
+            items: value.into_iter().map(BookTransferResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookTransferResponse {
+    pub book_id: BookId,
+    pub book_title: String,
+    pub from: BookOwner,
+    pub to: TransferRecipient,
+    pub proposed_at: DateTime<Utc>,
+}
+
+impl From<BookTransfer> for BookTransferResponse {
+    fn from(value: BookTransfer) -> Self {
+        let BookTransfer {
+            book_id,
+            book_title,
+            from,
+            to,
+            proposed_at,
+        } = value;
+        Self {
+            book_id,
+            book_title,
+            from: from.into(),
+            to: to.into(),
+            proposed_at,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use derive_new::new;
 use garde::Validate;
 use kernel::model::{
     id::UserId,
+    list::CursorPaginatedList,
     role::Role,
     user::{
         event::{CreateUser, UpdateUserPassword, UpdateUserRole},
@@ -42,6 +43,35 @@ impl From<RoleName> for Role {
 #[serde(rename_all = "camelCase")]
 pub struct UsersResponse {
     pub items: Vec<UserResponse>,
+    // カーソルによるページネーションを行った場合のみ値が入る
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+impl From<Vec<User>> for UsersResponse {
+    fn from(value: Vec<User>) -> Self {
+        Self {
+            items: value.into_iter().map(UserResponse::from).collect(),
+            next_cursor: None,
+            prev_cursor: None,
+        }
+    }
+}
+
+impl From<CursorPaginatedList<User>> for UsersResponse {
+    fn from(value: CursorPaginatedList<User>) -> Self {
+        let CursorPaginatedList {
+            items,
+            next_cursor,
+            prev_cursor,
+            ..
+        } = value;
+        Self {
+            items: items.into_iter().map(UserResponse::from).collect(),
+            next_cursor: next_cursor.map(|c| c.to_string()),
+            prev_cursor: prev_cursor.map(|c| c.to_string()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -158,6 +188,36 @@ impl From<kernel::model::user::BookOwner> for BookOwner {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BookEditor {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::BookEditor> for BookEditor {
+    fn from(value: kernel::model::user::BookEditor) -> Self {
+        let kernel::model::user::BookEditor { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TransferRecipient {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::TransferRecipient> for TransferRecipient {
+    fn from(value: kernel::model::user::TransferRecipient) -> Self {
+        let kernel::model::user::TransferRecipient { id, name } = value;
+        Self { id, name }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(ToSchema))]
 #[serde(rename_all = "camelCase")]
@@ -172,3 +232,33 @@ impl From<kernel::model::user::CheckoutUser> for CheckoutUser {
         Self { id, name }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DamageReporter {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::DamageReporter> for DamageReporter {
+    fn from(value: kernel::model::user::DamageReporter) -> Self {
+        let kernel::model::user::DamageReporter { id, name } = value;
+        Self { id, name }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Reviewer {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl From<kernel::model::user::Reviewer> for Reviewer {
+    fn from(value: kernel::model::user::Reviewer) -> Self {
+        let kernel::model::user::Reviewer { id, name } = value;
+        Self { id, name }
+    }
+}
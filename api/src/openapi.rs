@@ -22,31 +22,145 @@ Rust でしたいと思った際に、どのようなクレートや実装を利
         handler::book::show_book_list,
         handler::book::show_book,
         handler::book::register_book,
+        handler::book::import_books,
+        handler::book::export_books,
+        handler::book::lookup_book_metadata,
         handler::book::update_book,
         handler::book::delete_book,
+        handler::book::show_deleted_book_list,
+        handler::book::restore_book,
+        handler::book::purge_book,
+        handler::book::show_book_copies,
+        handler::book::add_book_copy,
+        handler::book::update_book_copy_location,
+        handler::book::update_book_copy_condition,
+        handler::book::withdraw_book_copy,
+        handler::book::reinstate_book_copy,
+        handler::book::delete_book_copy,
+        handler::recommendation::show_related_books,
+        handler::recommendation::show_recommendations,
+        handler::review::show_book_reviews,
+        handler::review::register_review,
+        handler::review::update_review,
+        handler::review::delete_review,
+        handler::revision::show_book_revisions,
+        handler::revision::revert_book,
+        handler::transfer::show_book_transfer,
+        handler::transfer::propose_book_transfer,
+        handler::transfer::accept_book_transfer,
+        handler::transfer::cancel_book_transfer,
+        handler::transfer::reassign_book_owner,
+        handler::transfer::show_incoming_transfers,
+        handler::cover::show_book_cover,
+        handler::cover::upload_book_cover,
+        handler::cover::delete_book_cover,
         handler::checkout::checkout_book,
+        handler::checkout::renew_checkout,
+        handler::checkout::hold_book,
+        handler::checkout::cancel_hold,
         handler::checkout::return_book,
         handler::checkout::checkout_history,
+        handler::damage::report_damage,
+        handler::damage::show_damage_report_list,
+        handler::damage::show_damage_report,
+        handler::damage::upload_damage_photo,
+        handler::damage::show_damage_photo,
+        handler::damage::resolve_damage_report,
+        handler::tag::list_tags,
+        handler::tag::register_tag,
+        handler::tag::update_tag,
+        handler::tag::delete_tag,
+        handler::author::suggest_authors,
+        handler::author::show_author_books,
+        handler::series::show_series,
+        handler::citation::show_book_citation,
+        handler::citation::show_book_citations,
+        handler::location::list_locations,
+        handler::location::register_location,
+        handler::location::update_location,
+        handler::location::delete_location,
         handler::user::get_current_user,
         handler::auth::login,
         handler::auth::logout,
     ),
     components(schemas(
         model::book::CreateBookRequest,
+        model::book::DuplicateBooksResponse,
+        model::book::DuplicateBookResponse,
+        model::book::DuplicateBookReason,
         model::book::UpdateBookRequest,
         model::book::BookResponse,
         model::book::PaginatedBookResponse,
+        model::book::CursorPaginatedBookResponse,
         model::book::BookCheckoutResponse,
+        model::book::CreateBookCopyRequest,
+        model::book::UpdateBookCopyLocationRequest,
+        model::book::CopyCondition,
+        model::book::UpdateBookCopyConditionRequest,
+        model::book::BookCopyResponse,
+        model::book::BookCopiesResponse,
+        model::book::BookMetadataResponse,
+        model::import::BookImportResponse,
+        model::import::BookImportRowResponse,
+        model::import::BookImportRowStatus,
+        model::recommendation::RecommendedBookResponse,
+        model::recommendation::RecommendedBooksResponse,
+        model::review::ReviewRequest,
+        model::review::ReviewResponse,
+        model::review::PaginatedReviewResponse,
+        model::revision::BookRevisionsResponse,
+        model::revision::BookRevisionResponse,
+        model::revision::BookFieldChangeResponse,
+        model::revision::BookFieldName,
+        model::transfer::ProposeTransferRequest,
+        model::transfer::ReassignOwnerRequest,
+        model::transfer::BookTransferResponse,
+        model::transfer::BookTransfersResponse,
         model::checkout::CheckoutsResponse,
         model::checkout::CheckoutResponse,
         model::checkout::CheckoutBookResponse,
+        model::checkout::CheckoutRenewalResponse,
+        model::damage::CreateDamageReportRequest,
+        model::damage::ResolveDamageReportRequest,
+        model::damage::DamageReportStatus,
+        model::damage::DamageReportResponse,
+        model::damage::DamageReportsResponse,
+        model::tag::CreateTagRequest,
+        model::tag::UpdateTagRequest,
+        model::tag::TagResponse,
+        model::tag::TagsResponse,
+        model::author::AuthorRole,
+        model::author::AuthorCreditRequest,
+        model::author::BookAuthorResponse,
+        model::author::AuthorResponse,
+        model::author::AuthorsResponse,
+        model::series::SeriesMembershipRequest,
+        model::series::BookSeriesResponse,
+        model::series::SeriesResponse,
+        model::series::SeriesVolumeResponse,
+        model::location::LocationKind,
+        model::location::CreateLocationRequest,
+        model::location::UpdateLocationRequest,
+        model::location::LocationResponse,
+        model::location::LocationsResponse,
         model::user::BookOwner,
+        model::user::BookEditor,
+        model::user::TransferRecipient,
         model::user::CheckoutUser,
+        model::user::DamageReporter,
+        model::user::Reviewer,
         model::auth::LoginRequest,
         model::auth::AccessTokenResponse,
         kernel::model::id::BookId,
         kernel::model::id::UserId,
         kernel::model::id::CheckoutId,
+        kernel::model::id::CopyId,
+        kernel::model::id::TagId,
+        kernel::model::id::AuthorId,
+        kernel::model::id::SeriesId,
+        kernel::model::id::LocationId,
+        kernel::model::id::DamageReportId,
+        kernel::model::id::ReviewId,
     ))
 )]
 pub struct ApiDoc;
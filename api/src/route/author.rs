@@ -0,0 +1,10 @@
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+use crate::handler::author::{show_author_books, suggest_authors};
+
+pub fn build_author_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/authors", get(suggest_authors))
+        .route("/authors/:author_id/books", get(show_author_books))
+}
@@ -1,21 +1,90 @@
 use axum::{
+    extract::DefaultBodyLimit,
     routing::{delete, get, post, put},
     Router,
 };
+use kernel::model::cover::MAX_COVER_IMAGE_BYTES;
 use registry::AppRegistry;
 
 use crate::handler::{
-    book::{delete_book, register_book, show_book, show_book_list, update_book},
-    checkout::{checkout_book, checkout_history, return_book, show_checked_out_list},
+    book::{
+        add_book_copy, delete_book, delete_book_copy, export_books, import_books,
+        lookup_book_metadata, purge_book, register_book, reinstate_book_copy, restore_book,
+        show_book, show_book_copies, show_book_list, show_deleted_book_list, update_book,
+        update_book_copy_condition, update_book_copy_location, withdraw_book_copy,
+    },
+    checkout::{
+        cancel_hold, checkout_book, checkout_history, hold_book, renew_checkout, return_book,
+        show_checked_out_list,
+    },
+    citation::{show_book_citation, show_book_citations},
+    cover::{delete_book_cover, show_book_cover, upload_book_cover},
+    damage::report_damage,
+    recommendation::show_related_books,
+    review::{delete_review, register_review, show_book_reviews, update_review},
+    revision::{revert_book, show_book_revisions},
+    transfer::{
+        accept_book_transfer, cancel_book_transfer, propose_book_transfer, reassign_book_owner,
+        show_book_transfer,
+    },
 };
 
 pub fn build_book_routers() -> Router<AppRegistry> {
     let books_routers = Router::new()
         .route("/", post(register_book))
         .route("/", get(show_book_list))
+        .route("/import", post(import_books))
+        .route("/export", get(export_books))
+        .route("/citations", get(show_book_citations))
+        .route("/lookup", get(lookup_book_metadata))
+        .route("/deleted", get(show_deleted_book_list))
         .route("/:book_id", get(show_book))
         .route("/:book_id", put(update_book))
-        .route("/:book_id", delete(delete_book));
+        .route("/:book_id", delete(delete_book))
+        .route("/:book_id/restore", post(restore_book))
+        .route("/:book_id/purge", delete(purge_book))
+        .route("/:book_id/revisions", get(show_book_revisions))
+        .route("/:book_id/revisions/:revision/revert", post(revert_book))
+        .route(
+            "/:book_id/transfer",
+            get(show_book_transfer)
+                .post(propose_book_transfer)
+                .delete(cancel_book_transfer),
+        )
+        .route("/:book_id/transfer/accept", post(accept_book_transfer))
+        .route("/:book_id/owner", put(reassign_book_owner))
+        .route("/:book_id/copies", get(show_book_copies))
+        .route("/:book_id/copies", post(add_book_copy))
+        .route("/:book_id/copies/:copy_id", delete(delete_book_copy))
+        .route(
+            "/:book_id/copies/:copy_id/location",
+            put(update_book_copy_location),
+        )
+        .route(
+            "/:book_id/copies/:copy_id/condition",
+            put(update_book_copy_condition),
+        )
+        .route(
+            "/:book_id/copies/:copy_id/withdrawal",
+            put(withdraw_book_copy).delete(reinstate_book_copy),
+        )
+        .route("/:book_id/citation", get(show_book_citation))
+        .route("/:book_id/related", get(show_related_books))
+        .route(
+            "/:book_id/reviews",
+            get(show_book_reviews).post(register_review),
+        )
+        .route(
+            "/:book_id/reviews/:review_id",
+            put(update_review).delete(delete_review),
+        )
+        .route("/:book_id/cover", get(show_book_cover))
+        // 画像は既定のリクエストボディの上限を超えうるので、表紙画像の上限に合わせる
+        .route(
+            "/:book_id/cover",
+            put(upload_book_cover).layer(DefaultBodyLimit::max(MAX_COVER_IMAGE_BYTES)),
+        )
+        .route("/:book_id/cover", delete(delete_book_cover));
 
     let checkout_router = Router::new()
         .route("/checkouts", get(show_checked_out_list))
@@ -24,6 +93,15 @@ pub fn build_book_routers() -> Router<AppRegistry> {
             "/:book_id/checkouts/:checkout_id/returned",
             put(return_book),
         )
+        .route(
+            "/:book_id/checkouts/:checkout_id/renewals",
+            post(renew_checkout),
+        )
+        .route("/:book_id/holds", post(hold_book).delete(cancel_hold))
+        .route(
+            "/:book_id/checkouts/:checkout_id/damage-reports",
+            post(report_damage),
+        )
         .route("/:book_id/checkout-history", put(checkout_history));
 
     Router::new().nest("/books", books_routers.merge(checkout_router))
@@ -0,0 +1,27 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+    Router,
+};
+use kernel::model::damage::MAX_DAMAGE_PHOTO_BYTES;
+use registry::AppRegistry;
+
+use crate::handler::damage::{
+    resolve_damage_report, show_damage_photo, show_damage_report, show_damage_report_list,
+    upload_damage_photo,
+};
+
+pub fn build_damage_report_router() -> Router<AppRegistry> {
+    let routers = Router::new()
+        .route("/", get(show_damage_report_list))
+        .route("/:damage_report_id", get(show_damage_report))
+        .route(
+            "/:damage_report_id/photo",
+            get(show_damage_photo)
+                .put(upload_damage_photo)
+                .layer(DefaultBodyLimit::max(MAX_DAMAGE_PHOTO_BYTES)),
+        )
+        .route("/:damage_report_id/resolve", post(resolve_damage_report));
+
+    Router::new().nest("/damage-reports", routers)
+}
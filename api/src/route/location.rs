@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use registry::AppRegistry;
+
+use crate::handler::location::{
+    delete_location, list_locations, register_location, update_location,
+};
+
+pub fn build_location_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/locations", get(list_locations).post(register_location))
+        .route(
+            "/locations/:location_id",
+            put(update_location).delete(delete_location),
+        )
+}
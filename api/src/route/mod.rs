@@ -1,5 +1,10 @@
 pub mod auth;
+pub mod author;
 pub mod book;
+pub mod damage;
 pub mod health;
+pub mod location;
+pub mod series;
+pub mod tag;
 pub mod user;
 pub mod v1;
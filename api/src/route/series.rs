@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+use registry::AppRegistry;
+
+use crate::handler::series::show_series;
+
+pub fn build_series_router() -> Router<AppRegistry> {
+    Router::new().route("/series/:series_id", get(show_series))
+}
@@ -0,0 +1,13 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+use registry::AppRegistry;
+
+use crate::handler::tag::{delete_tag, list_tags, register_tag, update_tag};
+
+pub fn build_tag_router() -> Router<AppRegistry> {
+    Router::new()
+        .route("/tags", get(list_tags).post(register_tag))
+        .route("/tags/:tag_id", put(update_tag).delete(delete_tag))
+}
@@ -2,9 +2,13 @@ use axum::routing::{delete, get, put};
 use axum::Router;
 use registry::AppRegistry;
 
-use crate::handler::user::{
-    change_password, change_role, delete_user, get_checkouts, get_current_user, list_users,
-    register_user,
+use crate::handler::{
+    recommendation::show_recommendations,
+    transfer::show_incoming_transfers,
+    user::{
+        change_password, change_role, delete_user, get_checkouts, get_current_user, list_users,
+        register_user,
+    },
 };
 
 // me がパスに入っているリクエストはリクエストを送る自分自身しかできないという設計
@@ -15,6 +19,8 @@ pub fn build_user_router() -> Router<AppRegistry> {
         .route("/users/me", get(get_current_user))
         .route("/users/me/password", put(change_password))
         .route("/users/me/checkouts", get(get_checkouts))
+        .route("/users/me/transfers", get(show_incoming_transfers))
+        .route("/users/me/recommendations", get(show_recommendations))
         .route("/users", get(list_users).post(register_user))
         .route("/users/:user_id", delete(delete_user))
         .route("/users/:user_id/role", put(change_role))
@@ -1,13 +1,22 @@
 use axum::Router;
 use registry::AppRegistry;
 
-use super::{book::build_book_routers, health::build_health_check_routes, user::build_user_router};
+use super::{
+    author::build_author_router, book::build_book_routers, damage::build_damage_report_router,
+    health::build_health_check_routes, location::build_location_router,
+    series::build_series_router, tag::build_tag_router, user::build_user_router,
+};
 
 pub fn routes() -> Router<AppRegistry> {
     let router = Router::new()
         .merge(build_health_check_routes())
         .merge(build_book_routers())
-        .merge(build_user_router());
+        .merge(build_user_router())
+        .merge(build_tag_router())
+        .merge(build_author_router())
+        .merge(build_series_router())
+        .merge(build_location_router())
+        .merge(build_damage_report_router());
 
     Router::new().nest("/api/v1", router)
 }
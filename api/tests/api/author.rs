@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use api::model::{
+    author::{AuthorRole, AuthorsResponse},
+    book::PaginatedBookResponse,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use kernel::{
+    model::{
+        author::{self, Author, BookAuthor},
+        book::Book,
+        id::{AuthorId, BookId, UserId},
+        list::PaginatedList,
+        user::BookOwner,
+    },
+    repository::{author::MockAuthorRepository, book::MockBookRepository},
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+fn make_book(author_id: AuthorId) -> Book {
+    Book {
+        id: BookId::new(),
+        title: "プログラミング言語Rust".to_string(),
+        isbn: "9784048930703".to_string(),
+        author: "Steve Klabnik, Carol Nichols, 尾崎亮太 (訳)".to_string(),
+        authors: vec![
+            BookAuthor {
+                id: AuthorId::new(),
+                name: "Steve Klabnik".to_string(),
+                role: author::AuthorRole::Author,
+            },
+            BookAuthor {
+                id: AuthorId::new(),
+                name: "Carol Nichols".to_string(),
+                role: author::AuthorRole::Author,
+            },
+            BookAuthor {
+                id: author_id,
+                name: "尾崎亮太".to_string(),
+                role: author::AuthorRole::Translator,
+            },
+        ],
+        description: "".to_string(),
+        publisher: None,
+        published_year: None,
+        owner: BookOwner {
+            id: UserId::new(),
+            name: "Yuki Toyoda".to_string(),
+        },
+        total_copies: 1,
+        available_copies: 1,
+        checkouts: vec![],
+        tags: vec![],
+        series: None,
+        has_cover: false,
+        average_rating: None,
+        review_count: 0,
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn suggest_authors_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_author_repository().returning(|| {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_suggest()
+            .withf(|keyword, limit| keyword == "高野" && *limit == 10)
+            .returning(|_, _| {
+                Ok(vec![Author {
+                    id: AuthorId::new(),
+                    name: "高野祐輝".into(),
+                }])
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/authors?keyword=%E9%AB%98%E9%87%8E"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, AuthorsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].name, "高野祐輝");
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/authors")]
+#[case("/authors?keyword=")]
+#[case("/authors?keyword=a&limit=0")]
+#[case("/authors?keyword=a&limit=51")]
+#[tokio::test]
+async fn suggest_authors_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("", None)]
+#[case("?role=translator", Some(author::AuthorRole::Translator))]
+#[tokio::test]
+async fn show_author_books_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_role: Option<author::AuthorRole>,
+) -> anyhow::Result<()> {
+    let author_id = AuthorId::new();
+    fixture.expect_author_repository().returning(move || {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_find_by_id()
+            .withf(move |id| *id == author_id)
+            .returning(move |id| {
+                Ok(Some(Author {
+                    id,
+                    name: "尾崎亮太".into(),
+                }))
+            });
+        Arc::new(mock)
+    });
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.author_id == Some(author_id) && opt.author_role == expected_role)
+            .returning(move |opt| {
+                Ok(PaginatedList {
+                    total: 1,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![make_book(author_id)],
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/authors/{author_id}/books{query}");
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // 蔵書ごとに、著者の一覧が役割とともに並び順どおりに返る
+    let result = deserialize_json!(resp, PaginatedBookResponse);
+    assert_eq!(result.total, 1);
+    let authors = &result.items[0].authors;
+    assert_eq!(authors.len(), 3);
+    assert_eq!(authors[2].id, author_id);
+    assert_eq!(authors[2].role, AuthorRole::Translator);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_author_books_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_author_repository().returning(|| {
+        let mut mock = MockAuthorRepository::new();
+        mock.expect_find_by_id().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/authors/{}/books", AuthorId::new());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_book_with_authors_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_duplicates().returning(|_| Ok(vec![]));
+        mock.expect_create()
+            .withf(|event, _| {
+                event.authors.iter().map(|a| (a.name.as_str(), a.role)).eq([
+                    ("Steve Klabnik", author::AuthorRole::Author),
+                    ("尾崎亮太", author::AuthorRole::Translator),
+                ])
+            })
+            .returning(|_, _| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    // 役割を省略した場合は著者として扱い、名前の前後の空白は取り除く
+    let body = serde_json::json!({
+        "title": "プログラミング言語Rust",
+        "authors": [
+            { "name": "Steve Klabnik" },
+            { "name": " 尾崎亮太 ", "role": "translator" },
+        ],
+        "isbn": "9784048930703",
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(serde_json::json!([]))]
+#[case(serde_json::json!([{ "name": "" }]))]
+#[tokio::test]
+async fn register_book_without_authors_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] authors: serde_json::Value,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "プログラミング言語Rust",
+        "authors": authors,
+        "isbn": "9784048930703",
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
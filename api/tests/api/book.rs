@@ -1,22 +1,28 @@
 use std::sync::Arc;
 
-use api::model::book::PaginatedBookResponse;
-use axum::{body::Body, http::Request};
+use api::model::book::{CursorPaginatedBookResponse, PaginatedBookResponse};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
 use kernel::{
     model::{
-        book::Book,
-        id::{BookId, UserId},
-        list::PaginatedList,
+        book::{
+            duplicate::{DuplicateCandidate, DuplicateReason},
+            Book, BookAvailability, BookSortKey,
+        },
+        id::{BookId, TagId, UserId},
+        list::{Cursor, CursorPaginatedList, PageCursor, PaginatedList, SortOrder},
         user::BookOwner,
     },
-    repository::book::MockBookRepository,
+    repository::{book::MockBookRepository, cover::MockCoverImageRepository},
 };
 use rstest::rstest;
 use tower::ServiceExt;
 
 use crate::{
     deserialize_json,
-    helper::{fixture, make_router, v1, TestRequestExt},
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
 };
 
 #[rstest]
@@ -44,12 +50,24 @@ async fn show_book_list_with_query_200(
                 title: "RustによるWebアプリケーション開発".to_string(),
                 isbn: "".to_string(),
                 author: "Yuki Toyoda".to_string(),
+                authors: vec![],
                 description: "RustによるWebアプリケーション開発".to_string(),
+                publisher: None,
+                published_year: None,
                 owner: BookOwner {
                     id: UserId::new(),
                     name: "Yuki Toyoda".to_string(),
                 },
-                checkout: None,
+                total_copies: 1,
+                available_copies: 1,
+                checkouts: vec![],
+                tags: vec![],
+                series: None,
+                has_cover: true,
+                average_rating: None,
+                review_count: 0,
+                updated_at: chrono::Utc::now(),
+                deleted_at: None,
             }];
 
             Ok(PaginatedList {
@@ -75,13 +93,28 @@ async fn show_book_list_with_query_200(
     let result = deserialize_json!(resp, PaginatedBookResponse);
     assert_eq!(result.limit, expected_limit);
     assert_eq!(result.offset, expected_offset);
+    assert_eq!(
+        result.items[0].cover_url.as_deref(),
+        Some(format!("/api/v1/books/{}/cover", book_id.raw()).as_str())
+    );
 
     Ok(())
 }
 
 #[rstest]
 #[case("/books?limit=-1")]
+#[case("/books?limit=101")]
+#[case("/books?paging=cursor&limit=9223372036854775807")]
 #[case("/books?offset=aaa")]
+#[case("/books?sort=price")]
+#[case("/books?order=up")]
+#[case("/books?availability=lost")]
+#[case("/books?owner=not-a-uuid")]
+#[case("/books?isbn=aaa")]
+#[case("/books?tags=not-a-uuid")]
+#[case("/books?paging=page")]
+#[case("/books?after=!!!")]
+#[case("/books?after=YQ&before=YQ")]
 #[tokio::test]
 async fn show_book_list_with_query_400(
     mut fixture: registry::MockAppRegistryExt,
@@ -97,12 +130,24 @@ async fn show_book_list_with_query_400(
                 title: "RustによるWebアプリケーション開発".to_string(),
                 isbn: "".to_string(),
                 author: "Yuki Toyoda".to_string(),
+                authors: vec![],
                 description: "RustによるWebアプリケーション開発".to_string(),
+                publisher: None,
+                published_year: None,
                 owner: BookOwner {
                     id: UserId::new(),
                     name: "Yuki Toyoda".to_string(),
                 },
-                checkout: None,
+                total_copies: 1,
+                available_copies: 1,
+                checkouts: vec![],
+                tags: vec![],
+                series: None,
+                has_cover: false,
+                average_rating: None,
+                review_count: 0,
+                updated_at: chrono::Utc::now(),
+                deleted_at: None,
             }];
             Ok(PaginatedList {
                 total: 1,
@@ -125,3 +170,681 @@ async fn show_book_list_with_query_400(
 
     Ok(())
 }
+
+#[rstest]
+#[case("/books?keyword=Rust", Some("Rust"))]
+#[case("/books?keyword=%E5%85%A5%E9%96%80%20Rust", Some("入門 Rust"))]
+#[case("/books?keyword=", None)]
+#[case("/books", None)]
+#[tokio::test]
+async fn show_book_list_with_keyword_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_keyword: Option<&'static str>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.keyword.as_deref() == expected_keyword)
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_list_with_conditions_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let owner = UserId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| {
+                opt.owner == Some(owner)
+                    && opt.author.as_deref() == Some("Yuki")
+                    && opt.availability == Some(BookAvailability::CheckedOut)
+                    && opt.sort == Some(BookSortKey::UpdatedAt)
+                    && opt.order == Some(SortOrder::Asc)
+            })
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books?owner={}&author=Yuki&availability=checkedOut&sort=updatedAt&order=asc",
+        owner.raw()
+    );
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?paging=cursor&limit=10", None)]
+#[case("/books?after=dGl0bGU6YXNj.YQ.Yg", Some(PageCursor::After(Cursor { order_by: "title:asc".into(), keys: vec!["a".into(), "b".into()] })))]
+#[case("/books?before=dGl0bGU6YXNj.YQ.Yg", Some(PageCursor::Before(Cursor { order_by: "title:asc".into(), keys: vec!["a".into(), "b".into()] })))]
+#[tokio::test]
+async fn show_book_list_by_cursor_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] expected_cursor: Option<PageCursor>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected_cursor = expected_cursor.clone();
+        mock.expect_find_all_by_cursor()
+            .withf(move |_, cursor| cursor == &expected_cursor)
+            .returning(|opt, _| {
+                Ok(CursorPaginatedList {
+                    limit: opt.limit,
+                    items: vec![],
+                    next_cursor: Some(Cursor {
+                        order_by: "title:asc".into(),
+                        keys: vec!["a".into(), "b".into()],
+                    }),
+                    prev_cursor: None,
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, CursorPaginatedBookResponse);
+    assert_eq!(result.next_cursor.as_deref(), Some("dGl0bGU6YXNj.YQ.Yg"));
+    assert!(result.prev_cursor.is_none());
+
+    Ok(())
+}
+
+// base64 としては正しいが、日時のキーが日時として解釈できないカーソル
+#[rstest]
+#[tokio::test]
+async fn show_book_list_by_tampered_cursor_400(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all_by_cursor()
+            .withf(|_, cursor| matches!(cursor, Some(PageCursor::After(c)) if c.keys[0] == "abc"))
+            .returning(|_, _| {
+                Err(shared::error::AppError::InvalidCursorError(
+                    "不正なカーソルです。".into(),
+                ))
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(
+        "/books?after=Y3JlYXRlZF9hdDpkZXNj.YWJj.OTg5MDczNmUtYTRlNC00NjFhLWE3N2QtZWFjMzUxN2VmMTFi",
+    ))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("4-7980-6170-0")]
+#[case("978-4-7980-6170-2")]
+#[case("9784798061702")]
+#[tokio::test]
+async fn register_book_normalizes_isbn_201(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_duplicates().returning(|_| Ok(vec![]));
+        mock.expect_create()
+            .withf(|event, _| event.isbn.as_str() == "9784798061702")
+            .returning(|_, _| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "authors": [{ "name": "初田直也他" }],
+        "isbn": isbn,
+        "description": "",
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("aaa")]
+#[case("")]
+#[case("978-4-7980-6170-3")]
+#[case("4-7980-6170-X")]
+#[case("123-4-7980-6170-2")]
+#[tokio::test]
+async fn register_book_with_invalid_isbn_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "authors": [{ "name": "初田直也他" }],
+        "isbn": isbn,
+        "description": "",
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    // どの項目に不備があったかがレスポンスに含まれる
+    let result = deserialize_json!(resp, serde_json::Value);
+    assert_eq!(result["errors"][0]["field"], "isbn");
+
+    Ok(())
+}
+
+#[rstest]
+#[case(false, DuplicateReason::TitleAndAuthor, StatusCode::CONFLICT)]
+#[case(true, DuplicateReason::TitleAndAuthor, StatusCode::CREATED)]
+#[case(false, DuplicateReason::Isbn, StatusCode::CONFLICT)]
+#[case(true, DuplicateReason::Isbn, StatusCode::CONFLICT)]
+#[tokio::test]
+async fn register_book_with_duplicates(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] force: bool,
+    #[case] reason: DuplicateReason,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    // 重複の確認と登録で同じモックを使い、登録が呼ばれた回数を確かめる
+    let mut mock = MockBookRepository::new();
+    mock.expect_find_duplicates().returning(move |_| {
+        Ok(vec![DuplicateCandidate {
+            book_id,
+            title: "実践Rustプログラミング入門 第2版".into(),
+            author: "初田直也他".into(),
+            isbn: "9784798061702".into(),
+            reason,
+        }])
+    });
+    // 同じ ISBN の蔵書がある場合は、force を指定しても登録しない
+    mock.expect_create()
+        .times(usize::from(expected == StatusCode::CREATED))
+        .returning(|_, _| Ok(()));
+    let mock = Arc::new(mock);
+    fixture
+        .expect_book_repository()
+        .returning(move || mock.clone());
+
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "authors": [{ "name": "初田直也他" }],
+        "isbn": "9784798061702",
+        "force": force,
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    // 登録しなかった場合は重複の候補を返す
+    if expected == StatusCode::CONFLICT {
+        let result = deserialize_json!(resp, serde_json::Value);
+        assert_eq!(result["candidates"][0]["bookId"], book_id.to_string());
+        assert_eq!(
+            result["candidates"][0]["reason"],
+            match reason {
+                DuplicateReason::Isbn => "isbn",
+                DuplicateReason::TitleAndAuthor => "titleAndAuthor",
+            }
+        );
+    }
+
+    Ok(())
+}
+
+#[rstest]
+#[case(serde_json::json!({ "publishedYear": 0 }))]
+#[case(serde_json::json!({ "publishedYear": 10000 }))]
+#[case(serde_json::json!({ "publisher": "" }))]
+#[tokio::test]
+async fn register_book_with_invalid_publication_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] publication: serde_json::Value,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let mut body = serde_json::json!({
+        "title": "実践Rustプログラミング入門",
+        "authors": [{ "name": "初田直也他" }],
+        "isbn": "9784798061702",
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(publication.as_object().unwrap().clone());
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books?isbn=4-7980-6170-0")]
+#[case("/books?isbn=9784798061702")]
+#[tokio::test]
+async fn show_book_list_with_isbn_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(|opt| opt.isbn.as_ref().map(|i| i.as_str()) == Some("9784798061702"))
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_list_with_tags_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let tags = vec![TagId::new(), TagId::new()];
+    let expected = tags.clone();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected = expected.clone();
+        mock.expect_find_all()
+            .withf(move |opt| opt.tags == expected)
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books?tags={},{}", tags[0].raw(), tags[1].raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_deleted_book_list_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let deleted_at = chrono::Utc::now();
+
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_deleted()
+            .withf(|limit, offset| *limit == 20 && *offset == 0)
+            .returning(move |limit, offset| {
+                Ok(PaginatedList {
+                    total: 1,
+                    limit,
+                    offset,
+                    items: vec![Book {
+                        id: book_id,
+                        title: "RustによるWebアプリケーション開発".to_string(),
+                        isbn: "".to_string(),
+                        author: "Yuki Toyoda".to_string(),
+                        authors: vec![],
+                        description: "".to_string(),
+                        publisher: None,
+                        published_year: None,
+                        owner: BookOwner {
+                            id: UserId::new(),
+                            name: "Yuki Toyoda".to_string(),
+                        },
+                        total_copies: 1,
+                        available_copies: 1,
+                        checkouts: vec![],
+                        tags: vec![],
+                        series: None,
+                        has_cover: false,
+                        average_rating: None,
+                        review_count: 0,
+                        updated_at: chrono::Utc::now(),
+                        deleted_at: Some(deleted_at),
+                    }],
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::get(&v1("/books/deleted"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedBookResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].id, book_id);
+    assert!(result.items[0].deleted_at.is_some());
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Request::get(v1("/books/deleted")))]
+#[case(Request::post(v1(&format!("/books/{}/restore", BookId::new()))))]
+#[case(Request::delete(v1(&format!("/books/{}/purge", BookId::new()))))]
+#[tokio::test]
+async fn deleted_book_operations_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] req: axum::http::request::Builder,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_book_repository()
+        .returning(|| Arc::new(MockBookRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let resp = app.oneshot(req.bearer().body(Body::empty())?).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn purge_book_204(mut fixture_admin: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture_admin.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_purge()
+            .withf(move |event| event.book_id == book_id)
+            .times(1)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    // 完全に削除した蔵書の表紙画像も削除される
+    fixture_admin
+        .expect_cover_image_repository()
+        .returning(move || {
+            let mut mock = MockCoverImageRepository::new();
+            mock.expect_purge()
+                .withf(move |id| *id == book_id)
+                .times(1)
+                .returning(|_| Ok(()));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::delete(&v1(&format!("/books/{book_id}/purge")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+fn make_book(book_id: BookId, updated_at: chrono::DateTime<chrono::Utc>) -> Book {
+    Book {
+        id: book_id,
+        title: "RustによるWebアプリケーション開発".to_string(),
+        isbn: "9784065369579".to_string(),
+        author: "Yuki Toyoda".to_string(),
+        authors: vec![],
+        description: "".to_string(),
+        publisher: None,
+        published_year: None,
+        owner: BookOwner {
+            id: "5b4c96ac-316a-4bee-8e69-cac5eb84ff4c".parse().unwrap(),
+            name: "Yuki Toyoda".to_string(),
+        },
+        total_copies: 1,
+        available_copies: 1,
+        checkouts: vec![],
+        tags: vec![],
+        series: None,
+        has_cover: false,
+        average_rating: None,
+        review_count: 0,
+        updated_at,
+        deleted_at: None,
+    }
+}
+
+#[rstest]
+#[case(None, axum::http::StatusCode::OK)]
+#[case(Some("{etag}"), axum::http::StatusCode::NOT_MODIFIED)]
+#[case(Some("W/{etag}"), axum::http::StatusCode::NOT_MODIFIED)]
+#[case(Some(r#""1", {etag}"#), axum::http::StatusCode::NOT_MODIFIED)]
+#[case(Some("*"), axum::http::StatusCode::NOT_MODIFIED)]
+#[case(Some(r#""1700000000123""#), axum::http::StatusCode::OK)]
+#[case(Some(r#""1700000000000""#), axum::http::StatusCode::OK)]
+#[tokio::test]
+async fn show_book_with_etag(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] if_none_match: Option<&str>,
+    #[case] expected: axum::http::StatusCode,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let updated_at = chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id()
+            .returning(move |id| Ok(Some(make_book(id, updated_at))));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = v1(&format!("/books/{book_id}"));
+    let resp = app
+        .clone()
+        .oneshot(Request::get(&path).bearer().body(Body::empty())?)
+        .await?;
+    let etag = resp.headers()["ETag"].to_str()?.to_string();
+    assert!(etag.starts_with(r#""1700000000123-"#));
+
+    let mut req = Request::get(&path).bearer();
+    if let Some(if_none_match) = if_none_match {
+        req = req.header("If-None-Match", if_none_match.replace("{etag}", &etag));
+    }
+    let resp = app.oneshot(req.body(Body::empty())?).await?;
+    assert_eq!(resp.status(), expected);
+    assert_eq!(resp.headers()["ETag"], etag.as_str());
+
+    Ok(())
+}
+
+// 貸出などで蔵書の最終更新日時が変わらなくても、レスポンスの内容が変われば ETag も変わる
+#[rstest]
+#[tokio::test]
+async fn show_book_etag_changes_with_checkouts(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let updated_at = chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+
+    let mut available_copies = 1;
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let copies = available_copies;
+        mock.expect_find_by_id().returning(move |id| {
+            Ok(Some(Book {
+                available_copies: copies,
+                ..make_book(id, updated_at)
+            }))
+        });
+        available_copies -= 1;
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = v1(&format!("/books/{book_id}"));
+    let resp = app
+        .clone()
+        .oneshot(Request::get(&path).bearer().body(Body::empty())?)
+        .await?;
+    let etag = resp.headers()["ETag"].to_str()?.to_string();
+
+    let req = Request::get(&path)
+        .bearer()
+        .header("If-None-Match", &etag)
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+    assert_ne!(resp.headers()["ETag"], etag.as_str());
+
+    Ok(())
+}
+
+#[rstest]
+#[case(None, None)]
+#[case(Some("*"), None)]
+#[case(Some(r#""1700000000123""#), Some(vec![1_700_000_000_123]))]
+#[case(Some(r#""1700000000123-0123456789abcdef""#), Some(vec![1_700_000_000_123]))]
+#[case(Some(r#""1", W/"2", "3""#), Some(vec![1, 3]))]
+#[tokio::test]
+async fn update_book_with_if_match(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] if_match: Option<&'static str>,
+    #[case] expected: Option<Vec<i64>>,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let expected = expected.map(|v| {
+        v.into_iter()
+            .filter_map(chrono::DateTime::from_timestamp_millis)
+            .collect::<Vec<_>>()
+    });
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected = expected.clone();
+        mock.expect_update()
+            .withf(move |event| event.expected_updated_at == expected)
+            .times(1)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::put(&v1(&format!("/books/{book_id}")))
+        .bearer()
+        .application_json();
+    if let Some(if_match) = if_match {
+        req = req.header("If-Match", if_match);
+    }
+    let resp = app
+        .oneshot(req.body(Body::from(
+            r#"{"title":"Rust","authors":[{"name":"Yuki Toyoda"}],"isbn":"9784065369579","description":""}"#,
+        ))?)
+        .await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn delete_book_with_stale_etag_412(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_delete().returning(|_| {
+            Err(shared::error::AppError::PreconditionFailed(
+                "specified book has been modified".into(),
+            ))
+        });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::delete(&v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .header("If-Match", r#""1700000000000""#)
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::PRECONDITION_FAILED);
+
+    Ok(())
+}
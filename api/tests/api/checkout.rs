@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use kernel::{
+    model::{
+        checkout::{Checkout, CheckoutBook, CheckoutRenewal},
+        id::{BookId, CheckoutId, CopyId, UserId},
+        list::CursorPaginatedList,
+    },
+    repository::checkout::MockCheckoutRepository,
+};
+use rstest::rstest;
+use shared::error::AppError;
+use tower::ServiceExt;
+
+use crate::helper::{fixture, fixture_admin, make_router, v1, TestRequestExt};
+
+// days 日前に貸し出した、貸出期間 14 日の貸出
+fn checkout(days: i64) -> Checkout {
+    let checked_out_at = Utc::now() - Duration::days(days);
+    Checkout {
+        id: CheckoutId::new(),
+        checked_out_by: UserId::new(),
+        checked_out_at,
+        due_at: checked_out_at + Duration::days(14),
+        returned_at: None,
+        book: CheckoutBook {
+            book_id: BookId::new(),
+            copy_id: Some(CopyId::new()),
+            barcode: Some("0001".into()),
+            title: "Rust によるWebアプリケーション開発".into(),
+            author: "Yuki Toyoda".into(),
+            isbn: "9784065369579".into(),
+        },
+        renewals: vec![],
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_checked_out_list_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_checkout_repository().returning(|| {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_find_unreturned_all()
+            .returning(|| Ok(vec![checkout(20), checkout(1)]));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/checkouts"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let result = serde_json::from_slice::<serde_json::Value>(&bytes)?;
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert!(items[0]["dueAt"].is_string());
+    assert_eq!(items[0]["overdue"], true);
+    assert_eq!(items[1]["overdue"], false);
+    assert_eq!(items[0]["renewals"], serde_json::json!([]));
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books/checkouts?overdue=true")]
+#[case("/books/checkouts?overdue=true&limit=10")]
+#[tokio::test]
+async fn show_overdue_checkouts_as_admin_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    fixture_admin.expect_checkout_repository().returning(|| {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_find_overdue_all()
+            .returning(|_| Ok(vec![checkout(20)]));
+        mock.expect_find_overdue_all_by_cursor()
+            .withf(|_, options| options.limit == 10)
+            .returning(|_, options| {
+                Ok(CursorPaginatedList {
+                    limit: options.limit,
+                    items: vec![checkout(20)],
+                    next_cursor: None,
+                    prev_cursor: None,
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let result = serde_json::from_slice::<serde_json::Value>(&bytes)?;
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["overdue"], true);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books/checkouts?limit=101")]
+#[case("/books/checkouts?limit=9223372036854775807")]
+#[tokio::test]
+async fn show_checked_out_list_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_overdue_checkouts_as_user_403(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/checkouts?overdue=true"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn renew_checkout_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let (book_id, checkout_id) = (BookId::new(), CheckoutId::new());
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_renew()
+            .withf(move |event| event.book_id == book_id && event.checkout_id == checkout_id)
+            .returning(|event| {
+                let previous_due_at = event.renewed_at + Duration::days(3);
+                Ok(CheckoutRenewal {
+                    renewed_at: event.renewed_at,
+                    previous_due_at,
+                    due_at: previous_due_at + Duration::days(14),
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{book_id}/checkouts/{checkout_id}/renewals");
+    let req = Request::post(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    let result = serde_json::from_slice::<serde_json::Value>(&bytes)?;
+    assert!(result["renewedAt"].is_string());
+    assert!(result["previousDueAt"].is_string());
+    assert!(result["dueAt"].is_string());
+
+    Ok(())
+}
+
+#[rstest]
+#[case(|| AppError::EntityNotFound("not found".into()), StatusCode::NOT_FOUND)]
+#[case(
+    || AppError::UnprocessableEntiry("renewal limit".into()),
+    StatusCode::UNPROCESSABLE_ENTITY
+)]
+#[tokio::test]
+async fn renew_checkout_error(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] error: fn() -> AppError,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_renew().returning(move |_| Err(error()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/checkouts/{}/renewals",
+        BookId::new(),
+        CheckoutId::new()
+    );
+    let req = Request::post(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn hold_book_201(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_create_hold()
+            .withf(move |event| event.book_id == book_id)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{book_id}/holds");
+    let req = Request::post(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(|| Ok(()), StatusCode::NO_CONTENT)]
+#[case(|| Err(AppError::EntityNotFound("not found".into())), StatusCode::NOT_FOUND)]
+#[tokio::test]
+async fn cancel_hold(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] result: fn() -> shared::error::AppResult<()>,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_delete_hold().returning(move |_| result());
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/holds", BookId::new());
+    let req = Request::delete(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
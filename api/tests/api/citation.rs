@@ -0,0 +1,307 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use kernel::{
+    model::{
+        author::{AuthorRole, BookAuthor},
+        book::Book,
+        id::{AuthorId, BookId, SeriesId, UserId},
+        series::BookSeries,
+        user::BookOwner,
+    },
+    repository::book::MockBookRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::helper::{fixture, make_router, v1, TestRequestExt};
+
+fn credit(name: &str, role: AuthorRole) -> BookAuthor {
+    BookAuthor {
+        id: AuthorId::new(),
+        name: name.into(),
+        role,
+    }
+}
+
+// 出版社・出版年・シリーズが登録された蔵書
+fn translated_book(book_id: BookId) -> Book {
+    Book {
+        id: book_id,
+        title: "プログラミング言語Rust & 100%".into(),
+        author: "Steve Klabnik, 尾崎亮太 (訳)".into(),
+        authors: vec![
+            credit("Steve Klabnik", AuthorRole::Author),
+            credit("Carol Nichols", AuthorRole::Author),
+            credit("尾崎亮太", AuthorRole::Translator),
+        ],
+        isbn: "9784048930703".into(),
+        description: "公式ドキュメントの日本語訳\n第2版".into(),
+        publisher: Some("KADOKAWA".into()),
+        published_year: Some(2019),
+        owner: BookOwner {
+            id: UserId::new(),
+            name: "所有者".into(),
+        },
+        total_copies: 1,
+        available_copies: 1,
+        checkouts: vec![],
+        tags: vec![],
+        series: Some(BookSeries {
+            id: SeriesId::new(),
+            title: "Rust シリーズ".into(),
+            volume: 2,
+        }),
+        has_cover: false,
+        average_rating: None,
+        review_count: 0,
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+    }
+}
+
+// 出版社・出版年が登録されていない蔵書
+fn plain_book(book_id: BookId) -> Book {
+    Book {
+        id: book_id,
+        title: "実践Rustプログラミング入門".into(),
+        author: "初田直也他".into(),
+        authors: vec![credit("初田直也他", AuthorRole::Author)],
+        isbn: "9784798061702".into(),
+        description: "".into(),
+        publisher: None,
+        published_year: None,
+        owner: BookOwner {
+            id: UserId::new(),
+            name: "所有者".into(),
+        },
+        total_copies: 1,
+        available_copies: 1,
+        checkouts: vec![],
+        tags: vec![],
+        series: None,
+        has_cover: false,
+        average_rating: None,
+        review_count: 0,
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+    }
+}
+
+async fn read_text(resp: Response) -> anyhow::Result<String> {
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await?;
+    Ok(String::from_utf8(bytes.to_vec())?)
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some("*/*"))]
+#[case(Some("text/html, application/x-bibtex;q=0.9"))]
+#[tokio::test]
+async fn show_book_citation_bibtex_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] accept: Option<&'static str>,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id()
+            .returning(move |id| Ok(Some(translated_book(id))));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::get(&v1(&format!("/books/{book_id}/citation"))).bearer();
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+    let resp = app.oneshot(req.body(Body::empty())?).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/x-bibtex; charset=utf-8"
+    );
+
+    let body = read_text(resp).await?;
+    assert!(body.starts_with(&format!("@book{{{book_id},\n")));
+    // 特別な意味を持つ文字はエスケープする
+    assert!(body.contains("  title = {プログラミング言語Rust \\& 100\\%},\n"));
+    assert!(body.contains("  author = {Steve Klabnik and Carol Nichols},\n"));
+    assert!(body.contains("  translator = {尾崎亮太},\n"));
+    assert!(body.contains("  publisher = {KADOKAWA},\n"));
+    assert!(body.contains("  year = {2019},\n"));
+    assert!(body.contains("  series = {Rust シリーズ},\n"));
+    assert!(body.contains("  volume = {2},\n"));
+    assert!(!body.contains("editor"));
+
+    Ok(())
+}
+
+#[rstest]
+#[case("?format=ris", None)]
+#[case("", Some("application/x-research-info-systems"))]
+// format の指定は Accept ヘッダーより優先する
+#[case("?format=ris", Some("application/x-bibtex"))]
+#[tokio::test]
+async fn show_book_citation_ris_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] accept: Option<&'static str>,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_by_id()
+            .returning(move |id| Ok(Some(translated_book(id))));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::get(&v1(&format!("/books/{book_id}/citation{query}"))).bearer();
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+    let resp = app.oneshot(req.body(Body::empty())?).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/x-research-info-systems; charset=utf-8"
+    );
+
+    let body = read_text(resp).await?;
+    assert_eq!(
+        body.split("\r\n").collect::<Vec<_>>(),
+        vec![
+            "TY  - BOOK",
+            "TI  - プログラミング言語Rust & 100%",
+            "AU  - Steve Klabnik",
+            "AU  - Carol Nichols",
+            "A4  - 尾崎亮太",
+            "PB  - KADOKAWA",
+            "PY  - 2019",
+            "T2  - Rust シリーズ",
+            "VL  - 2",
+            "SN  - 9784048930703",
+            // 改行は空白に置き換える
+            "AB  - 公式ドキュメントの日本語訳 第2版",
+            "ER  - ",
+            "",
+        ]
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_citations_csl_json_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let (first, second) = (BookId::new(), BookId::new());
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all_by_ids()
+            .withf(move |ids| ids == [second, first])
+            .returning(move |_| Ok(vec![plain_book(second), translated_book(first)]));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(&format!("/books/citations?ids={second},{first}")))
+        .bearer()
+        .header(header::ACCEPT, "application/vnd.citationstyles.csl+json")
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "application/vnd.citationstyles.csl+json"
+    );
+
+    let body = read_text(resp).await?;
+    let items = serde_json::from_str::<serde_json::Value>(&body)?;
+    // 登録されていない項目は出力しない
+    assert_eq!(
+        items[0],
+        serde_json::json!({
+            "id": second.to_string(),
+            "type": "book",
+            "title": "実践Rustプログラミング入門",
+            "author": [{ "literal": "初田直也他" }],
+            "ISBN": "9784798061702",
+        })
+    );
+    assert_eq!(items[1]["id"], first.to_string());
+    assert_eq!(items[1]["publisher"], "KADOKAWA");
+    assert_eq!(
+        items[1]["issued"],
+        serde_json::json!({ "date-parts": [[2019]] })
+    );
+    assert_eq!(items[1]["collection-title"], "Rust シリーズ");
+    assert_eq!(items[1]["volume"], 2);
+    assert_eq!(
+        items[1]["translator"],
+        serde_json::json!([{ "literal": "尾崎亮太" }])
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_citations_with_missing_book_404(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let (first, second) = (BookId::new(), BookId::new());
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all_by_ids()
+            .returning(move |_| Ok(vec![plain_book(first)]));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(&format!("/books/citations?ids={first},{second}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("/books/citations?ids=", None, StatusCode::BAD_REQUEST)]
+#[case("/books/citations?format=ris", None, StatusCode::BAD_REQUEST)]
+#[case(
+    "/books/citations?ids=9890736e-a4e4-461a-a77d-eac3517ef11b&format=pdf",
+    None,
+    StatusCode::BAD_REQUEST
+)]
+#[case(
+    "/books/citations?ids=9890736e-a4e4-461a-a77d-eac3517ef11b",
+    Some("text/html"),
+    StatusCode::NOT_ACCEPTABLE
+)]
+#[tokio::test]
+async fn show_book_citations_with_invalid_request(
+    fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+    #[case] accept: Option<&'static str>,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::get(&v1(path)).bearer();
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+    let resp = app.oneshot(req.body(Body::empty())?).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use kernel::{
+    model::{
+        cover::{CoverImage, CoverImageFormat, CoverImageSize, MAX_COVER_IMAGE_BYTES},
+        id::BookId,
+    },
+    repository::cover::MockCoverImageRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::helper::{fixture, make_router, v1, TestRequestExt};
+
+#[rstest]
+#[tokio::test]
+async fn upload_book_cover_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_cover_image_repository().returning(move || {
+        let mut mock = MockCoverImageRepository::new();
+        mock.expect_store()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.format == CoverImageFormat::Png
+                    && event.data == b"dummy"
+            })
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(&v1(&format!("/books/{}/cover", book_id.raw())))
+        .bearer()
+        .header(header::CONTENT_TYPE, "image/png")
+        .body(Body::from("dummy"))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Some("image/gif"))]
+#[case(Some("text/plain"))]
+#[case(None)]
+#[tokio::test]
+async fn upload_book_cover_415(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] content_type: Option<&str>,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_cover_image_repository()
+        .returning(|| Arc::new(MockCoverImageRepository::new()));
+
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::put(&v1(&format!("/books/{}/cover", BookId::new().raw()))).bearer();
+    if let Some(content_type) = content_type {
+        req = req.header(header::CONTENT_TYPE, content_type);
+    }
+    let resp = app.oneshot(req.body(Body::from("dummy"))?).await?;
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn upload_book_cover_413(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture
+        .expect_cover_image_repository()
+        .returning(|| Arc::new(MockCoverImageRepository::new()));
+
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(&v1(&format!("/books/{}/cover", BookId::new().raw())))
+        .bearer()
+        .header(header::CONTENT_TYPE, "image/jpeg")
+        .body(Body::from(vec![0u8; MAX_COVER_IMAGE_BYTES + 1]))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("", CoverImageSize::Original)]
+#[case("?size=original", CoverImageSize::Original)]
+#[case("?size=thumbnail", CoverImageSize::Thumbnail)]
+#[tokio::test]
+async fn show_book_cover_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &str,
+    #[case] expected_size: CoverImageSize,
+) -> anyhow::Result<()> {
+    fixture.expect_cover_image_repository().returning(move || {
+        let mut mock = MockCoverImageRepository::new();
+        mock.expect_find()
+            .withf(move |_, size| *size == expected_size)
+            .returning(|_, _| {
+                Ok(Some(CoverImage {
+                    format: CoverImageFormat::Webp,
+                    data: b"dummy".to_vec(),
+                }))
+            });
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/cover{}", BookId::new().raw(), query);
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "image/webp");
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_cover_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_cover_image_repository().returning(|| {
+        let mut mock = MockCoverImageRepository::new();
+        mock.expect_find().returning(|_, _| Ok(None));
+        Arc::new(mock)
+    });
+
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/cover", BookId::new().raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
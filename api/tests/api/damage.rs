@@ -0,0 +1,308 @@
+use std::sync::Arc;
+
+use api::model::damage::{DamageReportResponse, DamageReportStatus, DamageReportsResponse};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use kernel::{
+    model::{
+        book::CopyCondition,
+        damage::DamageReport,
+        id::{BookId, CheckoutId, CopyId, DamageReportId, UserId},
+        user::DamageReporter,
+    },
+    repository::{book::MockBookRepository, damage::MockDamageReportRepository},
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
+};
+
+fn damage_report(reported_by: UserId) -> DamageReport {
+    DamageReport {
+        id: DamageReportId::new(),
+        checkout_id: CheckoutId::new(),
+        book_id: BookId::new(),
+        book_title: "Rust によるWebアプリケーション開発".into(),
+        copy_id: CopyId::new(),
+        barcode: "0001".into(),
+        reported_by: DamageReporter {
+            id: reported_by,
+            name: "dummy-user".into(),
+        },
+        description: "表紙が破れている".into(),
+        photo_format: None,
+        reported_at: chrono::Utc::now(),
+        resolution: None,
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn report_damage_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let checkout_id = CheckoutId::new();
+
+    fixture
+        .expect_damage_report_repository()
+        .returning(move || {
+            let mut mock = MockDamageReportRepository::new();
+            mock.expect_create()
+                .withf(move |event| {
+                    event.checkout_id == checkout_id && event.description == "表紙が破れている"
+                })
+                .returning(|event| Ok(damage_report(event.reported_by)));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/checkouts/{}/damage-reports",
+        BookId::new().raw(),
+        checkout_id.raw()
+    );
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"description":" 表紙が破れている "}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, DamageReportResponse);
+    assert_eq!(result.status, DamageReportStatus::Open);
+    assert!(!result.has_photo);
+    assert_eq!(result.resolved_at, None);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn report_damage_400(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture
+        .expect_damage_report_repository()
+        .returning(|| Arc::new(MockDamageReportRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/checkouts/{}/damage-reports",
+        BookId::new().raw(),
+        CheckoutId::new().raw()
+    );
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"description":""}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[case("", kernel::model::damage::DamageReportStatus::Open)]
+#[case(
+    "?status=resolved",
+    kernel::model::damage::DamageReportStatus::Resolved
+)]
+#[tokio::test]
+async fn show_damage_report_list_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+    #[case] query: &'static str,
+    #[case] expected: kernel::model::damage::DamageReportStatus,
+) -> anyhow::Result<()> {
+    fixture_admin
+        .expect_damage_report_repository()
+        .returning(move || {
+            let mut mock = MockDamageReportRepository::new();
+            mock.expect_find_all()
+                .withf(move |status| *status == expected)
+                .returning(|_| Ok(vec![damage_report(UserId::new())]));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::get(&v1(&format!("/damage-reports{query}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, DamageReportsResponse);
+    assert_eq!(result.items.len(), 1);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_damage_report_list_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_damage_report_repository()
+        .returning(|| Arc::new(MockDamageReportRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/damage-reports"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_damage_report_by_other_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_damage_report_repository().returning(|| {
+        let mut mock = MockDamageReportRepository::new();
+        mock.expect_find_by_id()
+            .returning(|_| Ok(Some(damage_report(UserId::new()))));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/damage-reports/{}", DamageReportId::new().raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn upload_damage_photo_415(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture
+        .expect_damage_report_repository()
+        .returning(|| Arc::new(MockDamageReportRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/damage-reports/{}/photo", DamageReportId::new().raw());
+    let req = Request::put(&v1(&path))
+        .bearer()
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(Body::from("dummy"))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn resolve_damage_report_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture_admin
+        .expect_damage_report_repository()
+        .returning(|| {
+            let mut mock = MockDamageReportRepository::new();
+            mock.expect_resolve()
+                .withf(|event| event.note == "修理済み")
+                .returning(|_| Ok(()));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let path = format!("/damage-reports/{}/resolve", DamageReportId::new().raw());
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"note":"修理済み"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn resolve_damage_report_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_damage_report_repository()
+        .returning(|| Arc::new(MockDamageReportRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/damage-reports/{}/resolve", DamageReportId::new().raw());
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"note":""}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn update_book_copy_condition_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update_copy_condition()
+            .withf(|event| event.condition == CopyCondition::Poor)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/copies/{}/condition",
+        BookId::new().raw(),
+        CopyId::new().raw()
+    );
+    let req = Request::put(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"condition":"poor"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(true, StatusCode::OK)]
+#[case(false, StatusCode::NO_CONTENT)]
+#[tokio::test]
+async fn update_book_copy_withdrawal(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] withdrawn: bool,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update_copy_withdrawal()
+            .withf(move |event| event.withdrawn == withdrawn)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/copies/{}/withdrawal",
+        BookId::new().raw(),
+        CopyId::new().raw()
+    );
+    let req = if withdrawn {
+        Request::put(&v1(&path))
+    } else {
+        Request::delete(&v1(&path))
+    };
+    let resp = app.oneshot(req.bearer().body(Body::empty())?).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use api::model::book::BookResponse;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::Response,
+};
+use chrono::Utc;
+use kernel::{
+    model::{
+        book::{Book, Checkout},
+        id::{BookId, CheckoutId, CopyId, UserId},
+        user::{BookOwner, CheckoutUser},
+    },
+    repository::book::MockBookRepository,
+};
+use rstest::rstest;
+use tokio_stream::StreamExt;
+use tower::ServiceExt;
+
+use crate::helper::{fixture, make_router, v1, TestRequestExt};
+
+fn book(title: &str, checked_out_by: Option<&str>) -> Book {
+    let checkouts = checked_out_by
+        .map(|name| Checkout {
+            checkout_id: CheckoutId::new(),
+            copy_id: CopyId::new(),
+            barcode: "0001".into(),
+            checked_out_by: CheckoutUser {
+                id: UserId::new(),
+                name: name.into(),
+            },
+            checked_out_at: Utc::now(),
+            due_at: Utc::now() + chrono::Duration::days(14),
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    Book {
+        id: BookId::new(),
+        title: title.into(),
+        author: "著者".into(),
+        authors: vec![],
+        isbn: "9784798061702".into(),
+        description: "説明, カンマを含む".into(),
+        publisher: None,
+        published_year: None,
+        owner: BookOwner {
+            id: UserId::new(),
+            name: "所有者".into(),
+        },
+        total_copies: 1,
+        available_copies: 1 - checkouts.len() as i64,
+        checkouts,
+        tags: vec![],
+        series: None,
+        has_cover: false,
+        average_rating: None,
+        review_count: 0,
+        updated_at: chrono::Utc::now(),
+        deleted_at: None,
+    }
+}
+
+fn mock_export(registry: &mut registry::MockAppRegistryExt) {
+    registry.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_export_all().returning(|| {
+            Box::pin(tokio_stream::iter(vec![
+                Ok(book("貸出中の本", Some("借りた人"))),
+                Ok(book("貸出可能な本", None)),
+            ]))
+        });
+        Arc::new(mock)
+    });
+}
+
+async fn read_body(resp: Response) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut stream = resp.into_body().into_data_stream();
+    while let Some(chunk) = stream.try_next().await? {
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+#[rstest]
+#[case("/books/export")]
+#[case("/books/export?format=csv")]
+#[tokio::test]
+async fn export_books_csv_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] path: &str,
+) -> anyhow::Result<()> {
+    mock_export(&mut fixture);
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[header::CONTENT_TYPE],
+        "text/csv; charset=utf-8"
+    );
+
+    let body = read_body(resp).await?;
+    // Excel で文字化けしないよう BOM を付ける
+    let body = body.strip_prefix(b"\xEF\xBB\xBF").unwrap();
+    let body = String::from_utf8(body.to_vec())?;
+    let lines = body.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id,title,author,isbn"));
+    assert!(lines[1].contains("貸出中の本"));
+    assert!(lines[1].contains("\"説明, カンマを含む\""));
+    assert!(lines[1].contains("借りた人"));
+    assert!(!lines[2].contains("借りた人"));
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn export_books_ndjson_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    mock_export(&mut fixture);
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/export?format=ndjson"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CONTENT_TYPE], "application/x-ndjson");
+
+    let body = read_body(resp).await?;
+    let books = body
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(serde_json::from_slice::<BookResponse>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(books.len(), 2);
+    assert_eq!(books[0].checkouts.len(), 1);
+    assert_eq!(books[0].available_copies, 0);
+    assert!(books[1].checkouts.is_empty());
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn export_books_400(fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/export?format=xlsx"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
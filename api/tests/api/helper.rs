@@ -63,9 +63,29 @@ pub fn fixture(mut fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
     fixture_auth
 }
 
+// 管理者としてリクエストするための fixture
+#[fixture]
+pub fn fixture_admin(mut fixture_auth: MockAppRegistryExt) -> MockAppRegistryExt {
+    fixture_auth.expect_user_repository().returning(|| {
+        let mut mock_user_repository = MockUserRepository::new();
+        mock_user_repository
+            .expect_find_current_user()
+            .returning(|id| {
+                Ok(Some(User {
+                    id,
+                    name: "dummy-admin".to_string(),
+                    email: "admin@example.com".to_string(),
+                    role: Role::Admin,
+                }))
+            });
+        Arc::new(mock_user_repository)
+    });
+    fixture_auth
+}
+
 pub trait TestRequestExt {
     fn bearer(self) -> Builder;
-    // fn application_json(self) -> Builder;
+    fn application_json(self) -> Builder;
 }
 
 impl TestRequestExt for Builder {
@@ -73,9 +93,9 @@ impl TestRequestExt for Builder {
         self.header("Authorization", "Bearer dummy")
     }
 
-    //fn application_json(self) -> Builder {
-    //self.header("Content-Type", "application/json")
-    //}
+    fn application_json(self) -> Builder {
+        self.header("Content-Type", "application/json")
+    }
 }
 
 // to_byte などを使って関数やトレイトに切り出してもよいのだが、
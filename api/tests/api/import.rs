@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use api::model::import::{BookImportResponse, BookImportRowStatus};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use kernel::{
+    model::{
+        book::{BookImportResult, BookImportStatus},
+        id::BookId,
+    },
+    repository::book::MockBookRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+// 受け取った行をすべて登録できたものとして返すモック
+fn mock_import(registry: &mut registry::MockAppRegistryExt, expected_lines: Vec<u64>) {
+    registry.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected_lines = expected_lines.clone();
+        mock.expect_import()
+            .withf(move |event| {
+                event.rows.iter().map(|row| row.line).collect::<Vec<_>>() == expected_lines
+            })
+            .returning(|event| {
+                Ok(event
+                    .rows
+                    .into_iter()
+                    .map(|row| BookImportResult {
+                        line: row.line,
+                        status: BookImportStatus::Imported(BookId::new()),
+                    })
+                    .collect())
+            });
+        Arc::new(mock)
+    });
+}
+
+#[rstest]
+#[tokio::test]
+async fn import_books_csv_best_effort_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    mock_import(&mut fixture, vec![2, 5]);
+    let app: axum::Router = make_router(fixture);
+
+    // 3 行目はタイトルが空、4 行目は ISBN のチェックディジットが誤っている
+    let csv = "\u{feff}title,author,isbn,description\n\
+        実践Rustプログラミング入門,初田直也他,978-4-7980-6170-2,入門書\n\
+        ,高野祐輝,9784065301951,\n\
+        RustによるWebアプリケーション開発,豊田優貴他,9784065369570,\n\
+        \"Rust, 入門\",著者,4-87311-865-4,\n";
+    let req = Request::post(&v1("/books/import?mode=bestEffort"))
+        .bearer()
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .body(Body::from(csv))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!((result.imported, result.failed, result.skipped), (2, 2, 0));
+    assert_eq!(
+        result.rows.iter().map(|r| r.status).collect::<Vec<_>>(),
+        vec![
+            BookImportRowStatus::Imported,
+            BookImportRowStatus::Failed,
+            BookImportRowStatus::Failed,
+            BookImportRowStatus::Imported,
+        ]
+    );
+    assert_eq!(
+        result.rows.iter().map(|r| r.line).collect::<Vec<_>>(),
+        vec![2, 3, 4, 5]
+    );
+    assert!(result.rows[1].errors[0].starts_with("title"));
+    assert!(result.rows[2].errors[0].starts_with("isbn"));
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn import_books_all_or_nothing_422(
+    fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    // 検証に通らない行がある場合はリポジトリを呼び出さない
+    let app: axum::Router = make_router(fixture);
+
+    let ndjson = r#"{"title":"実践Rustプログラミング入門","author":"初田直也他","isbn":"9784798061702"}
+
+{"title":"ゼロから学ぶRust","author":"高野祐輝"}
+"#;
+    let req = Request::post(&v1("/books/import"))
+        .bearer()
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from(ndjson))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!((result.imported, result.failed, result.skipped), (0, 1, 1));
+    assert_eq!(result.rows[0].line, 1);
+    assert_eq!(result.rows[0].status, BookImportRowStatus::Skipped);
+    assert_eq!(result.rows[1].line, 3);
+    assert_eq!(result.rows[1].status, BookImportRowStatus::Failed);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn import_books_ndjson_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    mock_import(&mut fixture, vec![1, 2]);
+    let app: axum::Router = make_router(fixture);
+
+    let ndjson = r#"{"title":"実践Rustプログラミング入門","author":"初田直也他","isbn":"9784798061702"}
+{"title":"ゼロから学ぶRust","author":"高野祐輝","isbn":"9784065301951","description":"入門書"}"#;
+    let req = Request::post(&v1("/books/import"))
+        .bearer()
+        .header(header::CONTENT_TYPE, "application/x-ndjson")
+        .body(Body::from(ndjson))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookImportResponse);
+    assert_eq!(result.imported, 2);
+    assert!(result.rows.iter().all(|r| r.book_id.is_some()));
+
+    Ok(())
+}
+
+#[rstest]
+#[case(Some("application/json"))]
+#[case(None)]
+#[tokio::test]
+async fn import_books_415(
+    fixture: registry::MockAppRegistryExt,
+    #[case] content_type: Option<&str>,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let mut req = Request::post(&v1("/books/import")).bearer();
+    if let Some(content_type) = content_type {
+        req = req.header(header::CONTENT_TYPE, content_type);
+    }
+    let resp = app.oneshot(req.body(Body::from("[]"))?).await?;
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    Ok(())
+}
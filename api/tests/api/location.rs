@@ -0,0 +1,264 @@
+use std::sync::Arc;
+
+use api::model::location::{LocationResponse, LocationsResponse};
+use axum::{body::Body, http::Request};
+use kernel::{
+    model::{
+        id::{BookId, CheckoutId, CopyId, LocationId},
+        list::PaginatedList,
+        location::{Location, LocationKind},
+    },
+    repository::{
+        book::MockBookRepository, checkout::MockCheckoutRepository,
+        location::MockLocationRepository,
+    },
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
+};
+
+fn mock_create_location(registry: &mut registry::MockAppRegistryExt) {
+    registry.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_create().returning(|event| {
+            Ok(Location {
+                id: LocationId::new(),
+                parent_id: event.parent_id,
+                path: vec!["本館".into(), event.name.clone()],
+                name: event.name,
+                kind: event.kind,
+            })
+        });
+        Arc::new(mock)
+    });
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_location_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    mock_create_location(&mut fixture_admin);
+    let app: axum::Router = make_router(fixture_admin);
+
+    let parent_id = LocationId::new();
+    let body = format!(
+        r#"{{"parentId":"{}","name":" 閲覧室 ","kind":"room"}}"#,
+        parent_id.raw()
+    );
+    let req = Request::post(&v1("/locations"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, LocationResponse);
+    assert_eq!(result.parent_id, Some(parent_id));
+    assert_eq!(result.name, "閲覧室");
+    assert_eq!(result.path, vec!["本館", "閲覧室"]);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_location_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    mock_create_location(&mut fixture);
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(&v1("/locations"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name":"本館","kind":"building"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"name":"","kind":"building"}"#)]
+#[case(r#"{"name":"本館","kind":"floor"}"#)]
+#[tokio::test]
+async fn register_location_4xx(
+    mut fixture_admin: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    mock_create_location(&mut fixture_admin);
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::post(&v1("/locations"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert!(resp.status().is_client_error());
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn list_locations_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let building_id = LocationId::new();
+    fixture.expect_location_repository().returning(move || {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_find_all().returning(move || {
+            Ok(vec![
+                Location {
+                    id: building_id,
+                    parent_id: None,
+                    name: "本館".into(),
+                    kind: LocationKind::Building,
+                    path: vec!["本館".into()],
+                },
+                Location {
+                    id: LocationId::new(),
+                    parent_id: Some(building_id),
+                    name: "閲覧室".into(),
+                    kind: LocationKind::Room,
+                    path: vec!["本館".into(), "閲覧室".into()],
+                },
+            ])
+        });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/locations"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, LocationsResponse);
+    assert_eq!(result.items.len(), 2);
+    assert_eq!(result.items[1].parent_id, Some(building_id));
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn delete_location_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_location_repository().returning(|| {
+        let mut mock = MockLocationRepository::new();
+        mock.expect_delete().returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/locations/{}", LocationId::new().raw());
+    let req = Request::delete(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_list_with_location_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let location_id = LocationId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(move |opt| opt.location == Some(location_id))
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books?location={}", location_id.raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test]
+async fn return_book_with_location_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] with_location: bool,
+) -> anyhow::Result<()> {
+    let location_id = LocationId::new();
+    let expected = with_location.then_some(location_id);
+
+    fixture.expect_checkout_repository().returning(move || {
+        let mut mock = MockCheckoutRepository::new();
+        mock.expect_update_returned()
+            .withf(move |event| event.location_id == expected)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let mut path = format!(
+        "/books/{}/checkouts/{}/returned",
+        BookId::new().raw(),
+        CheckoutId::new().raw()
+    );
+    if with_location {
+        path.push_str(&format!("?locationId={}", location_id.raw()));
+    }
+    let req = Request::put(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn update_book_copy_location_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let location_id = LocationId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_update_copy_location()
+            .withf(move |event| event.location_id == Some(location_id))
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!(
+        "/books/{}/copies/{}/location",
+        BookId::new().raw(),
+        CopyId::new().raw()
+    );
+    let body = format!(r#"{{"locationId":"{}"}}"#, location_id.raw());
+    let req = Request::put(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
@@ -1,2 +1,17 @@
+mod author;
 mod book;
+mod checkout;
+mod citation;
+mod cover;
+mod damage;
+mod export;
 mod helper;
+mod import;
+mod location;
+mod metadata;
+mod recommendation;
+mod review;
+mod revision;
+mod series;
+mod tag;
+mod transfer;
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use api::model::book::BookMetadataResponse;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use kernel::{
+    model::book::metadata::BookMetadata,
+    repository::{book::MockBookRepository, metadata::MockBookMetadataProvider},
+};
+use rstest::rstest;
+use shared::error::AppError;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+fn metadata() -> BookMetadata {
+    BookMetadata {
+        isbn: "9784798061702".parse().unwrap(),
+        title: Some("実践Rustプログラミング入門".into()),
+        authors: vec!["初田直也".into(), "山口聖弘".into()],
+        description: None,
+    }
+}
+
+fn mock_provider(
+    registry: &mut registry::MockAppRegistryExt,
+    result: fn() -> Result<Option<BookMetadata>, AppError>,
+) {
+    registry.expect_book_metadata_provider().returning(move || {
+        let mut mock = MockBookMetadataProvider::new();
+        mock.expect_lookup()
+            .withf(|isbn| isbn.as_str() == "9784798061702")
+            .returning(move |_| result());
+        Arc::new(mock)
+    });
+}
+
+#[rstest]
+#[case("9784798061702")]
+#[case("4-7980-6170-0")]
+#[tokio::test]
+async fn lookup_book_metadata_200(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] isbn: &str,
+) -> anyhow::Result<()> {
+    mock_provider(&mut fixture, || Ok(Some(metadata())));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/lookup?isbn={isbn}");
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookMetadataResponse);
+    assert_eq!(result.isbn, "9784798061702");
+    assert_eq!(result.title.as_deref(), Some("実践Rustプログラミング入門"));
+    assert!(result.description.is_none());
+
+    Ok(())
+}
+
+#[rstest]
+#[case(|| Ok(None), StatusCode::NOT_FOUND)]
+#[case(|| Err(AppError::ExternalServiceError("timeout".into())), StatusCode::BAD_GATEWAY)]
+#[tokio::test]
+async fn lookup_book_metadata_error(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] result: fn() -> Result<Option<BookMetadata>, AppError>,
+    #[case] expected: StatusCode,
+) -> anyhow::Result<()> {
+    mock_provider(&mut fixture, result);
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/lookup?isbn=9784798061702"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), expected);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn lookup_book_metadata_400(fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books/lookup?isbn=abc"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_book_with_autofill_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    mock_provider(&mut fixture, || Ok(Some(metadata())));
+    // 入力された項目は書誌情報で上書きしない
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_duplicates().returning(|_| Ok(vec![]));
+        mock.expect_create()
+            .withf(|event, _| {
+                event.title == "実践Rustプログラミング入門"
+                    && event.authors.len() == 1
+                    && event.authors[0].name == "初田直也他"
+                    && event.description.is_empty()
+            })
+            .returning(|_, _| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "authors": [{ "name": "初田直也他" }],
+        "isbn": "978-4-7980-6170-2",
+        "autofill": true,
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(|| Ok(None))]
+#[case(|| Err(AppError::ExternalServiceError("timeout".into())))]
+#[tokio::test]
+async fn register_book_with_autofill_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] result: fn() -> Result<Option<BookMetadata>, AppError>,
+) -> anyhow::Result<()> {
+    // 書誌情報を引けず、必須の項目が埋まらなかった場合はバリデーションエラーとなる
+    mock_provider(&mut fixture, result);
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "isbn": "9784798061702",
+        "autofill": true,
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use api::model::recommendation::RecommendedBooksResponse;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use kernel::{
+    model::{id::BookId, recommendation::RecommendedBook},
+    repository::recommendation::MockRecommendationRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+fn recommended_book(score: i64, has_cover: bool) -> RecommendedBook {
+    RecommendedBook {
+        book_id: BookId::new(),
+        title: "RustによるWebアプリケーション開発".into(),
+        author: "豊田優貴他".into(),
+        has_cover,
+        score,
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_related_books_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture
+        .expect_recommendation_repository()
+        .returning(move || {
+            let mut mock = MockRecommendationRepository::new();
+            mock.expect_find_related()
+                .withf(move |id, limit| *id == book_id && *limit == 10)
+                .returning(|_, _| Ok(vec![recommended_book(3, true), recommended_book(1, false)]));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/related", book_id.raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, RecommendedBooksResponse);
+    assert_eq!(result.items.len(), 2);
+    assert_eq!(result.items[0].score, 3);
+    assert!(result.items[0].cover_thumbnail_url.is_some());
+    assert!(result.items[1].cover_thumbnail_url.is_none());
+
+    Ok(())
+}
+
+#[rstest]
+#[case("?limit=0")]
+#[case("?limit=51")]
+#[tokio::test]
+async fn show_related_books_400(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] query: &'static str,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_recommendation_repository()
+        .returning(|| Arc::new(MockRecommendationRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/related{query}", BookId::new().raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_recommendations_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_recommendation_repository().returning(|| {
+        let mut mock = MockRecommendationRepository::new();
+        mock.expect_find_for_user()
+            .withf(|_, limit| *limit == 5)
+            .returning(|_, _| Ok(vec![recommended_book(2, false)]));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/users/me/recommendations?limit=5"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, RecommendedBooksResponse);
+    assert_eq!(result.items.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use api::model::review::{PaginatedReviewResponse, ReviewResponse};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use kernel::{
+    model::{
+        book::BookSortKey,
+        id::{BookId, ReviewId, UserId},
+        list::PaginatedList,
+        review::BookReview,
+        user::Reviewer,
+    },
+    repository::{book::MockBookRepository, review::MockReviewRepository},
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+fn book_review(book_id: BookId, user_id: UserId, rating: i16, comment: String) -> BookReview {
+    BookReview {
+        id: ReviewId::new(),
+        book_id,
+        reviewer: Reviewer {
+            id: user_id,
+            name: "dummy-user".into(),
+        },
+        rating,
+        comment,
+        created_at: chrono::Utc::now(),
+        updated_at: chrono::Utc::now(),
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_review_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_create()
+            .withf(move |event| event.book_id == book_id && event.rating == 4)
+            .returning(|event| {
+                Ok(book_review(
+                    event.book_id,
+                    event.requested_user,
+                    event.rating,
+                    event.comment,
+                ))
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/reviews", book_id.raw());
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"rating":4,"comment":" 入門に最適 "}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, ReviewResponse);
+    assert_eq!(result.book_id, book_id);
+    assert_eq!(result.rating, 4);
+    assert_eq!(result.comment, "入門に最適");
+
+    Ok(())
+}
+
+#[rstest]
+#[case(r#"{"rating":0}"#)]
+#[case(r#"{"rating":6}"#)]
+#[case(r#"{"comment":"評価がない"}"#)]
+#[tokio::test]
+async fn register_review_4xx(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] body: &'static str,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_review_repository()
+        .returning(|| Arc::new(MockReviewRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/reviews", BookId::new().raw());
+    let req = Request::post(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(body))?;
+    let resp = app.oneshot(req).await?;
+    assert!(resp.status().is_client_error());
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_reviews_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_find_by_book_id()
+            .withf(move |id, options| *id == book_id && options.limit == 5 && options.offset == 10)
+            .returning(|book_id, options| {
+                Ok(PaginatedList {
+                    total: 11,
+                    limit: options.limit,
+                    offset: options.offset,
+                    items: vec![book_review(book_id, UserId::new(), 3, String::new())],
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/reviews?limit=5&offset=10", book_id.raw());
+    let req = Request::get(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, PaginatedReviewResponse);
+    assert_eq!(result.total, 11);
+    assert_eq!(result.items.len(), 1);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn update_review_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let review_id = ReviewId::new();
+
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_update()
+            .withf(move |event| event.review_id == review_id && event.rating == 2)
+            .returning(|event| {
+                Ok(book_review(
+                    event.book_id,
+                    event.requested_user,
+                    event.rating,
+                    event.comment,
+                ))
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/reviews/{}", BookId::new().raw(), review_id.raw());
+    let req = Request::put(&v1(&path))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"rating":2}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn delete_review_204(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let review_id = ReviewId::new();
+
+    fixture.expect_review_repository().returning(move || {
+        let mut mock = MockReviewRepository::new();
+        mock.expect_delete()
+            .withf(move |event| event.review_id == review_id)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let path = format!("/books/{}/reviews/{}", BookId::new().raw(), review_id.raw());
+    let req = Request::delete(&v1(&path)).bearer().body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_book_list_sorted_by_rating_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(|| {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_all()
+            .withf(|opt| opt.sort == Some(BookSortKey::Rating))
+            .returning(|opt| {
+                Ok(PaginatedList {
+                    total: 0,
+                    limit: opt.limit,
+                    offset: opt.offset,
+                    items: vec![],
+                })
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/books?sort=rating"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use api::model::revision::{BookFieldName, BookRevisionsResponse};
+use axum::{body::Body, http::Request};
+use kernel::{
+    model::{
+        book::revision::{BookField, BookFieldChange, BookRevision},
+        id::{BookId, UserId},
+        user::BookEditor,
+    },
+    repository::book::MockBookRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
+};
+
+#[rstest]
+#[tokio::test]
+async fn show_book_revisions_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_find_revisions()
+            .withf(move |id| *id == book_id)
+            .returning(|_| {
+                Ok(vec![BookRevision {
+                    revision: 2,
+                    edited_by: Some(BookEditor {
+                        id: UserId::new(),
+                        name: "Yuki Toyoda".to_string(),
+                    }),
+                    edited_at: chrono::Utc::now(),
+                    changes: vec![BookFieldChange {
+                        field: BookField::Title,
+                        before: Some("Old Title".to_string()),
+                        after: Some("New Title".to_string()),
+                    }],
+                }])
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(&format!("/books/{book_id}/revisions")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookRevisionsResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].revision, 2);
+    assert_eq!(result.items[0].changes[0].field, BookFieldName::Title);
+    assert_eq!(
+        result.items[0].changes[0].before.as_deref(),
+        Some("Old Title")
+    );
+
+    Ok(())
+}
+
+// 権限の判定のため、ユーザーのロールがリポジトリへ渡される
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[tokio::test]
+async fn revert_book_200(
+    #[from(fixture)] user: registry::MockAppRegistryExt,
+    #[from(fixture_admin)] admin: registry::MockAppRegistryExt,
+    #[case] by_admin: bool,
+) -> anyhow::Result<()> {
+    let mut registry = if by_admin { admin } else { user };
+    let book_id = BookId::new();
+
+    registry.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_revert()
+            .withf(move |event| {
+                event.book_id == book_id
+                    && event.revision == 1
+                    && event.requested_by.role.can_manage_all_books() == by_admin
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(registry);
+
+    let req = Request::post(&v1(&format!("/books/{book_id}/revisions/1/revert")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+// If-Match で指定した ETag の最終更新日時がリポジトリへ渡される
+#[rstest]
+#[tokio::test]
+async fn revert_book_with_if_match(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let expected = chrono::DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        mock.expect_revert()
+            .withf(move |event| event.expected_updated_at == Some(vec![expected]))
+            .times(1)
+            .returning(|_| {
+                Err(shared::error::AppError::PreconditionFailed(
+                    "specified book has been modified".into(),
+                ))
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(&v1(&format!("/books/{book_id}/revisions/1/revert")))
+        .bearer()
+        .header("If-Match", r#""1700000000123-0123456789abcdef""#)
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::PRECONDITION_FAILED);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn revert_book_with_invalid_revision_400(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_book_repository()
+        .returning(|| Arc::new(MockBookRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(&v1(&format!(
+        "/books/{}/revisions/latest/revert",
+        BookId::new()
+    )))
+    .bearer()
+    .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
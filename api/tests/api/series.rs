@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use api::model::series::SeriesResponse;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use kernel::{
+    model::{
+        id::{BookId, SeriesId},
+        series::{Series, SeriesMembership, SeriesVolume},
+    },
+    repository::{book::MockBookRepository, series::MockSeriesRepository},
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, make_router, v1, TestRequestExt},
+};
+
+#[rstest]
+#[tokio::test]
+async fn show_series_200(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    let series_id = SeriesId::new();
+    fixture.expect_series_repository().returning(move || {
+        let mut mock = MockSeriesRepository::new();
+        mock.expect_find_by_id()
+            .withf(move |id| *id == series_id)
+            .returning(|id| {
+                let volume = |volume, available_copies| SeriesVolume {
+                    volume,
+                    book_id: BookId::new(),
+                    title: format!("ゴールデンカムイ {volume}"),
+                    author: "野田サトル".into(),
+                    total_copies: 1,
+                    available_copies,
+                };
+                Ok(Some(Series {
+                    id,
+                    title: "ゴールデンカムイ".into(),
+                    volumes: vec![volume(1, 1), volume(2, 0)],
+                }))
+            });
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(&format!("/series/{series_id}")))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let result = deserialize_json!(resp, SeriesResponse);
+    assert_eq!(result.id, series_id);
+    assert_eq!(
+        result
+            .volumes
+            .iter()
+            .map(|v| (v.volume, v.available_copies))
+            .collect::<Vec<_>>(),
+        vec![(1, 1), (2, 0)]
+    );
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_series_404(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    fixture.expect_series_repository().returning(|| {
+        let mut mock = MockSeriesRepository::new();
+        mock.expect_find_by_id().returning(|_| Ok(None));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1(&format!("/series/{}", SeriesId::new())))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+// series を省略した場合は変更せず、null の場合はシリーズから外す
+#[rstest]
+#[case(serde_json::json!({}), None)]
+#[case(serde_json::json!({ "series": null }), Some(None))]
+#[case(
+    serde_json::json!({ "series": { "title": " ゴールデンカムイ ", "volume": 3 } }),
+    Some(Some(SeriesMembership { title: "ゴールデンカムイ".into(), volume: 3 }))
+)]
+#[tokio::test]
+async fn update_book_series(
+    mut fixture: registry::MockAppRegistryExt,
+    #[case] series: serde_json::Value,
+    #[case] expected: Option<Option<SeriesMembership>>,
+) -> anyhow::Result<()> {
+    fixture.expect_book_repository().returning(move || {
+        let mut mock = MockBookRepository::new();
+        let expected = expected.clone();
+        mock.expect_update()
+            .withf(move |event| event.series == expected)
+            .times(1)
+            .returning(|_| Ok(()));
+        Arc::new(mock)
+    });
+    let app: axum::Router = make_router(fixture);
+
+    let mut body = serde_json::json!({
+        "title": "ゴールデンカムイ 3",
+        "authors": [{ "name": "野田サトル" }],
+        "isbn": "9784088901367",
+        "description": "",
+    });
+    body.as_object_mut()
+        .unwrap()
+        .extend(series.as_object().unwrap().clone());
+    let req = Request::put(&v1(&format!("/books/{}", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[case(serde_json::json!({ "title": "", "volume": 1 }))]
+#[case(serde_json::json!({ "title": "ゴールデンカムイ", "volume": 0 }))]
+#[tokio::test]
+async fn register_book_with_invalid_series_400(
+    fixture: registry::MockAppRegistryExt,
+    #[case] series: serde_json::Value,
+) -> anyhow::Result<()> {
+    let app: axum::Router = make_router(fixture);
+
+    let body = serde_json::json!({
+        "title": "ゴールデンカムイ 1",
+        "authors": [{ "name": "野田サトル" }],
+        "isbn": "9784088901367",
+        "series": series,
+    });
+    let req = Request::post(&v1("/books"))
+        .bearer()
+        .application_json()
+        .body(Body::from(body.to_string()))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use api::model::tag::TagResponse;
+use axum::{body::Body, http::Request};
+use kernel::{
+    model::{id::TagId, tag::Tag},
+    repository::tag::MockTagRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
+};
+
+fn mock_create_tag(registry: &mut registry::MockAppRegistryExt) {
+    registry.expect_tag_repository().returning(|| {
+        let mut mock = MockTagRepository::new();
+        mock.expect_create().returning(|event| {
+            Ok(Tag {
+                id: TagId::new(),
+                name: event.name,
+            })
+        });
+        Arc::new(mock)
+    });
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_tag_200(mut fixture_admin: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    mock_create_tag(&mut fixture_admin);
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::post(&v1("/tags"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name":"Rust"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, TagResponse);
+    assert_eq!(result.name, "Rust");
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_tag_by_user_403(mut fixture: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    mock_create_tag(&mut fixture);
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(&v1("/tags"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name":"Rust"}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn register_tag_400(mut fixture_admin: registry::MockAppRegistryExt) -> anyhow::Result<()> {
+    mock_create_tag(&mut fixture_admin);
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::post(&v1("/tags"))
+        .bearer()
+        .application_json()
+        .body(Body::from(r#"{"name":""}"#))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
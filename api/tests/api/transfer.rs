@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use api::model::transfer::BookTransfersResponse;
+use axum::{body::Body, http::Request};
+use kernel::{
+    model::{
+        id::{BookId, UserId},
+        transfer::BookTransfer,
+        user::{BookOwner, TransferRecipient},
+    },
+    repository::transfer::MockBookTransferRepository,
+};
+use rstest::rstest;
+use tower::ServiceExt;
+
+use crate::{
+    deserialize_json,
+    helper::{fixture, fixture_admin, make_router, v1, TestRequestExt},
+};
+
+#[rstest]
+#[tokio::test]
+async fn propose_book_transfer_201(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let to_user = UserId::new();
+
+    fixture
+        .expect_book_transfer_repository()
+        .returning(move || {
+            let mut mock = MockBookTransferRepository::new();
+            mock.expect_propose()
+                .withf(move |event| event.book_id == book_id && event.to_user == to_user)
+                .times(1)
+                .returning(|_| Ok(()));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::post(&v1(&format!("/books/{book_id}/transfer")))
+        .bearer()
+        .application_json()
+        .body(Body::from(format!(r#"{{"toUserId":"{to_user}"}}"#)))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn show_incoming_transfers_200(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+
+    fixture
+        .expect_book_transfer_repository()
+        .returning(move || {
+            let mut mock = MockBookTransferRepository::new();
+            mock.expect_find_incoming().returning(move |user_id| {
+                Ok(vec![BookTransfer {
+                    book_id,
+                    book_title: "RustによるWebアプリケーション開発".to_string(),
+                    from: BookOwner {
+                        id: UserId::new(),
+                        name: "Yuki Toyoda".to_string(),
+                    },
+                    to: TransferRecipient {
+                        id: user_id,
+                        name: "Recipient".to_string(),
+                    },
+                    proposed_at: chrono::Utc::now(),
+                }])
+            });
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::get(&v1("/users/me/transfers"))
+        .bearer()
+        .body(Body::empty())?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    let result = deserialize_json!(resp, BookTransfersResponse);
+    assert_eq!(result.items.len(), 1);
+    assert_eq!(result.items[0].book_id, book_id);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn reassign_book_owner_200(
+    mut fixture_admin: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    let book_id = BookId::new();
+    let to_user = UserId::new();
+
+    fixture_admin
+        .expect_book_transfer_repository()
+        .returning(move || {
+            let mut mock = MockBookTransferRepository::new();
+            mock.expect_reassign()
+                .withf(move |event| event.book_id == book_id && event.to_user == to_user)
+                .times(1)
+                .returning(|_| Ok(()));
+            Arc::new(mock)
+        });
+    let app: axum::Router = make_router(fixture_admin);
+
+    let req = Request::put(&v1(&format!("/books/{book_id}/owner")))
+        .bearer()
+        .application_json()
+        .body(Body::from(format!(r#"{{"userId":"{to_user}"}}"#)))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn reassign_book_owner_by_user_403(
+    mut fixture: registry::MockAppRegistryExt,
+) -> anyhow::Result<()> {
+    fixture
+        .expect_book_transfer_repository()
+        .returning(|| Arc::new(MockBookTransferRepository::new()));
+    let app: axum::Router = make_router(fixture);
+
+    let req = Request::put(&v1(&format!("/books/{}/owner", BookId::new())))
+        .bearer()
+        .application_json()
+        .body(Body::from(format!(r#"{{"userId":"{}"}}"#, UserId::new())))?;
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use strum::{AsRefStr, EnumString};
+
+use super::id::AuthorId;
+
+// 著者。表記の揺れで同じ人物が分かれないよう、名前ごとに 1 件だけ登録する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Author {
+    pub id: AuthorId,
+    pub name: String,
+}
+
+// 蔵書に対する著者の役割
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuthorRole {
+    #[default]
+    Author,
+    Editor,
+    Translator,
+}
+
+impl AuthorRole {
+    // 表示用の著者名で名前に添える役割の表記。著者の場合は添えない
+    fn credit_suffix(self) -> &'static str {
+        match self {
+            Self::Author => "",
+            Self::Editor => " (編)",
+            Self::Translator => " (訳)",
+        }
+    }
+}
+
+// 蔵書の著者。蔵書ごとに指定された順に並べる
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookAuthor {
+    pub id: AuthorId,
+    pub name: String,
+    pub role: AuthorRole,
+}
+
+// 蔵書の登録・更新時に指定する著者
+// 著者は名前で指定し、まだ登録されていない名前の場合は著者として登録する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorCredit {
+    pub name: String,
+    pub role: AuthorRole,
+}
+
+// 著者の一覧を、表示や検索・並び替えに使う 1 つの文字列にまとめる
+// 例: 「山田太郎, 佐藤花子 (編), John Smith (訳)」
+// 同じ著者が同じ役割で重複している場合は、最初のものだけを含める
+pub fn credit_line(credits: &[AuthorCredit]) -> String {
+    let mut seen = HashSet::new();
+    credits
+        .iter()
+        .filter(|c| seen.insert((c.name.as_str(), c.role)))
+        .map(|c| format!("{}{}", c.name, c.role.credit_suffix()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
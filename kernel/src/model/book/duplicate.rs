@@ -0,0 +1,47 @@
+use crate::model::id::BookId;
+
+// 登録しようとした蔵書と重複している可能性のある、登録済みの蔵書
+#[derive(Debug)]
+pub struct DuplicateCandidate {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub isbn: String,
+    pub reason: DuplicateReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    // ISBN が同じ。同じ ISBN の蔵書は重複して登録できない
+    Isbn,
+    // タイトルが似ていて、共通する著者がいる
+    TitleAndAuthor,
+}
+
+// タイトルを比較するための表記に揃える
+// 全角英数字を半角に、英字を小文字にし、空白や記号を取り除く
+pub fn title_key(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// 表記を揃えたタイトルが一致するか、一方が他方で始まる（副題や版の表記の有無の違い）場合に似ているとみなす
+pub fn is_similar_title(a: &str, b: &str) -> bool {
+    let (a, b) = (title_key(a), title_key(b));
+    !a.is_empty() && !b.is_empty() && (a.starts_with(&b) || b.starts_with(&a))
+}
+
+// 著者名を比較するための表記に揃える。空白を取り除き、英字を小文字にする
+pub fn author_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
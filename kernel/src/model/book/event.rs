@@ -1,24 +1,138 @@
-use crate::model::id::{BookId, UserId};
+use chrono::{DateTime, Utc};
 
+use super::CopyCondition;
+use crate::model::{
+    author::AuthorCredit,
+    id::{BookId, CopyId, LocationId, TagId, UserId},
+    isbn::Isbn,
+    series::SeriesMembership,
+    user::Actor,
+};
+
+#[derive(Debug)]
 pub struct CreateBook {
     pub title: String,
-    pub author: String,
-    pub isbn: String,
+    // 指定した順に並べる。1 人以上指定する
+    pub authors: Vec<AuthorCredit>,
+    pub isbn: Isbn,
     pub description: String,
+    // 出版社と出版年。不明な場合は None
+    pub publisher: Option<String>,
+    pub published_year: Option<i32>,
+    pub tags: Vec<TagId>,
+    // シリーズに属さない場合は None
+    pub series: Option<SeriesMembership>,
 }
 
 #[derive(Debug)]
 pub struct UpdateBook {
     pub book_id: BookId,
     pub title: String,
-    pub author: String,
-    pub isbn: String,
+    // 指定した順に並べる。1 人以上指定する
+    pub authors: Vec<AuthorCredit>,
+    pub isbn: Isbn,
     pub description: String,
-    pub requested_user: UserId,
+    // None の場合は変更しない。Some(None) の場合は未設定に戻す
+    pub publisher: Option<Option<String>>,
+    pub published_year: Option<Option<i32>>,
+    // None の場合はタグを変更しない
+    pub tags: Option<Vec<TagId>>,
+    // None の場合はシリーズを変更しない。Some(None) の場合はシリーズから外す
+    pub series: Option<Option<SeriesMembership>>,
+    pub requested_by: Actor,
+    // 蔵書の最終更新日時がいずれかと一致する場合のみ更新する。None の場合は確認しない
+    pub expected_updated_at: Option<Vec<DateTime<Utc>>>,
 }
 
 #[derive(Debug)]
 pub struct DeleteBook {
     pub book_id: BookId,
+    pub requested_by: Actor,
+    // 蔵書の最終更新日時がいずれかと一致する場合のみ削除する。None の場合は確認しない
+    pub expected_updated_at: Option<Vec<DateTime<Utc>>>,
+}
+
+// 蔵書の書誌情報を指定した版の内容に戻す
+#[derive(Debug)]
+pub struct RevertBook {
+    pub book_id: BookId,
+    pub revision: i32,
+    pub requested_by: Actor,
+    // 蔵書の最終更新日時がいずれかと一致する場合のみ戻す。None の場合は確認しない
+    pub expected_updated_at: Option<Vec<DateTime<Utc>>>,
+}
+
+// 論理削除済みの蔵書を元に戻す
+#[derive(Debug)]
+pub struct RestoreBook {
+    pub book_id: BookId,
+}
+
+// 論理削除済みの蔵書を完全に削除する
+#[derive(Debug)]
+pub struct PurgeBook {
+    pub book_id: BookId,
+}
+
+#[derive(Debug)]
+pub struct CreateBookCopy {
+    pub book_id: BookId,
+    // 指定しない場合は冊子 ID から生成する
+    pub barcode: Option<String>,
+    pub requested_by: Actor,
+}
+
+// 冊子を置く場所を変更する。location_id が None の場合は未設定に戻す
+#[derive(Debug)]
+pub struct UpdateBookCopyLocation {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub location_id: Option<LocationId>,
+    pub requested_by: Actor,
+}
+
+#[derive(Debug)]
+pub struct UpdateBookCopyCondition {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub condition: CopyCondition,
+    pub requested_by: Actor,
+}
+
+// 冊子を貸出の対象から外す、もしくは戻す。貸し出し中の冊子は外せない
+#[derive(Debug)]
+pub struct UpdateBookCopyWithdrawal {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub withdrawn: bool,
+    pub requested_by: Actor,
+}
+
+#[derive(Debug)]
+pub struct DeleteBookCopy {
+    pub book_id: BookId,
+    pub copy_id: CopyId,
+    pub requested_by: Actor,
+}
+
+// 蔵書の一括登録。line は取り込んだファイル上の行番号で、結果の報告に使う
+#[derive(Debug)]
+pub struct ImportBooks {
+    pub rows: Vec<ImportBookRow>,
+    pub mode: ImportMode,
     pub requested_user: UserId,
 }
+
+#[derive(Debug)]
+pub struct ImportBookRow {
+    pub line: u64,
+    pub book: CreateBook,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    // 1 行でも登録できなければ、すべての行を登録しない
+    AllOrNothing,
+    // 登録できた行だけを登録する
+    BestEffort,
+}
@@ -0,0 +1,11 @@
+use crate::model::isbn::Isbn;
+
+// ISBN から引いた書誌情報。提供元によっては得られない項目がある
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookMetadata {
+    pub isbn: Isbn,
+    pub title: Option<String>,
+    // 著者名。得られなかった場合は空
+    pub authors: Vec<String>,
+    pub description: Option<String>,
+}
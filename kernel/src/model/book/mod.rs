@@ -1,28 +1,153 @@
 use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
 
 use super::{
-    id::{BookId, CheckoutId},
+    author::{AuthorRole, BookAuthor},
+    id::{AuthorId, BookId, CheckoutId, CopyId, LocationId, TagId, UserId},
+    isbn::Isbn,
+    list::SortOrder,
+    location::Location,
+    series::BookSeries,
+    tag::Tag,
     user::{BookOwner, CheckoutUser},
 };
 
+pub mod duplicate;
 pub mod event;
+pub mod metadata;
+pub mod permission;
+pub mod revision;
+
+// 出版年として登録できる値の範囲
+pub const MIN_PUBLISHED_YEAR: i32 = 1;
+pub const MAX_PUBLISHED_YEAR: i32 = 9999;
 
 #[derive(Debug)]
 pub struct Book {
     pub id: BookId,
     pub title: String,
+    // 著者の一覧を 1 つにまとめた表示用の文字列
     pub author: String,
+    pub authors: Vec<BookAuthor>,
+    // 正規化前に登録された値も読み出せるよう、文字列のまま保持する
     pub isbn: String,
     pub description: String,
+    // 出版社と出版年。登録されていない場合は None
+    pub publisher: Option<String>,
+    pub published_year: Option<i32>,
     pub owner: BookOwner,
+    // 蔵書が持つ冊子の数と、そのうち貸出可能な冊子の数
+    pub total_copies: i64,
+    pub available_copies: i64,
+    // 貸出中の冊子の貸出情報
+    pub checkouts: Vec<Checkout>,
+    pub tags: Vec<Tag>,
+    // シリーズに属さない場合は None
+    pub series: Option<BookSeries>,
+    // 表紙画像が登録されているかどうか
+    pub has_cover: bool,
+    // レビューの評価の平均と件数。レビューがない場合、平均は None
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    // 最後に更新された日時。楽観的排他制御での版の確認に使う
+    pub updated_at: DateTime<Utc>,
+    // 論理削除された日時。削除されていない場合は None
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+// 蔵書の冊子。貸出は冊子の単位で行う
+#[derive(Debug)]
+pub struct BookCopy {
+    pub id: CopyId,
+    pub barcode: String,
+    // 冊子が現在置かれている場所。未設定の場合は None
+    pub location: Option<Location>,
+    pub condition: CopyCondition,
+    // 貸出の対象から外した日時。貸し出せる冊子の場合は None
+    pub withdrawn_at: Option<DateTime<Utc>>,
     pub checkout: Option<Checkout>,
 }
 
+// 冊子の状態の等級
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum CopyCondition {
+    New,
+    #[default]
+    Good,
+    Fair,
+    Poor,
+}
+
 // ページネーションの範囲を指定するための設定値を格納する型を追加
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct BookListOptions {
     pub limit: i64,
     pub offset: i64,
+    // タイトル・著者名・ISBN・説明文に対する検索キーワード
+    // 空白区切りで複数指定した場合はすべてを含む蔵書に絞り込む
+    pub keyword: Option<String>,
+    // 以下は絞り込み条件。None の場合は絞り込まない
+    pub owner: Option<UserId>,
+    pub isbn: Option<Isbn>,
+    pub author: Option<String>,
+    // 指定した著者が関わる蔵書に絞り込む。author_role も指定した場合はその役割に限る
+    pub author_id: Option<AuthorId>,
+    pub author_role: Option<AuthorRole>,
+    pub availability: Option<BookAvailability>,
+    // 指定したタグをすべて付けられた蔵書に絞り込む
+    pub tags: Vec<TagId>,
+    // 指定した場所、もしくはその配下に冊子が置かれている蔵書に絞り込む
+    pub location: Option<LocationId>,
+    // 並び順。sort が None の場合は検索キーワードとの関連度、登録日時の新しい順となる
+    pub sort: Option<BookSortKey>,
+    pub order: Option<SortOrder>,
+}
+
+// 貸出状態による絞り込み条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookAvailability {
+    // 貸出可能な冊子が 1 冊以上ある蔵書のみ
+    Available,
+    // 貸出可能な冊子がない蔵書のみ
+    CheckedOut,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSortKey {
+    Title,
+    Author,
+    CreatedAt,
+    UpdatedAt,
+    // レビューの評価の平均。レビューのない蔵書は評価が最も低いものとして扱う
+    Rating,
+}
+
+impl BookSortKey {
+    // 並び順の指定がない場合の既定値
+    // 文字列は昇順、日時と評価は新しい順・高い順とする
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::Title | Self::Author => SortOrder::Asc,
+            Self::CreatedAt | Self::UpdatedAt | Self::Rating => SortOrder::Desc,
+        }
+    }
+}
+
+// 一括登録した各行の結果
+#[derive(Debug)]
+pub struct BookImportResult {
+    pub line: u64,
+    pub status: BookImportStatus,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BookImportStatus {
+    Imported(BookId),
+    // 登録できなかった理由
+    Failed(Vec<String>),
+    // 行自体に問題はないが、他の行が登録できなかったため登録しなかった
+    Skipped,
 }
 
 // この型は model::checkout モジュール側でも同名の型を定義しているが、
@@ -31,6 +156,16 @@ pub struct BookListOptions {
 #[derive(Debug)]
 pub struct Checkout {
     pub checkout_id: CheckoutId,
+    pub copy_id: CopyId,
+    pub barcode: String,
     pub checked_out_by: CheckoutUser,
     pub checked_out_at: DateTime<Utc>,
+    pub due_at: DateTime<Utc>,
+}
+
+impl Checkout {
+    // 返却期限を過ぎても返却されていないかどうか
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due_at < now
+    }
 }
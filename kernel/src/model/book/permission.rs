@@ -0,0 +1,26 @@
+use shared::error::{AppError, AppResult};
+
+use crate::model::{id::UserId, user::Actor};
+
+// 蔵書の変更・削除が許可された理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookPermission {
+    // 蔵書の所有者による操作
+    Owner,
+    // 所有者以外のユーザーが、ロールの権限で行う操作。監査のため記録に残す
+    Override,
+}
+
+impl BookPermission {
+    // 蔵書の変更・削除を許可するか判定する
+    // 所有者以外は、すべての蔵書を管理できるロールのユーザーのみ許可する
+    pub fn check(actor: &Actor, owner: UserId) -> AppResult<Self> {
+        if actor.id == owner {
+            Ok(Self::Owner)
+        } else if actor.role.can_manage_all_books() {
+            Ok(Self::Override)
+        } else {
+            Err(AppError::ForbiddenOperation)
+        }
+    }
+}
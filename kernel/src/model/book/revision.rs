@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::user::BookEditor;
+
+// 蔵書の書誌情報の版。revision は蔵書ごとに 1 から順に振られる
+#[derive(Debug)]
+pub struct BookRevision {
+    pub revision: i32,
+    // 変更したユーザー。ユーザーが削除された場合は None
+    pub edited_by: Option<BookEditor>,
+    pub edited_at: DateTime<Utc>,
+    // 直前の版からの変更点。最初の版では、値のあるすべての項目が変更前の値なしとなる
+    pub changes: Vec<BookFieldChange>,
+}
+
+// 版として記録する蔵書の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookField {
+    Title,
+    Author,
+    Isbn,
+    Description,
+    Publisher,
+    PublishedYear,
+}
+
+// 出版社と出版年は値がない場合があるため、before・after ともに None になりうる
+#[derive(Debug, PartialEq, Eq)]
+pub struct BookFieldChange {
+    pub field: BookField,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
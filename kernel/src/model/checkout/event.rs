@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use derive_new::new;
 
-use crate::model::id::{BookId, CheckoutId, UserId};
+use crate::model::id::{BookId, CheckoutId, LocationId, UserId};
 
+// 蔵書の冊子のうち、貸出可能ないずれかを貸し出す
 #[derive(new)]
 pub struct CreateCheckout {
     pub book_id: BookId,
@@ -10,10 +11,36 @@ pub struct CreateCheckout {
     pub checked_out_at: DateTime<Utc>,
 }
 
+// 貸出の返却期限を延長する。延長できるのは借りたユーザーのみ
+#[derive(new)]
+pub struct CreateRenewal {
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub renewed_by: UserId,
+    pub renewed_at: DateTime<Utc>,
+}
+
+// 蔵書を予約する。予約したユーザーが貸し出すと予約は削除される
+#[derive(new)]
+pub struct CreateHold {
+    pub book_id: BookId,
+    pub held_by: UserId,
+    pub held_at: DateTime<Utc>,
+}
+
+// 自分の予約を取り消す
+#[derive(new)]
+pub struct DeleteHold {
+    pub book_id: BookId,
+    pub held_by: UserId,
+}
+
 #[derive(new)]
 pub struct UpdateReturned {
     pub checkout_id: CheckoutId,
     pub book_id: BookId,
     pub returned_by: UserId,
     pub returned_at: DateTime<Utc>,
+    // 返却した冊子を置いた場所。指定した場合は冊子の場所をここに変更する
+    pub location_id: Option<LocationId>,
 }
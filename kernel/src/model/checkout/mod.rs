@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 
-use super::id::{BookId, CheckoutId, UserId};
+use super::id::{BookId, CheckoutId, CopyId, UserId};
 
 pub mod event;
 
@@ -9,13 +9,38 @@ pub struct Checkout {
     pub id: CheckoutId,
     pub checked_out_by: UserId,
     pub checked_out_at: DateTime<Utc>,
+    // 返却期限。貸出日に既定の貸出期間を加えた日時
+    pub due_at: DateTime<Utc>,
     pub returned_at: Option<DateTime<Utc>>,
     pub book: CheckoutBook,
+    // 延長の記録。延長した日時の古い順に並ぶ
+    pub renewals: Vec<CheckoutRenewal>,
+}
+
+impl Checkout {
+    // 返却期限を過ぎても返却されていないかどうか。返却済みの貸出は延滞として扱わない
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.returned_at.is_none() && self.due_at < now
+    }
+}
+
+// 貸出の延長。延長するたびに返却期限を既定の貸出期間だけ後ろにずらす
+#[derive(Debug, Clone)]
+pub struct CheckoutRenewal {
+    pub renewed_at: DateTime<Utc>,
+    // 延長前の返却期限
+    pub previous_due_at: DateTime<Utc>,
+    // 延長後の返却期限
+    pub due_at: DateTime<Utc>,
 }
 
 #[derive(Debug)]
 pub struct CheckoutBook {
     pub book_id: BookId,
+    // 実際に貸し出した冊子
+    // 返却後に冊子が削除された場合や、冊子を管理する前の貸出では None
+    pub copy_id: Option<CopyId>,
+    pub barcode: Option<String>,
     pub title: String,
     pub author: String,
     pub isbn: String,
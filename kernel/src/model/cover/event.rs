@@ -0,0 +1,16 @@
+use super::CoverImageFormat;
+use crate::model::{id::BookId, user::Actor};
+
+#[derive(Debug)]
+pub struct UploadCover {
+    pub book_id: BookId,
+    pub format: CoverImageFormat,
+    pub data: Vec<u8>,
+    pub requested_by: Actor,
+}
+
+#[derive(Debug)]
+pub struct DeleteCover {
+    pub book_id: BookId,
+    pub requested_by: Actor,
+}
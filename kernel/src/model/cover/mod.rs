@@ -0,0 +1,63 @@
+use strum::{AsRefStr, EnumString};
+
+pub mod event;
+
+// アップロードできる表紙画像の最大サイズ (5 MiB)
+pub const MAX_COVER_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+// サムネイルの長辺の最大ピクセル数
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 200;
+
+// 受け付ける表紙画像の形式
+// DB には小文字の名前で保存する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum CoverImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl CoverImageFormat {
+    // Content-Type ヘッダーの値から画像形式を判定する
+    // パラメータ（`; charset=...` など）は無視する
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim();
+        match mime.to_ascii_lowercase().as_str() {
+            "image/png" => Some(Self::Png),
+            "image/jpeg" | "image/jpg" => Some(Self::Jpeg),
+            "image/webp" => Some(Self::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+// 取得する表紙画像の大きさ
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum CoverImageSize {
+    #[default]
+    Original,
+    Thumbnail,
+}
+
+#[derive(Debug)]
+pub struct CoverImage {
+    pub format: CoverImageFormat,
+    pub data: Vec<u8>,
+}
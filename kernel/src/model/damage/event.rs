@@ -0,0 +1,29 @@
+use crate::model::{
+    cover::CoverImageFormat,
+    id::{BookId, CheckoutId, DamageReportId, UserId},
+};
+
+// 貸出を受けたユーザー本人のみが報告できる。返却前でも返却後でも報告できる
+#[derive(Debug)]
+pub struct CreateDamageReport {
+    pub book_id: BookId,
+    pub checkout_id: CheckoutId,
+    pub description: String,
+    pub reported_by: UserId,
+}
+
+// 写真は報告したユーザーが、対応済みになる前まで添付し直せる
+#[derive(Debug)]
+pub struct UploadDamagePhoto {
+    pub damage_report_id: DamageReportId,
+    pub format: CoverImageFormat,
+    pub data: Vec<u8>,
+    pub requested_user: UserId,
+}
+
+#[derive(Debug)]
+pub struct ResolveDamageReport {
+    pub damage_report_id: DamageReportId,
+    pub note: String,
+    pub resolved_by: UserId,
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use strum::{AsRefStr, EnumString};
+
+use super::{
+    cover::CoverImageFormat,
+    id::{BookId, CheckoutId, CopyId, DamageReportId, UserId},
+    user::DamageReporter,
+};
+
+pub mod event;
+
+// 破損の報告に添付できる写真の最大サイズ (5 MiB)
+pub const MAX_DAMAGE_PHOTO_BYTES: usize = 5 * 1024 * 1024;
+
+// 借りた冊子の破損の報告
+#[derive(Debug)]
+pub struct DamageReport {
+    pub id: DamageReportId,
+    pub checkout_id: CheckoutId,
+    pub book_id: BookId,
+    pub book_title: String,
+    pub copy_id: CopyId,
+    pub barcode: String,
+    pub reported_by: DamageReporter,
+    pub description: String,
+    // 写真が添付されている場合はその形式
+    pub photo_format: Option<CoverImageFormat>,
+    pub reported_at: DateTime<Utc>,
+    // 対応済みでない場合は None
+    pub resolution: Option<DamageResolution>,
+}
+
+impl DamageReport {
+    pub fn status(&self) -> DamageReportStatus {
+        if self.resolution.is_some() {
+            DamageReportStatus::Resolved
+        } else {
+            DamageReportStatus::Open
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DamageResolution {
+    // 対応したユーザーが削除された場合は None
+    pub resolved_by: Option<UserId>,
+    pub note: String,
+    pub resolved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DamageReportStatus {
+    Open,
+    Resolved,
+}
+
+// 破損の報告に添付された写真。形式は表紙画像と同じものを受け付ける
+#[derive(Debug)]
+pub struct DamagePhoto {
+    pub format: CoverImageFormat,
+    pub data: Vec<u8>,
+}
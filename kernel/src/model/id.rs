@@ -62,3 +62,10 @@ macro_rules! define_id {
 define_id!(UserId);
 define_id!(BookId);
 define_id!(CheckoutId);
+define_id!(CopyId);
+define_id!(TagId);
+define_id!(AuthorId);
+define_id!(SeriesId);
+define_id!(LocationId);
+define_id!(DamageReportId);
+define_id!(ReviewId);
@@ -0,0 +1,187 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use shared::error::AppError;
+
+// ISBN を表す値型
+// ISBN-10・ISBN-13 のどちらでも受け付け、チェックディジットを検証したうえで
+// ハイフンなどの区切りを除いた 13 桁の ISBN-13 に正規化して保持する
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[serde(try_from = "String", into = "String")]
+#[sqlx(transparent)]
+pub struct Isbn(String);
+
+impl Isbn {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl FromStr for Isbn {
+    type Err = AppError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| AppError::InvalidIsbnError(format!("{reason}: {s}"));
+
+        // 区切りとして使われるハイフンと空白は読み飛ばす
+        let chars = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .collect::<Vec<_>>();
+
+        match chars.len() {
+            10 => {
+                // 末尾のチェックディジットのみ X (= 10) を許す
+                let mut digits = Vec::with_capacity(10);
+                for (i, c) in chars.iter().enumerate() {
+                    let d = match c {
+                        'X' | 'x' if i == 9 => 10,
+                        c => c
+                            .to_digit(10)
+                            .ok_or_else(|| invalid("ISBN に使えない文字が含まれています"))?,
+                    };
+                    digits.push(d);
+                }
+                // 重みを 10 から 1 まで減らしながら掛けた和が 11 の倍数になる
+                let sum: u32 = digits.iter().zip((1..=10).rev()).map(|(d, w)| d * w).sum();
+                if !sum.is_multiple_of(11) {
+                    return Err(invalid("ISBN のチェックディジットが正しくありません"));
+                }
+                // 先頭に 978 を付け、チェックディジットを計算し直して ISBN-13 にする
+                let mut digits13 = vec![9, 7, 8];
+                digits13.extend_from_slice(&digits[..9]);
+                digits13.push(check_digit13(&digits13));
+                Ok(Self(to_string(&digits13)))
+            }
+            13 => {
+                let digits = chars
+                    .iter()
+                    .map(|c| c.to_digit(10))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| invalid("ISBN に使えない文字が含まれています"))?;
+                if !(digits.starts_with(&[9, 7, 8]) || digits.starts_with(&[9, 7, 9])) {
+                    return Err(invalid("ISBN-13 は 978 または 979 で始まる必要があります"));
+                }
+                if check_digit13(&digits[..12]) != digits[12] {
+                    return Err(invalid("ISBN のチェックディジットが正しくありません"));
+                }
+                Ok(Self(to_string(&digits)))
+            }
+            _ => Err(invalid("ISBN は 10 桁または 13 桁で指定してください")),
+        }
+    }
+}
+
+// ISBN-13 の先頭 12 桁からチェックディジットを計算する
+// 奇数桁に 1、偶数桁に 3 を掛けた和を 10 の倍数にする値となる
+fn check_digit13(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .take(12)
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { *d } else { d * 3 })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+fn to_string(digits: &[u32]) -> String {
+    digits
+        .iter()
+        .filter_map(|d| char::from_digit(*d, 10))
+        .collect()
+}
+
+impl TryFrom<String> for Isbn {
+    type Error = AppError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Isbn> for String {
+    fn from(isbn: Isbn) -> Self {
+        isbn.0
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<String, AppError> {
+        s.parse::<Isbn>().map(Isbn::into_inner)
+    }
+
+    #[test]
+    fn test_isbn13() {
+        // 978・979 のどちらで始まるものも受け付ける
+        assert_eq!(parse("9784065301951").unwrap(), "9784065301951");
+        assert_eq!(parse("9791090636071").unwrap(), "9791090636071");
+        // 977 などほかの接頭辞は ISBN ではない
+        assert!(matches!(
+            parse("9770306406156"),
+            Err(AppError::InvalidIsbnError(_))
+        ));
+    }
+
+    #[test]
+    fn test_isbn10_is_converted_to_isbn13() {
+        assert_eq!(parse("0306406152").unwrap(), "9780306406157");
+        // チェックディジットの X は大文字・小文字のどちらでも受け付ける
+        assert_eq!(parse("080442957X").unwrap(), "9780804429573");
+        assert_eq!(parse("080442957x").unwrap(), "9780804429573");
+        // X はチェックディジット以外の桁には使えない
+        assert!(matches!(
+            parse("08044295X7"),
+            Err(AppError::InvalidIsbnError(_))
+        ));
+    }
+
+    #[test]
+    fn test_separators_are_stripped() {
+        assert_eq!(parse("978-4-06-530195-1").unwrap(), "9784065301951");
+        assert_eq!(parse("978 4 06 530195 1").unwrap(), "9784065301951");
+        assert_eq!(parse("0-8044-2957-X").unwrap(), "9780804429573");
+    }
+
+    #[test]
+    fn test_invalid_check_digit() {
+        assert!(matches!(
+            parse("9784065301952"),
+            Err(AppError::InvalidIsbnError(_))
+        ));
+        assert!(matches!(
+            parse("0306406153"),
+            Err(AppError::InvalidIsbnError(_))
+        ));
+        assert!(matches!(
+            parse("030640615X"),
+            Err(AppError::InvalidIsbnError(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_format() {
+        for s in [
+            "",
+            "aaa",
+            "978406530195",
+            "97840653019511",
+            "978-4-06-53019a-1",
+        ] {
+            assert!(
+                matches!(parse(s), Err(AppError::InvalidIsbnError(_))),
+                "{s} は ISBN として受け付けない"
+            );
+        }
+    }
+}
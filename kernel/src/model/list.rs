@@ -1,3 +1,8 @@
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use shared::error::AppError;
+
 #[derive(Debug)]
 pub struct PaginatedList<T> {
     pub total: i64,
@@ -11,3 +16,125 @@ impl<T> PaginatedList<T> {
         self.items
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+// キーセットページネーションでページの境界となる行の位置を表す型
+// 並び替えに使ったキーの値（最後の要素は ID）を保持し、
+// クライアントには中身を意識させない不透明な文字列として渡す
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    // カーソルを発行したときの並び順
+    // 異なる並び順のリクエストで使われたことを検知するために持つ
+    pub order_by: String,
+    pub keys: Vec<String>,
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = std::iter::once(&self.order_by)
+            .chain(self.keys.iter())
+            .map(|part| URL_SAFE_NO_PAD.encode(part))
+            .collect::<Vec<_>>()
+            .join(".");
+        write!(f, "{encoded}")
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = AppError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError::InvalidCursorError(format!("不正なカーソルです: {s}"));
+        let mut parts = s
+            .split('.')
+            .map(|part| {
+                URL_SAFE_NO_PAD
+                    .decode(part)
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if parts.len() < 2 {
+            return Err(invalid());
+        }
+        let order_by = parts.remove(0);
+        Ok(Self {
+            order_by,
+            keys: parts,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageCursor {
+    // 指定したカーソルより後ろのページ
+    After(Cursor),
+    // 指定したカーソルより前のページ
+    Before(Cursor),
+}
+
+#[derive(Debug, Default)]
+pub struct CursorOptions {
+    pub limit: i64,
+    // None の場合は先頭のページ
+    pub cursor: Option<PageCursor>,
+}
+
+#[derive(Debug)]
+pub struct CursorPaginatedList<T> {
+    pub limit: i64,
+    pub items: Vec<T>,
+    // 次のページ・前のページがない場合は None
+    pub next_cursor: Option<Cursor>,
+    pub prev_cursor: Option<Cursor>,
+}
+
+impl<T> CursorPaginatedList<T> {
+    // limit + 1 件まで取得した行からページと前後のカーソルを組み立てる
+    // カーソルが Before の場合、rows はページの末尾から先頭に向かう逆順で取得されているものとする
+    pub fn from_rows(
+        mut rows: Vec<T>,
+        options: &CursorOptions,
+        cursor_of: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let limit = options.limit;
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit.max(0) as usize);
+        let (has_prev, has_next) = match options.cursor {
+            None => (false, has_more),
+            Some(PageCursor::After(_)) => (true, has_more),
+            Some(PageCursor::Before(_)) => {
+                rows.reverse();
+                (has_more, true)
+            }
+        };
+        let prev_cursor = rows.first().filter(|_| has_prev).map(&cursor_of);
+        let next_cursor = rows.last().filter(|_| has_next).map(&cursor_of);
+        Self {
+            limit,
+            items: rows,
+            next_cursor,
+            prev_cursor,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CursorPaginatedList<U> {
+        let Self {
+            limit,
+            items,
+            next_cursor,
+            prev_cursor,
+        } = self;
+        CursorPaginatedList {
+            limit,
+            items: items.into_iter().map(f).collect(),
+            next_cursor,
+            prev_cursor,
+        }
+    }
+}
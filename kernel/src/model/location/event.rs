@@ -0,0 +1,22 @@
+use crate::model::id::LocationId;
+
+use super::LocationKind;
+
+// 部屋は建物の、書架は部屋の下に作る。建物は parent_id を指定しない
+#[derive(Debug)]
+pub struct CreateLocation {
+    pub parent_id: Option<LocationId>,
+    pub name: String,
+    pub kind: LocationKind,
+}
+
+#[derive(Debug)]
+pub struct UpdateLocation {
+    pub location_id: LocationId,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct DeleteLocation {
+    pub location_id: LocationId,
+}
@@ -0,0 +1,36 @@
+use strum::{AsRefStr, EnumString};
+
+use super::id::LocationId;
+
+pub mod event;
+
+// 冊子を置く場所。建物・部屋・書架の階層で管理する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub id: LocationId,
+    // 建物の場合は None
+    pub parent_id: Option<LocationId>,
+    pub name: String,
+    pub kind: LocationKind,
+    // 最上位の建物からこの場所までの名前を順に並べたもの
+    pub path: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum LocationKind {
+    Building,
+    Room,
+    Shelf,
+}
+
+impl LocationKind {
+    // 親にできる場所の種類。建物は最上位のため親を持たない
+    pub fn parent_kind(self) -> Option<Self> {
+        match self {
+            Self::Building => None,
+            Self::Room => Some(Self::Building),
+            Self::Shelf => Some(Self::Room),
+        }
+    }
+}
@@ -1,7 +1,17 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod cover;
+pub mod damage;
 pub mod id;
+pub mod isbn;
 pub mod list;
+pub mod location;
+pub mod recommendation;
+pub mod review;
 pub mod role;
+pub mod series;
+pub mod tag;
+pub mod transfer;
 pub mod user;
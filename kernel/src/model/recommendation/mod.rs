@@ -0,0 +1,12 @@
+use super::id::BookId;
+
+// 一緒に借りられている蔵書・おすすめの蔵書として返す蔵書
+#[derive(Debug)]
+pub struct RecommendedBook {
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub has_cover: bool,
+    // 同じユーザーに借りられた回数から求めた関連の強さ。大きいほど関連が強い
+    pub score: i64,
+}
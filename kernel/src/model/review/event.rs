@@ -0,0 +1,27 @@
+use crate::model::id::{BookId, ReviewId, UserId};
+
+// 蔵書を借りたことのあるユーザーのみがレビューを書ける
+#[derive(Debug)]
+pub struct CreateReview {
+    pub book_id: BookId,
+    pub rating: i16,
+    pub comment: String,
+    pub requested_user: UserId,
+}
+
+// レビューの変更・削除は書いたユーザー本人のみができる
+#[derive(Debug)]
+pub struct UpdateReview {
+    pub book_id: BookId,
+    pub review_id: ReviewId,
+    pub rating: i16,
+    pub comment: String,
+    pub requested_user: UserId,
+}
+
+#[derive(Debug)]
+pub struct DeleteReview {
+    pub book_id: BookId,
+    pub review_id: ReviewId,
+    pub requested_user: UserId,
+}
@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+
+use super::{
+    id::{BookId, ReviewId},
+    user::Reviewer,
+};
+
+pub mod event;
+
+// 評価として付けられる値の範囲
+pub const MIN_RATING: i16 = 1;
+pub const MAX_RATING: i16 = 5;
+
+#[derive(Debug)]
+pub struct BookReview {
+    pub id: ReviewId,
+    pub book_id: BookId,
+    pub reviewer: Reviewer,
+    pub rating: i16,
+    pub comment: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct ReviewListOptions {
+    pub limit: i64,
+    pub offset: i64,
+}
@@ -1,8 +1,16 @@
 use strum::{AsRefStr, EnumIter, EnumString};
 
-#[derive(Debug, EnumString, AsRefStr, EnumIter, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, EnumIter, Default, PartialEq, Eq)]
 pub enum Role {
     Admin,
     #[default]
     User,
 }
+
+impl Role {
+    // 所有者でなくても、すべての蔵書を変更・削除できるか
+    // 司書のように蔵書を管理するロールを追加する場合は、ここで許可する
+    pub fn can_manage_all_books(&self) -> bool {
+        matches!(self, Role::Admin)
+    }
+}
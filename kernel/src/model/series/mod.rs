@@ -0,0 +1,37 @@
+use super::id::{BookId, SeriesId};
+
+// 複数の巻からなる蔵書のまとまり。タイトルごとに 1 件だけ登録する
+#[derive(Debug)]
+pub struct Series {
+    pub id: SeriesId,
+    pub title: String,
+    // 巻数の順に並べる
+    pub volumes: Vec<SeriesVolume>,
+}
+
+// シリーズに含まれる 1 巻分の蔵書と、その貸出状況
+#[derive(Debug)]
+pub struct SeriesVolume {
+    pub volume: i32,
+    pub book_id: BookId,
+    pub title: String,
+    pub author: String,
+    pub total_copies: i64,
+    pub available_copies: i64,
+}
+
+// 蔵書が属するシリーズと巻数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookSeries {
+    pub id: SeriesId,
+    pub title: String,
+    pub volume: i32,
+}
+
+// 蔵書の登録・更新時に指定するシリーズと巻数
+// シリーズはタイトルで指定し、まだ登録されていないタイトルの場合はシリーズとして登録する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeriesMembership {
+    pub title: String,
+    pub volume: i32,
+}
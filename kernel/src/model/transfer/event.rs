@@ -0,0 +1,30 @@
+use crate::model::id::{BookId, UserId};
+
+// 所有者が譲渡を申し出る。すでに申し出がある場合は置き換える
+#[derive(Debug)]
+pub struct ProposeTransfer {
+    pub book_id: BookId,
+    pub to_user: UserId,
+    pub requested_user: UserId,
+}
+
+// 譲渡先のユーザーが承認し、所有者になる
+#[derive(Debug)]
+pub struct AcceptTransfer {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+}
+
+// 所有者による取り下げ、または譲渡先のユーザーによる辞退
+#[derive(Debug)]
+pub struct CancelTransfer {
+    pub book_id: BookId,
+    pub requested_user: UserId,
+}
+
+// 管理者が承認を経ずに所有者を付け替える
+#[derive(Debug)]
+pub struct ReassignOwner {
+    pub book_id: BookId,
+    pub to_user: UserId,
+}
@@ -0,0 +1,18 @@
+use chrono::{DateTime, Utc};
+
+use super::{
+    id::BookId,
+    user::{BookOwner, TransferRecipient},
+};
+
+pub mod event;
+
+// 承認待ちの蔵書の所有権の譲渡
+#[derive(Debug)]
+pub struct BookTransfer {
+    pub book_id: BookId,
+    pub book_title: String,
+    pub from: BookOwner,
+    pub to: TransferRecipient,
+    pub proposed_at: DateTime<Utc>,
+}
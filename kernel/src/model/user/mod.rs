@@ -9,14 +9,49 @@ pub struct User {
     pub role: Role,
 }
 
+// 操作を要求したユーザー。操作の権限の判定に使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub id: UserId,
+    pub role: Role,
+}
+
 #[derive(Debug)]
 pub struct BookOwner {
     pub id: UserId,
     pub name: String,
 }
 
+// 蔵書の書誌情報を変更したユーザー
+#[derive(Debug)]
+pub struct BookEditor {
+    pub id: UserId,
+    pub name: String,
+}
+
+// 蔵書の所有権の譲渡先のユーザー
+#[derive(Debug)]
+pub struct TransferRecipient {
+    pub id: UserId,
+    pub name: String,
+}
+
 #[derive(Debug)]
 pub struct CheckoutUser {
     pub id: UserId,
     pub name: String,
 }
+
+// 冊子の破損を報告したユーザー
+#[derive(Debug)]
+pub struct DamageReporter {
+    pub id: UserId,
+    pub name: String,
+}
+
+// 蔵書のレビューを書いたユーザー
+#[derive(Debug)]
+pub struct Reviewer {
+    pub id: UserId,
+    pub name: String,
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{author::Author, id::AuthorId};
+
+#[mockall::automock]
+#[async_trait]
+pub trait AuthorRepository: Send + Sync {
+    async fn find_by_id(&self, author_id: AuthorId) -> AppResult<Option<Author>>;
+    // 入力補完のため、名前に keyword を含む著者を前方一致するものから順に最大 limit 件返す
+    // 削除されていない蔵書を 1 冊も持たない著者は含めない
+    async fn suggest(&self, keyword: &str, limit: i64) -> AppResult<Vec<Author>>;
+}
@@ -1,21 +1,70 @@
+use std::pin::Pin;
+
 use async_trait::async_trait;
 use shared::error::AppResult;
+use tokio_stream::Stream;
 
 use crate::model::{
     book::{
-        event::{CreateBook, DeleteBook, UpdateBook},
-        Book, BookListOptions,
+        duplicate::DuplicateCandidate,
+        event::{
+            CreateBook, CreateBookCopy, DeleteBook, DeleteBookCopy, ImportBooks, PurgeBook,
+            RestoreBook, RevertBook, UpdateBook, UpdateBookCopyCondition, UpdateBookCopyLocation,
+            UpdateBookCopyWithdrawal,
+        },
+        revision::BookRevision,
+        Book, BookCopy, BookImportResult, BookListOptions,
     },
-    id::{BookId, UserId},
-    list::PaginatedList,
+    id::{BookId, CopyId, UserId},
+    list::{CursorPaginatedList, PageCursor, PaginatedList},
 };
 
+// 蔵書を 1 件ずつ取り出すストリーム
+pub type BookStream = Pin<Box<dyn Stream<Item = AppResult<Book>> + Send>>;
+
 #[mockall::automock]
 #[async_trait]
 pub trait BookRepository: Send + Sync {
+    // 蔵書の登録時には冊子も 1 冊登録する
     async fn create(&self, event: CreateBook, user_id: UserId) -> AppResult<()>;
+    // 登録しようとしている蔵書と、ISBN が同じ、もしくはタイトルが似ていて共通する著者がいる
+    // 登録済みの蔵書を、登録の古い順に取得する。削除済みの蔵書は含めない
+    async fn find_duplicates(&self, event: &CreateBook) -> AppResult<Vec<DuplicateCandidate>>;
+    // 蔵書を一括で登録し、行ごとの結果を返す
+    async fn import(&self, event: ImportBooks) -> AppResult<Vec<BookImportResult>>;
     async fn find_all(&self, options: BookListOptions) -> AppResult<PaginatedList<Book>>;
+    // カーソルによるページネーション。options の offset は使わない
+    async fn find_all_by_cursor(
+        &self,
+        options: BookListOptions,
+        cursor: Option<PageCursor>,
+    ) -> AppResult<CursorPaginatedList<Book>>;
     async fn find_by_id(&self, book_id: BookId) -> AppResult<Option<Book>>;
+    // 指定した ID の蔵書を指定した順に取得する。存在しない ID と削除済みの蔵書は含めない
+    async fn find_all_by_ids(&self, book_ids: &[BookId]) -> AppResult<Vec<Book>>;
+    // すべての蔵書を登録順に、DB から読み出しながら返す
+    fn export_all(&self) -> BookStream;
+    // 書誌情報を変更した場合は、変更後の内容を新しい版として記録する
     async fn update(&self, event: UpdateBook) -> AppResult<()>;
+    // 蔵書の版の一覧を、新しい順に取得する
+    async fn find_revisions(&self, book_id: BookId) -> AppResult<Vec<BookRevision>>;
+    // 指定した版の内容に戻し、それを新しい版として記録する
+    async fn revert(&self, event: RevertBook) -> AppResult<()>;
+    // 蔵書は論理削除し、一覧や貸出の対象から外す。貸出中の蔵書は削除できない
     async fn delete(&self, event: DeleteBook) -> AppResult<()>;
+    // 論理削除された蔵書の一覧を、削除日時の新しい順に取得する
+    async fn find_deleted(&self, limit: i64, offset: i64) -> AppResult<PaginatedList<Book>>;
+    async fn restore(&self, event: RestoreBook) -> AppResult<()>;
+    // 論理削除された蔵書のみ完全に削除できる
+    async fn purge(&self, event: PurgeBook) -> AppResult<()>;
+    // 蔵書の冊子の一覧を取得する
+    async fn find_copies(&self, book_id: BookId) -> AppResult<Vec<BookCopy>>;
+    // 蔵書に冊子を追加する。追加・削除の権限は蔵書の変更と同じく BookPermission で判定する
+    async fn add_copy(&self, event: CreateBookCopy) -> AppResult<CopyId>;
+    async fn update_copy_location(&self, event: UpdateBookCopyLocation) -> AppResult<()>;
+    async fn update_copy_condition(&self, event: UpdateBookCopyCondition) -> AppResult<()>;
+    // 貸出の対象から外した冊子は、貸出可能な冊子の数に含めず、貸し出しもしない
+    async fn update_copy_withdrawal(&self, event: UpdateBookCopyWithdrawal) -> AppResult<()>;
+    // 貸出中の冊子は削除できない
+    async fn delete_copy(&self, event: DeleteBookCopy) -> AppResult<()>;
 }
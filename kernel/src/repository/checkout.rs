@@ -1,12 +1,14 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shared::error::AppResult;
 
 use crate::model::{
     checkout::{
-        event::{CreateCheckout, UpdateReturned},
-        Checkout,
+        event::{CreateCheckout, CreateHold, CreateRenewal, DeleteHold, UpdateReturned},
+        Checkout, CheckoutRenewal,
     },
     id::{BookId, UserId},
+    list::{CursorOptions, CursorPaginatedList},
 };
 
 #[mockall::automock]
@@ -14,12 +16,40 @@ use crate::model::{
 pub trait CheckoutRepository: Send + Sync {
     // 貸出操作
     async fn create(&self, event: CreateCheckout) -> AppResult<()>;
+    // 延長操作
+    async fn renew(&self, event: CreateRenewal) -> AppResult<CheckoutRenewal>;
+    // 予約操作
+    async fn create_hold(&self, event: CreateHold) -> AppResult<()>;
+    // 予約の取り消し操作
+    async fn delete_hold(&self, event: DeleteHold) -> AppResult<()>;
     // 返却操作
     async fn update_returned(&self, event: UpdateReturned) -> AppResult<()>;
     // すべての未返却の貸出情報を取得する
     async fn find_unreturned_all(&self) -> AppResult<Vec<Checkout>>;
+    // 返却期限が now より前の未返却の貸出情報を取得する
+    async fn find_overdue_all(&self, now: DateTime<Utc>) -> AppResult<Vec<Checkout>>;
     // ユーザー ID に紐づく未返却の貸出情報を取得する
     async fn find_unreturned_by_user_id(&self, user_id: UserId) -> AppResult<Vec<Checkout>>;
     // 蔵書の貸出履歴（返却済みも含む）
     async fn find_history_by_book_id(&self, book_id: BookId) -> AppResult<Vec<Checkout>>;
+    // 以下は上記の一覧取得をカーソルによるページネーションで行うもの
+    async fn find_unreturned_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
+    async fn find_overdue_all_by_cursor(
+        &self,
+        now: DateTime<Utc>,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
+    async fn find_unreturned_by_user_id_by_cursor(
+        &self,
+        user_id: UserId,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
+    async fn find_history_by_book_id_by_cursor(
+        &self,
+        book_id: BookId,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<Checkout>>;
 }
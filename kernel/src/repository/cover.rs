@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    cover::{
+        event::{DeleteCover, UploadCover},
+        CoverImage, CoverImageSize,
+    },
+    id::BookId,
+};
+
+// 蔵書の表紙画像の保存先
+#[mockall::automock]
+#[async_trait]
+pub trait CoverImageRepository: Send + Sync {
+    // 画像を検証してサムネイルを生成し、元画像とあわせて保存する
+    // すでに表紙画像がある場合は置き換える
+    async fn store(&self, event: UploadCover) -> AppResult<()>;
+    // 表紙画像が登録されていない場合は None を返す
+    async fn find(&self, book_id: BookId, size: CoverImageSize) -> AppResult<Option<CoverImage>>;
+    async fn delete(&self, event: DeleteCover) -> AppResult<()>;
+    // 削除された蔵書の画像ファイルを片付ける。所有者の確認は行わない
+    async fn purge(&self, book_id: BookId) -> AppResult<()>;
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    damage::{
+        event::{CreateDamageReport, ResolveDamageReport, UploadDamagePhoto},
+        DamagePhoto, DamageReport, DamageReportStatus,
+    },
+    id::DamageReportId,
+};
+
+// 冊子の破損の報告
+#[mockall::automock]
+#[async_trait]
+pub trait DamageReportRepository: Send + Sync {
+    async fn create(&self, event: CreateDamageReport) -> AppResult<DamageReport>;
+    async fn find_by_id(&self, damage_report_id: DamageReportId)
+        -> AppResult<Option<DamageReport>>;
+    // 指定した状態の報告を、報告日時の古い順に取得する
+    async fn find_all(&self, status: DamageReportStatus) -> AppResult<Vec<DamageReport>>;
+    // すでに写真が添付されている場合は置き換える
+    async fn store_photo(&self, event: UploadDamagePhoto) -> AppResult<()>;
+    // 写真が添付されていない場合は None を返す
+    async fn find_photo(&self, damage_report_id: DamageReportId) -> AppResult<Option<DamagePhoto>>;
+    async fn resolve(&self, event: ResolveDamageReport) -> AppResult<()>;
+}
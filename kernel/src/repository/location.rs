@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    id::LocationId,
+    location::{
+        event::{CreateLocation, DeleteLocation, UpdateLocation},
+        Location,
+    },
+};
+
+#[mockall::automock]
+#[async_trait]
+pub trait LocationRepository: Send + Sync {
+    // 階層をたどった順に並べて返す
+    async fn find_all(&self) -> AppResult<Vec<Location>>;
+    async fn find_by_id(&self, location_id: LocationId) -> AppResult<Option<Location>>;
+    async fn create(&self, event: CreateLocation) -> AppResult<Location>;
+    async fn update(&self, event: UpdateLocation) -> AppResult<()>;
+    // 配下に場所がある場合は削除できない。置かれていた冊子の場所は未設定に戻る
+    async fn delete(&self, event: DeleteLocation) -> AppResult<()>;
+}
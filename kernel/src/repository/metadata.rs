@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{book::metadata::BookMetadata, isbn::Isbn};
+
+// ISBN から書誌情報を引く外部サービス
+#[mockall::automock]
+#[async_trait]
+pub trait BookMetadataProvider: Send + Sync {
+    // 該当する書誌情報がない場合は None を返す
+    async fn lookup(&self, isbn: &Isbn) -> AppResult<Option<BookMetadata>>;
+}
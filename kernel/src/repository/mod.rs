@@ -1,5 +1,15 @@
 pub mod auth;
+pub mod author;
 pub mod book;
 pub mod checkout;
+pub mod cover;
+pub mod damage;
 pub mod health;
+pub mod location;
+pub mod metadata;
+pub mod recommendation;
+pub mod review;
+pub mod series;
+pub mod tag;
+pub mod transfer;
 pub mod user;
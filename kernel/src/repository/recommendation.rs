@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    id::{BookId, UserId},
+    recommendation::RecommendedBook,
+};
+
+// 貸出の履歴から集計した、一緒に借りられている蔵書とおすすめの蔵書
+// 集計は refresh で定期的に行い、取得時は集計済みの結果を返す
+#[mockall::automock]
+#[async_trait]
+pub trait RecommendationRepository: Send + Sync {
+    // 貸し出し中・返却済みの貸出をもとに集計し直す
+    async fn refresh(&self) -> AppResult<()>;
+    // 指定した蔵書と同じユーザーに借りられている蔵書を、関連の強い順に取得する
+    async fn find_related(&self, book_id: BookId, limit: i64) -> AppResult<Vec<RecommendedBook>>;
+    // ユーザーがまだ借りたことのない蔵書を、おすすめの順に取得する
+    async fn find_for_user(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RecommendedBook>>;
+}
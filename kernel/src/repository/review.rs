@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    id::BookId,
+    list::PaginatedList,
+    review::{
+        event::{CreateReview, DeleteReview, UpdateReview},
+        BookReview, ReviewListOptions,
+    },
+};
+
+#[mockall::automock]
+#[async_trait]
+pub trait ReviewRepository: Send + Sync {
+    async fn create(&self, event: CreateReview) -> AppResult<BookReview>;
+    // 蔵書のレビューを新しい順に取得する
+    async fn find_by_book_id(
+        &self,
+        book_id: BookId,
+        options: ReviewListOptions,
+    ) -> AppResult<PaginatedList<BookReview>>;
+    async fn update(&self, event: UpdateReview) -> AppResult<BookReview>;
+    async fn delete(&self, event: DeleteReview) -> AppResult<()>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{id::SeriesId, series::Series};
+
+#[mockall::automock]
+#[async_trait]
+pub trait SeriesRepository: Send + Sync {
+    // 削除されていない蔵書を巻数の順に並べて返す
+    async fn find_by_id(&self, series_id: SeriesId) -> AppResult<Option<Series>>;
+}
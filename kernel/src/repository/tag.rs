@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::tag::{
+    event::{CreateTag, DeleteTag, UpdateTag},
+    Tag,
+};
+
+#[mockall::automock]
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn find_all(&self) -> AppResult<Vec<Tag>>;
+    async fn create(&self, event: CreateTag) -> AppResult<Tag>;
+    async fn update(&self, event: UpdateTag) -> AppResult<()>;
+    // タグを削除すると、蔵書に付けられていたそのタグも外れる
+    async fn delete(&self, event: DeleteTag) -> AppResult<()>;
+}
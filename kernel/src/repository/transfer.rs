@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use shared::error::AppResult;
+
+use crate::model::{
+    id::{BookId, UserId},
+    transfer::{
+        event::{AcceptTransfer, CancelTransfer, ProposeTransfer, ReassignOwner},
+        BookTransfer,
+    },
+};
+
+// 蔵書の所有権の譲渡
+#[mockall::automock]
+#[async_trait]
+pub trait BookTransferRepository: Send + Sync {
+    // 蔵書に対する承認待ちの譲渡を取得する
+    async fn find_by_book_id(&self, book_id: BookId) -> AppResult<Option<BookTransfer>>;
+    // ユーザーが譲渡先になっている承認待ちの譲渡の一覧を取得する
+    async fn find_incoming(&self, user_id: UserId) -> AppResult<Vec<BookTransfer>>;
+    async fn propose(&self, event: ProposeTransfer) -> AppResult<()>;
+    async fn accept(&self, event: AcceptTransfer) -> AppResult<()>;
+    async fn cancel(&self, event: CancelTransfer) -> AppResult<()>;
+    // 管理者が所有者を付け替える。承認待ちの譲渡があれば取り消す
+    async fn reassign(&self, event: ReassignOwner) -> AppResult<()>;
+}
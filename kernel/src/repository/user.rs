@@ -3,6 +3,7 @@ use shared::error::AppResult;
 
 use crate::model::{
     id::UserId,
+    list::{CursorOptions, CursorPaginatedList},
     user::{
         event::{CreateUser, DeleteUser, UpdateUserPassword, UpdateUserRole},
         User,
@@ -14,6 +15,10 @@ use crate::model::{
 pub trait UserRepository: Send + Sync {
     async fn find_current_user(&self, current_user_id: UserId) -> AppResult<Option<User>>;
     async fn find_all(&self) -> AppResult<Vec<User>>;
+    async fn find_all_by_cursor(
+        &self,
+        options: CursorOptions,
+    ) -> AppResult<CursorPaginatedList<User>>;
     async fn create(&self, event: CreateUser) -> AppResult<User>;
     async fn update_password(&self, event: UpdateUserPassword) -> AppResult<()>;
     async fn update_role(&self, event: UpdateUserRole) -> AppResult<()>;
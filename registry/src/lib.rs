@@ -4,13 +4,30 @@ use adapter::{
     database::ConnectionPool,
     redis::RedisClient,
     repository::{
-        auth::AuthRepositoryImpl, book::BookRepositoryImpl, checkout::CheckoutRepositoryImpl,
-        health::HealthCheckRepositoryImpl, user::UserRepositoryImpl,
+        auth::AuthRepositoryImpl,
+        author::AuthorRepositoryImpl,
+        book::BookRepositoryImpl,
+        checkout::CheckoutRepositoryImpl,
+        cover::CoverImageRepositoryImpl,
+        damage::DamageReportRepositoryImpl,
+        health::HealthCheckRepositoryImpl,
+        location::LocationRepositoryImpl,
+        metadata::{CachedMetadataProvider, OpenLibraryMetadataProvider},
+        recommendation::RecommendationRepositoryImpl,
+        review::ReviewRepositoryImpl,
+        series::SeriesRepositoryImpl,
+        tag::TagRepositoryImpl,
+        transfer::BookTransferRepositoryImpl,
+        user::UserRepositoryImpl,
     },
+    storage::LocalStorage,
 };
 use kernel::repository::{
-    auth::AuthRepository, book::BookRepository, checkout::CheckoutRepository,
-    health::HealthCheckRepository, user::UserRepository,
+    auth::AuthRepository, author::AuthorRepository, book::BookRepository,
+    checkout::CheckoutRepository, cover::CoverImageRepository, damage::DamageReportRepository,
+    health::HealthCheckRepository, location::LocationRepository, metadata::BookMetadataProvider,
+    recommendation::RecommendationRepository, review::ReviewRepository, series::SeriesRepository,
+    tag::TagRepository, transfer::BookTransferRepository, user::UserRepository,
 };
 use shared::config::AppConfig;
 
@@ -21,6 +38,16 @@ pub struct AppRegistryImpl {
     auth_repository: Arc<dyn AuthRepository>,
     user_repository: Arc<dyn UserRepository>,
     checkout_repository: Arc<dyn CheckoutRepository>,
+    tag_repository: Arc<dyn TagRepository>,
+    cover_image_repository: Arc<dyn CoverImageRepository>,
+    book_metadata_provider: Arc<dyn BookMetadataProvider>,
+    book_transfer_repository: Arc<dyn BookTransferRepository>,
+    author_repository: Arc<dyn AuthorRepository>,
+    series_repository: Arc<dyn SeriesRepository>,
+    location_repository: Arc<dyn LocationRepository>,
+    damage_report_repository: Arc<dyn DamageReportRepository>,
+    review_repository: Arc<dyn ReviewRepository>,
+    recommendation_repository: Arc<dyn RecommendationRepository>,
 }
 
 impl AppRegistryImpl {
@@ -37,13 +64,46 @@ impl AppRegistryImpl {
             app_config.auth.ttl,
         ));
         let user_repository = Arc::new(UserRepositoryImpl::new(pool.clone()));
-        let checkout_repository = Arc::new(CheckoutRepositoryImpl::new(pool.clone()));
+        let checkout_repository = Arc::new(CheckoutRepositoryImpl::new(
+            pool.clone(),
+            app_config.checkout.loan_period_days,
+            app_config.checkout.max_renewals,
+        ));
+        let tag_repository = Arc::new(TagRepositoryImpl::new(pool.clone()));
+        let storage = Arc::new(LocalStorage::new(&app_config.storage));
+        let cover_image_repository =
+            Arc::new(CoverImageRepositoryImpl::new(pool.clone(), storage.clone()));
+        let damage_report_repository =
+            Arc::new(DamageReportRepositoryImpl::new(pool.clone(), storage));
+        let book_metadata_provider = Arc::new(CachedMetadataProvider::new(
+            Arc::new(OpenLibraryMetadataProvider::new(
+                app_config.book_metadata.endpoint,
+            )),
+            redis_client.clone(),
+            app_config.book_metadata.cache_ttl,
+        ));
+        let book_transfer_repository = Arc::new(BookTransferRepositoryImpl::new(pool.clone()));
+        let author_repository = Arc::new(AuthorRepositoryImpl::new(pool.clone()));
+        let series_repository = Arc::new(SeriesRepositoryImpl::new(pool.clone()));
+        let location_repository = Arc::new(LocationRepositoryImpl::new(pool.clone()));
+        let review_repository = Arc::new(ReviewRepositoryImpl::new(pool.clone()));
+        let recommendation_repository = Arc::new(RecommendationRepositoryImpl::new(pool.clone()));
         Self {
             health_check_repository,
             book_repository,
             auth_repository,
             user_repository,
             checkout_repository,
+            tag_repository,
+            cover_image_repository,
+            book_metadata_provider,
+            book_transfer_repository,
+            author_repository,
+            series_repository,
+            location_repository,
+            damage_report_repository,
+            review_repository,
+            recommendation_repository,
         }
     }
 }
@@ -55,6 +115,16 @@ pub trait AppRegistryExt {
     fn auth_repository(&self) -> Arc<dyn AuthRepository>;
     fn checkout_repository(&self) -> Arc<dyn CheckoutRepository>;
     fn user_repository(&self) -> Arc<dyn UserRepository>;
+    fn tag_repository(&self) -> Arc<dyn TagRepository>;
+    fn cover_image_repository(&self) -> Arc<dyn CoverImageRepository>;
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider>;
+    fn book_transfer_repository(&self) -> Arc<dyn BookTransferRepository>;
+    fn author_repository(&self) -> Arc<dyn AuthorRepository>;
+    fn series_repository(&self) -> Arc<dyn SeriesRepository>;
+    fn location_repository(&self) -> Arc<dyn LocationRepository>;
+    fn damage_report_repository(&self) -> Arc<dyn DamageReportRepository>;
+    fn review_repository(&self) -> Arc<dyn ReviewRepository>;
+    fn recommendation_repository(&self) -> Arc<dyn RecommendationRepository>;
 }
 
 impl AppRegistryExt for AppRegistryImpl {
@@ -77,6 +147,46 @@ impl AppRegistryExt for AppRegistryImpl {
     fn checkout_repository(&self) -> Arc<dyn CheckoutRepository> {
         self.checkout_repository.clone()
     }
+
+    fn tag_repository(&self) -> Arc<dyn TagRepository> {
+        self.tag_repository.clone()
+    }
+
+    fn cover_image_repository(&self) -> Arc<dyn CoverImageRepository> {
+        self.cover_image_repository.clone()
+    }
+
+    fn book_metadata_provider(&self) -> Arc<dyn BookMetadataProvider> {
+        self.book_metadata_provider.clone()
+    }
+
+    fn book_transfer_repository(&self) -> Arc<dyn BookTransferRepository> {
+        self.book_transfer_repository.clone()
+    }
+
+    fn author_repository(&self) -> Arc<dyn AuthorRepository> {
+        self.author_repository.clone()
+    }
+
+    fn series_repository(&self) -> Arc<dyn SeriesRepository> {
+        self.series_repository.clone()
+    }
+
+    fn location_repository(&self) -> Arc<dyn LocationRepository> {
+        self.location_repository.clone()
+    }
+
+    fn damage_report_repository(&self) -> Arc<dyn DamageReportRepository> {
+        self.damage_report_repository.clone()
+    }
+
+    fn review_repository(&self) -> Arc<dyn ReviewRepository> {
+        self.review_repository.clone()
+    }
+
+    fn recommendation_repository(&self) -> Arc<dyn RecommendationRepository> {
+        self.recommendation_repository.clone()
+    }
 }
 
 pub type AppRegistry = Arc<dyn AppRegistryExt + Send + Sync + 'static>;
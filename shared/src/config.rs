@@ -1,9 +1,15 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
 
 pub struct AppConfig {
     pub database: DatabaseConfig,
     pub redis: RedisConfig,
     pub auth: AuthConfig,
+    pub storage: StorageConfig,
+    pub book_metadata: BookMetadataConfig,
+    pub recommendation: RecommendationConfig,
+    pub checkout: CheckoutConfig,
 }
 
 impl AppConfig {
@@ -22,10 +28,42 @@ impl AppConfig {
         let auth = AuthConfig {
             ttl: std::env::var("AUTH_TOKEN_TTL")?.parse::<u64>()?,
         };
+        let storage = StorageConfig {
+            root: std::env::var("STORAGE_ROOT")?.into(),
+        };
+        let book_metadata = BookMetadataConfig {
+            endpoint: std::env::var("BOOK_METADATA_ENDPOINT")?,
+            cache_ttl: std::env::var("BOOK_METADATA_CACHE_TTL")?.parse::<u64>()?,
+        };
+        let recommendation = RecommendationConfig {
+            refresh_interval: std::env::var("RECOMMENDATION_REFRESH_INTERVAL")?.parse::<u64>()?,
+        };
+        // 集計の間隔に 0 は指定できない（tokio::time::interval が panic する）
+        anyhow::ensure!(
+            recommendation.refresh_interval >= 1,
+            "RECOMMENDATION_REFRESH_INTERVAL must be at least 1"
+        );
+        let checkout = CheckoutConfig {
+            loan_period_days: std::env::var("CHECKOUT_LOAN_PERIOD_DAYS")?.parse::<i64>()?,
+            max_renewals: std::env::var("CHECKOUT_MAX_RENEWALS")?.parse::<i64>()?,
+        };
+        // 返却期限が貸出日より前にならないよう、貸出期間は 1 日以上とする
+        anyhow::ensure!(
+            checkout.loan_period_days >= 1,
+            "CHECKOUT_LOAN_PERIOD_DAYS must be at least 1"
+        );
+        anyhow::ensure!(
+            checkout.max_renewals >= 0,
+            "CHECKOUT_MAX_RENEWALS must not be negative"
+        );
         Ok(Self {
             database,
             redis,
             auth,
+            storage,
+            book_metadata,
+            recommendation,
+            checkout,
         })
     }
 }
@@ -46,3 +84,31 @@ pub struct RedisConfig {
 pub struct AuthConfig {
     pub ttl: u64,
 }
+
+// 表紙画像などのファイルを保存するディレクトリ
+pub struct StorageConfig {
+    pub root: PathBuf,
+}
+
+// ISBN から書誌情報を引く外部サービスの設定
+pub struct BookMetadataConfig {
+    // OpenLibrary の Books API 互換のエンドポイント
+    pub endpoint: String,
+    // 問い合わせ結果を Redis にキャッシュする秒数
+    pub cache_ttl: u64,
+}
+
+// 貸出の履歴からおすすめの蔵書を集計する処理の設定
+pub struct RecommendationConfig {
+    // 集計し直す間隔の秒数
+    pub refresh_interval: u64,
+}
+
+// 貸出の設定
+pub struct CheckoutConfig {
+    // 貸出日から返却期限までの日数
+    // 返却期限を導入する前からある貸出には適用されず、マイグレーションで一律 14 日後が設定される
+    pub loan_period_days: i64,
+    // 1 回の貸出で延長できる回数の上限
+    pub max_renewals: i64,
+}
@@ -1,4 +1,5 @@
-use axum::{http::StatusCode, response::IntoResponse};
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -30,24 +31,77 @@ pub enum AppError {
     ForbiddenOperation,
     #[error("{0}")]
     ConversionEntityError(String),
+    #[error("{0}")]
+    InvalidCursorError(String),
+    #[error("{0}")]
+    InvalidIsbnError(String),
+    #[error("{0}")]
+    ConflictError(String),
+    #[error("{0}")]
+    PreconditionFailed(String),
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    #[error("{0}")]
+    NotAcceptable(String),
+    #[error("{0}")]
+    StorageError(#[from] std::io::Error),
+    #[error("{0}")]
+    ExternalServiceError(String),
+}
+
+// バリデーションエラーの際に、どの項目に不備があったかをクライアントへ返すための型
+#[derive(Serialize)]
+struct ValidationErrorResponse {
+    errors: Vec<FieldError>,
+}
+
+#[derive(Serialize)]
+struct FieldError {
+    field: String,
+    message: String,
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
+        if let AppError::ValidationError(report) = &self {
+            let errors = report
+                .iter()
+                .map(|(path, error)| FieldError {
+                    field: path.to_string(),
+                    message: error.message().to_string(),
+                })
+                .collect();
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ValidationErrorResponse { errors }),
+            )
+                .into_response();
+        }
+
         let status_code = match self {
             AppError::UnprocessableEntiry(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::EntityNotFound(_) => StatusCode::NOT_FOUND,
-            AppError::ValidationError(_) | AppError::ConvertToUuidError(_) => {
-                StatusCode::BAD_REQUEST
-            }
+            AppError::ValidationError(_)
+            | AppError::ConvertToUuidError(_)
+            | AppError::InvalidCursorError(_)
+            | AppError::InvalidIsbnError(_) => StatusCode::BAD_REQUEST,
+            AppError::ConflictError(_) => StatusCode::CONFLICT,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
             AppError::UnauthenticatedError | AppError::ForbiddenOperation => StatusCode::FORBIDDEN,
             AppError::UnauthorizedError => StatusCode::UNAUTHORIZED,
+            AppError::ExternalServiceError(e) => {
+                tracing::warn!(error.message = %e, "External service error");
+                StatusCode::BAD_GATEWAY
+            }
             e @ (AppError::TransactionError(_)
             | AppError::SpecificOperationError(_)
             | AppError::NoRowAffectedError(_)
             | AppError::KeyValueStoreError(_)
             | AppError::BcriptError(_)
-            | AppError::ConversionEntityError(_)) => {
+            | AppError::ConversionEntityError(_)
+            | AppError::StorageError(_)) => {
                 tracing::error!(
                     error.cause_chain = ?e,
                     error.message = %e,
@@ -1,6 +1,7 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
     sync::Arc,
+    time::Duration,
 };
 
 use adapter::{database::connect_database_with, redis::RedisClient};
@@ -11,12 +12,12 @@ use api::{
 };
 use axum::{http::Method, Router};
 use opentelemetry::global;
-use registry::AppRegistryImpl;
+use registry::{AppRegistryExt, AppRegistryImpl};
 use shared::{
     config::AppConfig,
     env::{which, Environment},
 };
-use tokio::net::TcpListener;
+use tokio::{net::TcpListener, time::MissedTickBehavior};
 use tower_http::{
     cors::{self, CorsLayer},
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
@@ -44,9 +45,12 @@ async fn bootstrap() -> Result<()> {
     let app_config = AppConfig::new()?;
     let kv = Arc::new(RedisClient::new(&app_config.redis)?);
     let pool = connect_database_with(&app_config.database);
+    let refresh_interval = Duration::from_secs(app_config.recommendation.refresh_interval);
 
     let registry = Arc::new(AppRegistryImpl::new(pool, kv, app_config));
 
+    tokio::spawn(refresh_recommendations(registry.clone(), refresh_interval));
+
     let router = Router::new().merge(v1::routes()).merge(auth::routes());
     #[cfg(debug_assertions)]
     let router = router.merge(Redoc::with_url("/docs", ApiDoc::openapi()));
@@ -83,6 +87,24 @@ async fn bootstrap() -> Result<()> {
         })
 }
 
+// 貸出の履歴からおすすめの蔵書を定期的に集計し直す
+// 起動直後に 1 回集計し、以降は refresh_interval ごとに集計する
+async fn refresh_recommendations(registry: Arc<AppRegistryImpl>, refresh_interval: Duration) {
+    let mut interval = tokio::time::interval(refresh_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        match registry.recommendation_repository().refresh().await {
+            Ok(()) => tracing::info!("おすすめの蔵書を集計しました。"),
+            Err(e) => tracing::error!(
+                error.cause_chain = ?e,
+                error.message = %e,
+                "Failed to refresh recommendations"
+            ),
+        }
+    }
+}
+
 fn init_logger() -> Result<()> {
     let log_level = match which() {
         Environment::Development => "debug",